@@ -1,11 +1,19 @@
 import React from 'react';
 import { BrowserRouter as Router, Routes, Route, useNavigate, useLocation } from 'react-router-dom';
 import { Layout, Menu } from 'antd';
-import { UserOutlined, BookOutlined, SettingOutlined, MessageOutlined } from '@ant-design/icons';
+import { UserOutlined, BookOutlined, SettingOutlined, MessageOutlined, NotificationOutlined, FileTextOutlined, ReadOutlined, QuestionCircleOutlined, ThunderboltOutlined, AuditOutlined, SafetyOutlined, UserAddOutlined } from '@ant-design/icons';
 import StudentManagement from './pages/StudentManagement';
 import GradeManagement from './pages/GradeManagement';
 import ConfigManagement from './pages/ConfigManagement';
 import BulkMessage from './pages/BulkMessage';
+import NotificationManagement from './pages/NotificationManagement';
+import TemplateManagement from './pages/TemplateManagement';
+import KnowledgeManagement from './pages/KnowledgeManagement';
+import FaqManagement from './pages/FaqManagement';
+import GroupRuleManagement from './pages/GroupRuleManagement';
+import ModerationLog from './pages/ModerationLog';
+import ContentIncidentLog from './pages/ContentIncidentLog';
+import JoinRequestManagement from './pages/JoinRequestManagement';
 import './App.css';
 
 const { Header, Content, Sider } = Layout;
@@ -24,6 +32,22 @@ const AppContent: React.FC = () => {
         return '3';
       case '/config':
         return '4';
+      case '/notifications':
+        return '5';
+      case '/templates':
+        return '6';
+      case '/knowledge':
+        return '7';
+      case '/faqs':
+        return '8';
+      case '/group-rules':
+        return '9';
+      case '/moderation-logs':
+        return '10';
+      case '/content-incidents':
+        return '11';
+      case '/join-requests':
+        return '12';
       default:
         return '1';
     }
@@ -49,6 +73,46 @@ const AppContent: React.FC = () => {
       key: '4',
       icon: <SettingOutlined />,
       label: '系统配置',
+    },
+    {
+      key: '5',
+      icon: <NotificationOutlined />,
+      label: '成绩通知',
+    },
+    {
+      key: '6',
+      icon: <FileTextOutlined />,
+      label: '消息模板',
+    },
+    {
+      key: '7',
+      icon: <ReadOutlined />,
+      label: '课程知识库',
+    },
+    {
+      key: '8',
+      icon: <QuestionCircleOutlined />,
+      label: '常见问题',
+    },
+    {
+      key: '9',
+      icon: <ThunderboltOutlined />,
+      label: '群规则',
+    },
+    {
+      key: '10',
+      icon: <AuditOutlined />,
+      label: '群管理日志',
+    },
+    {
+      key: '11',
+      icon: <SafetyOutlined />,
+      label: '内容审核',
+    },
+    {
+      key: '12',
+      icon: <UserAddOutlined />,
+      label: '好友与入群申请',
     }
   ];
 
@@ -66,6 +130,30 @@ const AppContent: React.FC = () => {
       case '4':
         navigate('/config');
         break;
+      case '5':
+        navigate('/notifications');
+        break;
+      case '6':
+        navigate('/templates');
+        break;
+      case '7':
+        navigate('/knowledge');
+        break;
+      case '8':
+        navigate('/faqs');
+        break;
+      case '9':
+        navigate('/group-rules');
+        break;
+      case '10':
+        navigate('/moderation-logs');
+        break;
+      case '11':
+        navigate('/content-incidents');
+        break;
+      case '12':
+        navigate('/join-requests');
+        break;
     }
   };
 
@@ -93,6 +181,14 @@ const AppContent: React.FC = () => {
               <Route path="/grades" element={<GradeManagement />} />
               <Route path="/bulk-message" element={<BulkMessage />} />
               <Route path="/config" element={<ConfigManagement />} />
+              <Route path="/notifications" element={<NotificationManagement />} />
+              <Route path="/templates" element={<TemplateManagement />} />
+              <Route path="/knowledge" element={<KnowledgeManagement />} />
+              <Route path="/faqs" element={<FaqManagement />} />
+              <Route path="/group-rules" element={<GroupRuleManagement />} />
+              <Route path="/moderation-logs" element={<ModerationLog />} />
+              <Route path="/content-incidents" element={<ContentIncidentLog />} />
+              <Route path="/join-requests" element={<JoinRequestManagement />} />
             </Routes>
           </div>
         </Content>
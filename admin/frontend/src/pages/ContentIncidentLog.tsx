@@ -0,0 +1,112 @@
+import React, { useState, useEffect } from 'react';
+import { Table, Button, Tag, message } from 'antd';
+import { ReloadOutlined } from '@ant-design/icons';
+import { ContentIncident } from '../types';
+import { contentIncidentApi } from '../services/api';
+
+const sourceLabels: Record<string, string> = {
+  blocklist: '屏蔽词',
+  regex: '正则',
+  model: '审核模型',
+};
+
+const ContentIncidentLog: React.FC = () => {
+  const [incidents, setIncidents] = useState<ContentIncident[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [pagination, setPagination] = useState({
+    current: 1,
+    pageSize: 10,
+    total: 0,
+  });
+
+  useEffect(() => {
+    fetchIncidents();
+  }, [pagination.current, pagination.pageSize]);
+
+  const fetchIncidents = async () => {
+    setLoading(true);
+    try {
+      const response = await contentIncidentApi.list(pagination.current, pagination.pageSize);
+      setIncidents(response.data.data);
+      setPagination(prev => ({
+        ...prev,
+        total: response.data.total,
+      }));
+    } catch (error) {
+      message.error('获取拦截记录失败');
+    }
+    setLoading(false);
+  };
+
+  const columns = [
+    {
+      title: '时间',
+      dataIndex: 'created_at',
+      key: 'created_at',
+      render: (value: string) => new Date(value).toLocaleString(),
+    },
+    {
+      title: '用户',
+      dataIndex: 'user_id',
+      key: 'user_id',
+    },
+    {
+      title: '群号',
+      dataIndex: 'group_id',
+      key: 'group_id',
+      render: (value?: number | null) => value ?? '私聊',
+    },
+    {
+      title: '环节',
+      dataIndex: 'stage',
+      key: 'stage',
+      render: (value: string) =>
+        value === 'input' ? <Tag color="blue">用户消息</Tag> : <Tag color="purple">模型回复</Tag>,
+    },
+    {
+      title: '命中',
+      key: 'matched',
+      render: (_: any, record: ContentIncident) => (
+        <span>
+          <Tag>{sourceLabels[record.source] || record.source}</Tag>
+          {record.matched}
+        </span>
+      ),
+    },
+    {
+      title: '内容',
+      dataIndex: 'content',
+      key: 'content',
+      ellipsis: true,
+    },
+  ];
+
+  return (
+    <div>
+      <div style={{ marginBottom: 16 }}>
+        <Button icon={<ReloadOutlined />} onClick={fetchIncidents}>
+          刷新
+        </Button>
+      </div>
+
+      <Table
+        columns={columns}
+        dataSource={incidents}
+        rowKey="id"
+        loading={loading}
+        pagination={{
+          ...pagination,
+          onChange: (page: number, pageSize?: number) => {
+            setPagination(prev => ({
+              ...prev,
+              current: page,
+              pageSize: pageSize || 10,
+            }));
+          },
+        }}
+      />
+    </div>
+  );
+};
+
+export default ContentIncidentLog;
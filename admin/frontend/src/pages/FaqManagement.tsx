@@ -0,0 +1,207 @@
+import React, { useState, useEffect } from 'react';
+import { Table, Button, Modal, Form, Input, InputNumber, Select, Switch, Tag, message, Space, Popconfirm } from 'antd';
+import { PlusOutlined, EditOutlined, DeleteOutlined } from '@ant-design/icons';
+import { Faq } from '../types';
+import { faqApi } from '../services/api';
+
+const { TextArea, Search } = Input;
+
+const matchTypes = [
+  { value: 'keyword', label: '关键词', color: 'blue', placeholder: '逗号分隔，全部出现时命中，如：期中,考试,时间' },
+  { value: 'regex', label: '正则', color: 'purple', placeholder: '正则表达式，如：(期中|期末).*(什么时候|哪天)' },
+  { value: 'fuzzy', label: '模糊', color: 'green', placeholder: '标准问法，相似度达到阈值时命中，如：期中考试是什么时候' },
+];
+
+const FaqManagement: React.FC = () => {
+  const [faqs, setFaqs] = useState<Faq[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [modalVisible, setModalVisible] = useState(false);
+  const [editingFaq, setEditingFaq] = useState<Faq | null>(null);
+  const [matchType, setMatchType] = useState<string>('keyword');
+  const [testResult, setTestResult] = useState<string>('');
+  const [form] = Form.useForm();
+
+  useEffect(() => {
+    fetchFaqs();
+  }, []);
+
+  const fetchFaqs = async () => {
+    setLoading(true);
+    try {
+      const response = await faqApi.list();
+      setFaqs(response.data);
+    } catch (error) {
+      message.error('获取常见问题失败');
+    }
+    setLoading(false);
+  };
+
+  const handleAdd = () => {
+    setEditingFaq(null);
+    setMatchType('keyword');
+    form.resetFields();
+    form.setFieldsValue({ match_type: 'keyword', priority: 0, enabled: true });
+    setModalVisible(true);
+  };
+
+  const handleEdit = (faq: Faq) => {
+    setEditingFaq(faq);
+    setMatchType(faq.match_type);
+    form.setFieldsValue(faq);
+    setModalVisible(true);
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await faqApi.delete(id);
+      message.success('删除成功');
+      fetchFaqs();
+    } catch (error) {
+      message.error('删除失败');
+    }
+  };
+
+  const handleSubmit = async (values: any) => {
+    try {
+      if (editingFaq) {
+        await faqApi.update(editingFaq.id, values);
+        message.success('更新成功');
+      } else {
+        await faqApi.create(values);
+        message.success('创建成功');
+      }
+      setModalVisible(false);
+      fetchFaqs();
+    } catch (error: any) {
+      message.error(error.response?.data || '保存失败');
+    }
+  };
+
+  const handleTest = async (text: string) => {
+    if (!text.trim()) {
+      setTestResult('');
+      return;
+    }
+    try {
+      const response = await faqApi.test(text);
+      setTestResult(response.data ? `命中 #${response.data.id}「${response.data.question}」：${response.data.answer}` : '未命中，将交给大模型回复');
+    } catch (error: any) {
+      message.error(error.response?.data || '测试失败');
+    }
+  };
+
+  const columns = [
+    {
+      title: '问题',
+      dataIndex: 'question',
+      key: 'question',
+    },
+    {
+      title: '匹配方式',
+      dataIndex: 'match_type',
+      key: 'match_type',
+      render: (type: string) => {
+        const item = matchTypes.find((t) => t.value === type);
+        return <Tag color={item?.color}>{item?.label || type}</Tag>;
+      },
+    },
+    {
+      title: '匹配规则',
+      dataIndex: 'pattern',
+      key: 'pattern',
+    },
+    {
+      title: '生效范围',
+      dataIndex: 'group_id',
+      key: 'group_id',
+      render: (groupId?: number | null) => (groupId ? `群 ${groupId}` : '全部'),
+    },
+    {
+      title: '优先级',
+      dataIndex: 'priority',
+      key: 'priority',
+    },
+    {
+      title: '命中次数',
+      dataIndex: 'hit_count',
+      key: 'hit_count',
+    },
+    {
+      title: '状态',
+      dataIndex: 'enabled',
+      key: 'enabled',
+      render: (enabled: boolean) => (enabled ? <Tag color="green">启用</Tag> : <Tag>停用</Tag>),
+    },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_: any, record: Faq) => (
+        <Space size="middle">
+          <Button type="link" icon={<EditOutlined />} onClick={() => handleEdit(record)}>
+            编辑
+          </Button>
+          <Popconfirm
+            title="确定删除该问题吗？"
+            onConfirm={() => handleDelete(record.id)}
+            okText="是"
+            cancelText="否"
+          >
+            <Button type="link" danger icon={<DeleteOutlined />}>
+              删除
+            </Button>
+          </Popconfirm>
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <div>
+      <Space style={{ marginBottom: 16 }}>
+        <Button type="primary" icon={<PlusOutlined />} onClick={handleAdd}>
+          添加问题
+        </Button>
+        <Search placeholder="输入一条消息测试匹配结果" enterButton="测试" style={{ width: 400 }} onSearch={handleTest} />
+      </Space>
+      {testResult && <p style={{ color: '#888' }}>{testResult}</p>}
+
+      <Table columns={columns} dataSource={faqs} rowKey="id" loading={loading} pagination={false} />
+
+      <Modal
+        title={editingFaq ? '编辑问题' : '添加问题'}
+        open={modalVisible}
+        width={640}
+        onCancel={() => setModalVisible(false)}
+        onOk={() => form.submit()}
+      >
+        <Form form={form} layout="vertical" onFinish={handleSubmit}>
+          <Form.Item name="question" label="问题" rules={[{ required: true, message: '请输入问题' }]}>
+            <Input placeholder="如：期中考试时间" />
+          </Form.Item>
+          <Form.Item name="match_type" label="匹配方式" rules={[{ required: true }]}>
+            <Select options={matchTypes} onChange={(value: string) => setMatchType(value)} />
+          </Form.Item>
+          <Form.Item name="pattern" label="匹配规则" rules={[{ required: true, message: '请输入匹配规则' }]}>
+            <Input placeholder={matchTypes.find((t) => t.value === matchType)?.placeholder} />
+          </Form.Item>
+          <Form.Item name="answer" label="回复" rules={[{ required: true, message: '请输入回复内容' }]}>
+            <TextArea rows={4} />
+          </Form.Item>
+          <Space>
+            <Form.Item name="group_id" label="生效群号（留空为全部）">
+              <InputNumber style={{ width: 200 }} />
+            </Form.Item>
+            <Form.Item name="priority" label="优先级">
+              <InputNumber />
+            </Form.Item>
+            <Form.Item name="enabled" label="启用" valuePropName="checked">
+              <Switch />
+            </Form.Item>
+          </Space>
+        </Form>
+      </Modal>
+    </div>
+  );
+};
+
+export default FaqManagement;
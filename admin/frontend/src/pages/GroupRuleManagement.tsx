@@ -0,0 +1,240 @@
+import React, { useState, useEffect } from 'react';
+import { Table, Button, Modal, Form, Input, InputNumber, Select, Switch, Tag, message, Space, Popconfirm } from 'antd';
+import { PlusOutlined, EditOutlined, DeleteOutlined } from '@ant-design/icons';
+import { GroupRule } from '../types';
+import { groupRuleApi } from '../services/api';
+
+const { TextArea } = Input;
+
+const triggerTypes = [
+  { value: 'keyword', label: '关键词', color: 'blue', placeholder: '逗号分隔，出现任意一个即命中，如：代写,刷单' },
+  { value: 'regex', label: '正则', color: 'purple', placeholder: '正则表达式，也会匹配分享卡片内容，如：https?://\\S+' },
+  { value: 'segment', label: '消息类型', color: 'cyan', placeholder: '逗号分隔的消息段类型，如：file,json,video' },
+  { value: 'at', label: '@', color: 'orange', placeholder: '被 @ 的 QQ 号，留空表示 @ 任何人，all 表示 @全体成员' },
+];
+
+const actionTypes = [
+  { value: 'reply', label: '回复', color: 'green', placeholder: '回复内容，支持模板变量 {{ sender.name }}、{{ message }}' },
+  { value: 'recall', label: '撤回', color: 'red', placeholder: '无需填写' },
+  { value: 'mute', label: '禁言', color: 'volcano', placeholder: '禁言时长，如：10m、1h、1d' },
+  { value: 'notify_admin', label: '通知管理员', color: 'gold', placeholder: '私聊管理员的内容，如：{{ sender.name }} 在群 {{ group_id }} 发送了：{{ message }}' },
+];
+
+const GroupRuleManagement: React.FC = () => {
+  const [rules, setRules] = useState<GroupRule[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [groupFilter, setGroupFilter] = useState<number | null>(null);
+  const [modalVisible, setModalVisible] = useState(false);
+  const [editingRule, setEditingRule] = useState<GroupRule | null>(null);
+  const [triggerType, setTriggerType] = useState<string>('keyword');
+  const [actionType, setActionType] = useState<string>('reply');
+  const [form] = Form.useForm();
+
+  useEffect(() => {
+    fetchRules();
+  }, [groupFilter]);
+
+  const fetchRules = async () => {
+    setLoading(true);
+    try {
+      const response = await groupRuleApi.list(groupFilter ?? undefined);
+      setRules(response.data);
+    } catch (error) {
+      message.error('获取群规则失败');
+    }
+    setLoading(false);
+  };
+
+  const handleAdd = () => {
+    setEditingRule(null);
+    setTriggerType('keyword');
+    setActionType('reply');
+    form.resetFields();
+    form.setFieldsValue({
+      group_id: groupFilter ?? undefined,
+      trigger_type: 'keyword',
+      action_type: 'reply',
+      priority: 0,
+      stop_processing: false,
+      enabled: true,
+    });
+    setModalVisible(true);
+  };
+
+  const handleEdit = (rule: GroupRule) => {
+    setEditingRule(rule);
+    setTriggerType(rule.trigger_type);
+    setActionType(rule.action_type);
+    form.setFieldsValue(rule);
+    setModalVisible(true);
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await groupRuleApi.delete(id);
+      message.success('删除成功');
+      fetchRules();
+    } catch (error) {
+      message.error('删除失败');
+    }
+  };
+
+  const handleSubmit = async (values: any) => {
+    try {
+      if (editingRule) {
+        await groupRuleApi.update(editingRule.id, values);
+        message.success('更新成功');
+      } else {
+        await groupRuleApi.create(values);
+        message.success('创建成功');
+      }
+      setModalVisible(false);
+      fetchRules();
+    } catch (error: any) {
+      message.error(error.response?.data || '保存失败');
+    }
+  };
+
+  const renderTag = (types: typeof triggerTypes, type: string) => {
+    const item = types.find((t) => t.value === type);
+    return <Tag color={item?.color}>{item?.label || type}</Tag>;
+  };
+
+  const columns = [
+    {
+      title: '群号',
+      dataIndex: 'group_id',
+      key: 'group_id',
+    },
+    {
+      title: '名称',
+      dataIndex: 'name',
+      key: 'name',
+    },
+    {
+      title: '触发条件',
+      key: 'trigger',
+      render: (_: any, record: GroupRule) => (
+        <Space>
+          {renderTag(triggerTypes, record.trigger_type)}
+          <span>{record.trigger_value}</span>
+        </Space>
+      ),
+    },
+    {
+      title: '动作',
+      key: 'action_type',
+      render: (_: any, record: GroupRule) => (
+        <Space>
+          {renderTag(actionTypes, record.action_type)}
+          <span>{record.action_value}</span>
+        </Space>
+      ),
+    },
+    {
+      title: '优先级',
+      dataIndex: 'priority',
+      key: 'priority',
+    },
+    {
+      title: '命中后停止',
+      dataIndex: 'stop_processing',
+      key: 'stop_processing',
+      render: (stop: boolean) => (stop ? '是' : '否'),
+    },
+    {
+      title: '状态',
+      dataIndex: 'enabled',
+      key: 'enabled',
+      render: (enabled: boolean) => (enabled ? <Tag color="green">启用</Tag> : <Tag>停用</Tag>),
+    },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_: any, record: GroupRule) => (
+        <Space size="middle">
+          <Button type="link" icon={<EditOutlined />} onClick={() => handleEdit(record)}>
+            编辑
+          </Button>
+          <Popconfirm
+            title="确定删除该规则吗？"
+            onConfirm={() => handleDelete(record.id)}
+            okText="是"
+            cancelText="否"
+          >
+            <Button type="link" danger icon={<DeleteOutlined />}>
+              删除
+            </Button>
+          </Popconfirm>
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <div>
+      <Space style={{ marginBottom: 16 }}>
+        <Button type="primary" icon={<PlusOutlined />} onClick={handleAdd}>
+          添加规则
+        </Button>
+        <InputNumber
+          placeholder="按群号筛选"
+          style={{ width: 200 }}
+          value={groupFilter}
+          onChange={(value) => setGroupFilter(value)}
+        />
+        <span style={{ color: '#888' }}>规则按优先级从高到低匹配，先于命令和大模型执行，约 1 分钟内生效</span>
+      </Space>
+
+      <Table columns={columns} dataSource={rules} rowKey="id" loading={loading} pagination={false} />
+
+      <Modal
+        title={editingRule ? '编辑规则' : '添加规则'}
+        open={modalVisible}
+        width={640}
+        onCancel={() => setModalVisible(false)}
+        onOk={() => form.submit()}
+      >
+        <Form form={form} layout="vertical" onFinish={handleSubmit}>
+          <Space>
+            <Form.Item name="group_id" label="群号" rules={[{ required: true, message: '请输入群号' }]}>
+              <InputNumber style={{ width: 200 }} />
+            </Form.Item>
+            <Form.Item name="name" label="名称" rules={[{ required: true, message: '请输入规则名称' }]}>
+              <Input placeholder="如：广告链接" style={{ width: 360 }} />
+            </Form.Item>
+          </Space>
+          <Form.Item name="trigger_type" label="触发条件" rules={[{ required: true }]}>
+            <Select options={triggerTypes} onChange={(value: string) => setTriggerType(value)} />
+          </Form.Item>
+          <Form.Item name="trigger_value" label="触发内容">
+            <Input placeholder={triggerTypes.find((t) => t.value === triggerType)?.placeholder} />
+          </Form.Item>
+          <Form.Item name="action_type" label="动作" rules={[{ required: true }]}>
+            <Select options={actionTypes} onChange={(value: string) => setActionType(value)} />
+          </Form.Item>
+          <Form.Item name="action_value" label="动作参数">
+            <TextArea
+              rows={3}
+              disabled={actionType === 'recall'}
+              placeholder={actionTypes.find((t) => t.value === actionType)?.placeholder}
+            />
+          </Form.Item>
+          <Space>
+            <Form.Item name="priority" label="优先级">
+              <InputNumber />
+            </Form.Item>
+            <Form.Item name="stop_processing" label="命中后停止处理" valuePropName="checked">
+              <Switch />
+            </Form.Item>
+            <Form.Item name="enabled" label="启用" valuePropName="checked">
+              <Switch />
+            </Form.Item>
+          </Space>
+        </Form>
+      </Modal>
+    </div>
+  );
+};
+
+export default GroupRuleManagement;
@@ -0,0 +1,199 @@
+import React, { useState, useEffect } from 'react';
+import { Table, Button, Select, Tag, message, Space, Popconfirm, Input } from 'antd';
+import { ReloadOutlined, CheckOutlined, CloseOutlined } from '@ant-design/icons';
+import { JoinRequest } from '../types';
+import { joinRequestApi } from '../services/api';
+
+const { Option } = Select;
+
+const statusColors: Record<string, string> = {
+  pending: 'blue',
+  approving: 'cyan',
+  rejecting: 'orange',
+  approved: 'green',
+  rejected: 'default',
+  failed: 'red',
+};
+
+const statusLabels: Record<string, string> = {
+  pending: '待审批',
+  approving: '同意中',
+  rejecting: '拒绝中',
+  approved: '已同意',
+  rejected: '已拒绝',
+  failed: '失败',
+};
+
+const typeLabel = (record: JoinRequest) => {
+  if (record.request_type === 'friend') {
+    return '好友申请';
+  }
+  return record.sub_type === 'invite' ? `入群邀请（${record.group_id}）` : `加群申请（${record.group_id}）`;
+};
+
+const JoinRequestManagement: React.FC = () => {
+  const [requests, setRequests] = useState<JoinRequest[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [status, setStatus] = useState<string | undefined>('pending');
+  const [rejectReason, setRejectReason] = useState('');
+  const [pagination, setPagination] = useState({
+    current: 1,
+    pageSize: 10,
+    total: 0,
+  });
+
+  useEffect(() => {
+    fetchRequests();
+  }, [pagination.current, pagination.pageSize, status]);
+
+  const fetchRequests = async () => {
+    setLoading(true);
+    try {
+      const response = await joinRequestApi.list(pagination.current, pagination.pageSize, status);
+      setRequests(response.data.data);
+      setPagination(prev => ({
+        ...prev,
+        total: response.data.total,
+      }));
+    } catch (error) {
+      message.error('获取申请列表失败');
+    }
+    setLoading(false);
+  };
+
+  const handleApprove = async (id: number) => {
+    try {
+      await joinRequestApi.approve(id);
+      message.success('已同意，机器人稍后执行');
+      fetchRequests();
+    } catch (error: any) {
+      message.error(error.response?.data || '审批失败');
+    }
+  };
+
+  const handleReject = async (id: number) => {
+    try {
+      await joinRequestApi.reject(id, rejectReason);
+      message.success('已拒绝，机器人稍后执行');
+      setRejectReason('');
+      fetchRequests();
+    } catch (error: any) {
+      message.error(error.response?.data || '审批失败');
+    }
+  };
+
+  const columns = [
+    {
+      title: '时间',
+      dataIndex: 'created_at',
+      key: 'created_at',
+      render: (value: string) => new Date(value).toLocaleString(),
+    },
+    {
+      title: '类型',
+      key: 'request_type',
+      render: (_: any, record: JoinRequest) => typeLabel(record),
+    },
+    {
+      title: 'QQ号',
+      dataIndex: 'user_id',
+      key: 'user_id',
+    },
+    {
+      title: '验证信息',
+      dataIndex: 'comment',
+      key: 'comment',
+      ellipsis: true,
+    },
+    {
+      title: '原因',
+      dataIndex: 'reason',
+      key: 'reason',
+    },
+    {
+      title: '状态',
+      dataIndex: 'status',
+      key: 'status',
+      render: (value: string, record: JoinRequest) => (
+        <Tag color={statusColors[value]} title={record.error || undefined}>
+          {statusLabels[value] || value}
+        </Tag>
+      ),
+    },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_: any, record: JoinRequest) =>
+        record.status === 'pending' && (
+          <Space size="middle">
+            <Button type="link" icon={<CheckOutlined />} onClick={() => handleApprove(record.id)}>
+              同意
+            </Button>
+            <Popconfirm
+              title={
+                <Input
+                  placeholder="拒绝理由（可选）"
+                  value={rejectReason}
+                  onChange={(e) => setRejectReason(e.target.value)}
+                />
+              }
+              onConfirm={() => handleReject(record.id)}
+              okText="拒绝"
+              cancelText="取消"
+            >
+              <Button type="link" danger icon={<CloseOutlined />}>
+                拒绝
+              </Button>
+            </Popconfirm>
+          </Space>
+        ),
+    },
+  ];
+
+  return (
+    <div>
+      <div style={{ marginBottom: 16 }}>
+        <Space>
+          <Select
+            allowClear
+            placeholder="按状态筛选"
+            style={{ width: 160 }}
+            value={status}
+            onChange={(value?: string) => {
+              setStatus(value);
+              setPagination(prev => ({ ...prev, current: 1 }));
+            }}
+          >
+            {Object.entries(statusLabels).map(([value, label]) => (
+              <Option key={value} value={value}>
+                {label}
+              </Option>
+            ))}
+          </Select>
+          <Button icon={<ReloadOutlined />} onClick={fetchRequests}>
+            刷新
+          </Button>
+        </Space>
+      </div>
+
+      <Table
+        columns={columns}
+        dataSource={requests}
+        rowKey="id"
+        loading={loading}
+        pagination={{
+          ...pagination,
+          onChange: (page: number, pageSize?: number) => {
+            setPagination(prev => ({
+              ...prev,
+              current: page,
+              pageSize: pageSize || 10,
+            }));
+          },
+        }}
+      />
+    </div>
+  );
+};
+
+export default JoinRequestManagement;
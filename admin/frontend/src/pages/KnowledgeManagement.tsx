@@ -0,0 +1,153 @@
+import React, { useState, useEffect } from 'react';
+import { Table, Button, Upload, Input, Tag, message, Space, Popconfirm, List } from 'antd';
+import { UploadOutlined, DeleteOutlined } from '@ant-design/icons';
+import { KnowledgeDocument, KnowledgeHit } from '../types';
+import { knowledgeApi } from '../services/api';
+
+const { Search } = Input;
+
+const KnowledgeManagement: React.FC = () => {
+  const [documents, setDocuments] = useState<KnowledgeDocument[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [uploading, setUploading] = useState(false);
+  const [hits, setHits] = useState<KnowledgeHit[]>([]);
+
+  useEffect(() => {
+    fetchDocuments();
+  }, []);
+
+  const fetchDocuments = async () => {
+    setLoading(true);
+    try {
+      const response = await knowledgeApi.list();
+      setDocuments(response.data);
+    } catch (error) {
+      message.error('获取文档列表失败');
+    }
+    setLoading(false);
+  };
+
+  const handleUpload = async (file: File) => {
+    setUploading(true);
+    try {
+      const response = await knowledgeApi.upload(file);
+      message.success(`上传成功，共切分为 ${response.data.chunk_count} 个片段`);
+      fetchDocuments();
+    } catch (error: any) {
+      message.error(error.response?.data || '上传失败');
+    }
+    setUploading(false);
+    // 阻止 Upload 组件自行发送请求
+    return false;
+  };
+
+  const handleDelete = async (id: number) => {
+    try {
+      await knowledgeApi.delete(id);
+      message.success('删除成功');
+      fetchDocuments();
+    } catch (error) {
+      message.error('删除失败');
+    }
+  };
+
+  const handleSearch = async (query: string) => {
+    if (!query.trim()) {
+      setHits([]);
+      return;
+    }
+    try {
+      const response = await knowledgeApi.search(query);
+      setHits(response.data);
+      if (response.data.length === 0) {
+        message.info('没有检索到相关片段');
+      }
+    } catch (error: any) {
+      message.error(error.response?.data || '检索失败');
+    }
+  };
+
+  const columns = [
+    {
+      title: '标题',
+      dataIndex: 'title',
+      key: 'title',
+    },
+    {
+      title: '文件名',
+      dataIndex: 'filename',
+      key: 'filename',
+    },
+    {
+      title: '格式',
+      dataIndex: 'format',
+      key: 'format',
+      render: (format: string) => <Tag>{format}</Tag>,
+    },
+    {
+      title: '片段数',
+      dataIndex: 'chunk_count',
+      key: 'chunk_count',
+    },
+    {
+      title: '上传时间',
+      dataIndex: 'created_at',
+      key: 'created_at',
+      render: (time: string) => new Date(time).toLocaleString(),
+    },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_: any, record: KnowledgeDocument) => (
+        <Popconfirm
+          title="确定删除该文档吗？"
+          onConfirm={() => handleDelete(record.id)}
+          okText="是"
+          cancelText="否"
+        >
+          <Button type="link" danger icon={<DeleteOutlined />}>
+            删除
+          </Button>
+        </Popconfirm>
+      ),
+    },
+  ];
+
+  return (
+    <div>
+      <Space style={{ marginBottom: 16 }}>
+        <Upload accept=".md,.markdown,.pdf,.txt" showUploadList={false} beforeUpload={handleUpload}>
+          <Button type="primary" icon={<UploadOutlined />} loading={uploading}>
+            上传文档
+          </Button>
+        </Upload>
+        <span style={{ color: '#888' }}>支持 Markdown、PDF 和纯文本，上传后约 5 分钟内机器人开始引用</span>
+      </Space>
+
+      <Table
+        columns={columns}
+        dataSource={documents}
+        rowKey="id"
+        loading={loading}
+        pagination={false}
+      />
+
+      <h3 style={{ marginTop: 24 }}>试检索</h3>
+      <Search placeholder="输入学生可能提出的问题" enterButton="检索" onSearch={handleSearch} />
+      <List
+        style={{ marginTop: 16 }}
+        dataSource={hits}
+        renderItem={(hit, index) => (
+          <List.Item>
+            <List.Item.Meta
+              title={`[${index + 1}] ${hit.title}（相似度 ${hit.score.toFixed(2)}）`}
+              description={<pre style={{ whiteSpace: 'pre-wrap', margin: 0 }}>{hit.content}</pre>}
+            />
+          </List.Item>
+        )}
+      />
+    </div>
+  );
+};
+
+export default KnowledgeManagement;
@@ -0,0 +1,131 @@
+import React, { useState, useEffect } from 'react';
+import { Table, Button, InputNumber, Tag, message, Space } from 'antd';
+import { ReloadOutlined } from '@ant-design/icons';
+import { ModerationLog as ModerationLogItem } from '../types';
+import { moderationApi } from '../services/api';
+
+const actionLabels: Record<string, string> = {
+  mute: '禁言',
+  unmute: '解除禁言',
+  kick: '移出群',
+  recall: '撤回',
+  whole_mute: '全员禁言',
+};
+
+const ModerationLog: React.FC = () => {
+  const [logs, setLogs] = useState<ModerationLogItem[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [groupId, setGroupId] = useState<number | null>(null);
+  const [pagination, setPagination] = useState({
+    current: 1,
+    pageSize: 10,
+    total: 0,
+  });
+
+  useEffect(() => {
+    fetchLogs();
+  }, [pagination.current, pagination.pageSize, groupId]);
+
+  const fetchLogs = async () => {
+    setLoading(true);
+    try {
+      const response = await moderationApi.list(pagination.current, pagination.pageSize, groupId ?? undefined);
+      setLogs(response.data.data);
+      setPagination(prev => ({
+        ...prev,
+        total: response.data.total,
+      }));
+    } catch (error) {
+      message.error('获取操作日志失败');
+    }
+    setLoading(false);
+  };
+
+  const columns = [
+    {
+      title: '时间',
+      dataIndex: 'created_at',
+      key: 'created_at',
+      render: (value: string) => new Date(value).toLocaleString(),
+    },
+    {
+      title: '群号',
+      dataIndex: 'group_id',
+      key: 'group_id',
+    },
+    {
+      title: '操作人',
+      dataIndex: 'operator_id',
+      key: 'operator_id',
+    },
+    {
+      title: '操作',
+      dataIndex: 'action',
+      key: 'action',
+      render: (value: string) => <Tag>{actionLabels[value] || value}</Tag>,
+    },
+    {
+      title: '对象',
+      dataIndex: 'target_id',
+      key: 'target_id',
+      render: (value?: number | null) => value ?? '-',
+    },
+    {
+      title: '详情',
+      dataIndex: 'detail',
+      key: 'detail',
+    },
+    {
+      title: '结果',
+      dataIndex: 'success',
+      key: 'success',
+      render: (success: boolean) => (success ? <Tag color="green">成功</Tag> : <Tag color="red">失败</Tag>),
+    },
+    {
+      title: '失败原因',
+      dataIndex: 'error',
+      key: 'error',
+      ellipsis: true,
+    },
+  ];
+
+  return (
+    <div>
+      <div style={{ marginBottom: 16 }}>
+        <Space>
+          <InputNumber
+            placeholder="按群号筛选"
+            style={{ width: 200 }}
+            value={groupId}
+            onChange={(value) => {
+              setGroupId(value);
+              setPagination(prev => ({ ...prev, current: 1 }));
+            }}
+          />
+          <Button icon={<ReloadOutlined />} onClick={fetchLogs}>
+            刷新
+          </Button>
+        </Space>
+      </div>
+
+      <Table
+        columns={columns}
+        dataSource={logs}
+        rowKey="id"
+        loading={loading}
+        pagination={{
+          ...pagination,
+          onChange: (page: number, pageSize?: number) => {
+            setPagination(prev => ({
+              ...prev,
+              current: page,
+              pageSize: pageSize || 10,
+            }));
+          },
+        }}
+      />
+    </div>
+  );
+};
+
+export default ModerationLog;
@@ -0,0 +1,170 @@
+import React, { useState, useEffect } from 'react';
+import { Table, Button, Select, Tag, message, Space } from 'antd';
+import { ReloadOutlined } from '@ant-design/icons';
+import { GradeNotification } from '../types';
+import { notificationApi } from '../services/api';
+
+const { Option } = Select;
+
+const statusColors: Record<string, string> = {
+  pending: 'blue',
+  sent: 'green',
+  failed: 'red',
+};
+
+const statusLabels: Record<string, string> = {
+  pending: '待发送',
+  sent: '已送达',
+  failed: '失败',
+};
+
+const NotificationManagement: React.FC = () => {
+  const [notifications, setNotifications] = useState<GradeNotification[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [status, setStatus] = useState<string | undefined>(undefined);
+  const [pagination, setPagination] = useState({
+    current: 1,
+    pageSize: 10,
+    total: 0,
+  });
+
+  useEffect(() => {
+    fetchNotifications();
+  }, [pagination.current, pagination.pageSize, status]);
+
+  const fetchNotifications = async () => {
+    setLoading(true);
+    try {
+      const response = await notificationApi.list(pagination.current, pagination.pageSize, status);
+      setNotifications(response.data.data);
+      setPagination(prev => ({
+        ...prev,
+        total: response.data.total,
+      }));
+    } catch (error) {
+      message.error('获取通知记录失败');
+    }
+    setLoading(false);
+  };
+
+  const handleRetry = async (id: number) => {
+    try {
+      await notificationApi.retry(id);
+      message.success('已重新加入发送队列');
+      fetchNotifications();
+    } catch (error) {
+      message.error('重试失败');
+    }
+  };
+
+  const columns = [
+    {
+      title: '学生姓名',
+      dataIndex: 'student_name',
+      key: 'student_name',
+    },
+    {
+      title: '学号',
+      dataIndex: 'student_id',
+      key: 'student_id',
+    },
+    {
+      title: 'QQ号',
+      dataIndex: 'qq_number',
+      key: 'qq_number',
+    },
+    {
+      title: '考试名称',
+      dataIndex: 'exam_name',
+      key: 'exam_name',
+    },
+    {
+      title: '成绩',
+      dataIndex: 'score',
+      key: 'score',
+    },
+    {
+      title: '状态',
+      dataIndex: 'status',
+      key: 'status',
+      render: (value: string) => (
+        <Tag color={statusColors[value]}>{statusLabels[value] || value}</Tag>
+      ),
+    },
+    {
+      title: '尝试次数',
+      dataIndex: 'attempts',
+      key: 'attempts',
+    },
+    {
+      title: '失败原因',
+      dataIndex: 'last_error',
+      key: 'last_error',
+      ellipsis: true,
+    },
+    {
+      title: '送达时间',
+      dataIndex: 'sent_at',
+      key: 'sent_at',
+      render: (value?: string) => (value ? new Date(value).toLocaleString() : '-'),
+    },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_: any, record: GradeNotification) => (
+        <Space size="middle">
+          {record.status !== 'sent' && (
+            <Button type="link" icon={<ReloadOutlined />} onClick={() => handleRetry(record.id)}>
+              重试
+            </Button>
+          )}
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <div>
+      <div style={{ marginBottom: 16 }}>
+        <Space>
+          <Select
+            allowClear
+            placeholder="按状态筛选"
+            style={{ width: 160 }}
+            value={status}
+            onChange={(value?: string) => {
+              setStatus(value);
+              setPagination(prev => ({ ...prev, current: 1 }));
+            }}
+          >
+            <Option value="pending">待发送</Option>
+            <Option value="sent">已送达</Option>
+            <Option value="failed">失败</Option>
+          </Select>
+          <Button icon={<ReloadOutlined />} onClick={fetchNotifications}>
+            刷新
+          </Button>
+        </Space>
+      </div>
+
+      <Table
+        columns={columns}
+        dataSource={notifications}
+        rowKey="id"
+        loading={loading}
+        pagination={{
+          ...pagination,
+          onChange: (page: number, pageSize?: number) => {
+            setPagination(prev => ({
+              ...prev,
+              current: page,
+              pageSize: pageSize || 10,
+            }));
+          },
+        }}
+      />
+    </div>
+  );
+};
+
+export default NotificationManagement;
@@ -0,0 +1,152 @@
+import React, { useState, useEffect } from 'react';
+import { Table, Button, Modal, Form, Input, Tag, message, Space, Popconfirm } from 'antd';
+import { EditOutlined, UndoOutlined, EyeOutlined } from '@ant-design/icons';
+import { MessageTemplate } from '../types';
+import { templateApi } from '../services/api';
+
+const { TextArea } = Input;
+
+const TemplateManagement: React.FC = () => {
+  const [templates, setTemplates] = useState<MessageTemplate[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [modalVisible, setModalVisible] = useState(false);
+  const [editingTemplate, setEditingTemplate] = useState<MessageTemplate | null>(null);
+  const [preview, setPreview] = useState<string>('');
+  const [form] = Form.useForm();
+
+  useEffect(() => {
+    fetchTemplates();
+  }, []);
+
+  const fetchTemplates = async () => {
+    setLoading(true);
+    try {
+      const response = await templateApi.list();
+      setTemplates(response.data);
+    } catch (error) {
+      message.error('获取模板列表失败');
+    }
+    setLoading(false);
+  };
+
+  const handleEdit = (template: MessageTemplate) => {
+    setEditingTemplate(template);
+    setPreview('');
+    form.setFieldsValue({ content: template.content });
+    setModalVisible(true);
+  };
+
+  const handleReset = async (key: string) => {
+    try {
+      await templateApi.reset(key);
+      message.success('已恢复默认模板');
+      fetchTemplates();
+    } catch (error) {
+      message.error('恢复失败');
+    }
+  };
+
+  const handlePreview = async () => {
+    try {
+      const response = await templateApi.preview(form.getFieldValue('content'));
+      setPreview(response.data.output);
+    } catch (error: any) {
+      message.error(error.response?.data || '模板渲染失败');
+    }
+  };
+
+  const handleSubmit = async (values: any) => {
+    try {
+      await templateApi.update(editingTemplate!.key, values.content);
+      message.success('保存成功');
+      setModalVisible(false);
+      fetchTemplates();
+    } catch (error: any) {
+      message.error(error.response?.data || '保存失败');
+    }
+  };
+
+  const columns = [
+    {
+      title: '模板',
+      dataIndex: 'key',
+      key: 'key',
+    },
+    {
+      title: '说明',
+      dataIndex: 'description',
+      key: 'description',
+    },
+    {
+      title: '状态',
+      dataIndex: 'customized',
+      key: 'customized',
+      render: (customized: boolean) =>
+        customized ? <Tag color="orange">已自定义</Tag> : <Tag>默认</Tag>,
+    },
+    {
+      title: '操作',
+      key: 'action',
+      render: (_: any, record: MessageTemplate) => (
+        <Space size="middle">
+          <Button type="link" icon={<EditOutlined />} onClick={() => handleEdit(record)}>
+            编辑
+          </Button>
+          {record.customized && (
+            <Popconfirm
+              title="确定恢复为默认模板吗？"
+              onConfirm={() => handleReset(record.key)}
+              okText="是"
+              cancelText="否"
+            >
+              <Button type="link" icon={<UndoOutlined />}>
+                恢复默认
+              </Button>
+            </Popconfirm>
+          )}
+        </Space>
+      ),
+    },
+  ];
+
+  return (
+    <div>
+      <Table
+        columns={columns}
+        dataSource={templates}
+        rowKey="key"
+        loading={loading}
+        pagination={false}
+      />
+
+      <Modal
+        title={`编辑模板 ${editingTemplate?.key || ''}`}
+        open={modalVisible}
+        width={720}
+        onCancel={() => setModalVisible(false)}
+        onOk={() => form.submit()}
+      >
+        <p style={{ color: '#888' }}>{editingTemplate?.description}</p>
+        <Form form={form} layout="vertical" onFinish={handleSubmit}>
+          <Form.Item
+            name="content"
+            label="模板内容"
+            rules={[{ required: true, message: '请输入模板内容' }]}
+          >
+            <TextArea rows={10} style={{ fontFamily: 'monospace' }} />
+          </Form.Item>
+        </Form>
+        <Button icon={<EyeOutlined />} onClick={handlePreview}>
+          使用示例数据预览
+        </Button>
+        {preview && (
+          <pre style={{ marginTop: 16, padding: 12, background: '#f5f5f5', whiteSpace: 'pre-wrap' }}>
+            {preview}
+          </pre>
+        )}
+      </Modal>
+    </div>
+  );
+};
+
+export default TemplateManagement;
@@ -1,5 +1,5 @@
 import axios from 'axios';
-import { Student, Grade, Config, ApiResponse } from '../types';
+import { Student, Grade, Config, GradeNotification, MessageTemplate, KnowledgeDocument, KnowledgeHit, Faq, GroupRule, ModerationLog, ContentIncident, JoinRequest, ApiResponse } from '../types';
 
 const API_BASE_URL = 'http://localhost:8080/api';
 
@@ -56,6 +56,115 @@ export const gradeApi = {
   
   getByStudent: (student_id: number) =>
     api.get<Grade[]>(`/grades/student/${student_id}`),
+
+  import: (grades: Omit<Grade, 'id'>[]) =>
+    api.post('/grades/import', { grades }),
+};
+
+// 成绩通知相关API
+export const notificationApi = {
+  list: (page: number = 1, limit: number = 10, status?: string) =>
+    api.get<ApiResponse<GradeNotification>>(
+      `/notifications?page=${page}&limit=${limit}${status ? `&status=${status}` : ''}`
+    ),
+
+  retry: (id: number) =>
+    api.post<GradeNotification>(`/notifications/${id}/retry`),
+};
+
+// 消息模板相关API
+export const templateApi = {
+  list: () =>
+    api.get<MessageTemplate[]>('/templates'),
+
+  update: (key: string, content: string) =>
+    api.put<string>(`/templates/${key}`, { content }),
+
+  reset: (key: string) =>
+    api.delete(`/templates/${key}`),
+
+  preview: (content: string) =>
+    api.post<{ output: string }>('/templates/preview', { content }),
+};
+
+// 知识库相关API
+export const knowledgeApi = {
+  list: () =>
+    api.get<KnowledgeDocument[]>('/knowledge'),
+
+  // 文件以原始内容上传，格式由扩展名决定
+  upload: (file: File, title?: string) =>
+    api.post<KnowledgeDocument>('/knowledge', file, {
+      params: { filename: file.name, title },
+      headers: { 'Content-Type': 'application/octet-stream' },
+    }),
+
+  delete: (id: number) =>
+    api.delete(`/knowledge/${id}`),
+
+  search: (query: string) =>
+    api.post<KnowledgeHit[]>('/knowledge/search', { query }),
+};
+
+// 常见问题相关API
+export const faqApi = {
+  list: () =>
+    api.get<Faq[]>('/faqs'),
+
+  create: (faq: Partial<Faq>) =>
+    api.post<Faq>('/faqs', faq),
+
+  update: (id: number, faq: Partial<Faq>) =>
+    api.put<Faq>(`/faqs/${id}`, faq),
+
+  delete: (id: number) =>
+    api.delete(`/faqs/${id}`),
+
+  test: (text: string, group_id?: number) =>
+    api.post<Faq | null>('/faqs/test', { text, group_id }),
+};
+
+// 群规则相关API
+export const groupRuleApi = {
+  list: (group_id?: number) =>
+    api.get<GroupRule[]>('/group-rules', { params: { group_id } }),
+
+  create: (rule: Partial<GroupRule>) =>
+    api.post<GroupRule>('/group-rules', rule),
+
+  update: (id: number, rule: Partial<GroupRule>) =>
+    api.put<GroupRule>(`/group-rules/${id}`, rule),
+
+  delete: (id: number) =>
+    api.delete(`/group-rules/${id}`),
+};
+
+// 群管理操作日志API
+export const moderationApi = {
+  list: (page: number = 1, limit: number = 10, group_id?: number) =>
+    api.get<ApiResponse<ModerationLog>>(
+      `/moderation-logs?page=${page}&limit=${limit}${group_id ? `&group_id=${group_id}` : ''}`
+    ),
+};
+
+// 内容审核拦截记录API
+export const contentIncidentApi = {
+  list: (page: number = 1, limit: number = 10) =>
+    api.get<ApiResponse<ContentIncident>>(`/content-incidents?page=${page}&limit=${limit}`),
+};
+
+// 好友与加群申请API
+export const joinRequestApi = {
+  list: (page: number = 1, limit: number = 10, status?: string) =>
+    api.get<ApiResponse<JoinRequest>>(
+      `/join-requests?page=${page}&limit=${limit}${status ? `&status=${status}` : ''}`
+    ),
+
+  approve: (id: number) =>
+    api.post<JoinRequest>(`/join-requests/${id}/approve`),
+
+  reject: (id: number, reason?: string) =>
+    api.post<JoinRequest>(`/join-requests/${id}/reject`, { reason }),
 };
 
 // 配置相关API
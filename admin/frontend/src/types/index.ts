@@ -51,6 +51,120 @@ export interface Config {
     timeout_seconds: number;
     auto_capture_group_messages: boolean;
   };
+  notify?: {
+    grade_enabled: boolean;
+    batch_size: number;
+    batch_interval: string;
+    send_delay: string;
+    max_attempts: number;
+  };
+}
+
+export interface GradeNotification {
+  id: number;
+  grade_id: number;
+  student_id: number;
+  qq_number: number;
+  student_name: string;
+  exam_name: string;
+  score: number;
+  status: 'pending' | 'sent' | 'failed';
+  attempts: number;
+  last_error?: string;
+  created_at: string;
+  sent_at?: string;
+}
+
+export interface MessageTemplate {
+  key: string;
+  description: string;
+  content: string;
+  default_content: string;
+  customized: boolean;
+}
+
+export interface KnowledgeDocument {
+  id: number;
+  title: string;
+  filename: string;
+  format: string;
+  chunk_count: number;
+  created_at: string;
+}
+
+export interface KnowledgeHit {
+  document_id: number;
+  title: string;
+  content: string;
+  score: number;
+}
+
+export interface Faq {
+  id: number;
+  question: string;
+  answer: string;
+  match_type: 'keyword' | 'regex' | 'fuzzy';
+  pattern: string;
+  group_id?: number | null;
+  priority: number;
+  enabled: boolean;
+  hit_count: number;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface GroupRule {
+  id: number;
+  group_id: number;
+  name: string;
+  trigger_type: 'keyword' | 'regex' | 'segment' | 'at';
+  trigger_value: string;
+  action_type: 'reply' | 'recall' | 'mute' | 'notify_admin';
+  action_value: string;
+  priority: number;
+  stop_processing: boolean;
+  enabled: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface ModerationLog {
+  id: number;
+  group_id: number;
+  operator_id: number;
+  action: 'mute' | 'unmute' | 'kick' | 'recall' | 'whole_mute';
+  target_id?: number | null;
+  detail: string;
+  success: boolean;
+  error?: string | null;
+  created_at: string;
+}
+
+export interface ContentIncident {
+  id: number;
+  user_id: number;
+  group_id?: number | null;
+  stage: 'input' | 'output';
+  source: 'blocklist' | 'regex' | 'model';
+  matched: string;
+  content: string;
+  created_at: string;
+}
+
+export interface JoinRequest {
+  id: number;
+  request_type: 'friend' | 'group';
+  sub_type: string;
+  flag: string;
+  user_id: number;
+  group_id?: number | null;
+  comment: string;
+  status: 'pending' | 'approving' | 'rejecting' | 'approved' | 'rejected' | 'failed';
+  reason: string;
+  handled_by?: number | null;
+  error?: string | null;
+  created_at: string;
+  updated_at: string;
 }
 
 export interface ApiResponse<T> {
@@ -0,0 +1,51 @@
+use actix_web::{web, HttpResponse, Result};
+use qqbot_core::{config::get_db, service::faq_service::FaqService};
+use crate::models::faq::*;
+
+pub async fn list_faqs() -> Result<HttpResponse> {
+    let db = get_db().await;
+
+    match FaqService::new(db).list().await {
+        Ok(faqs) => Ok(HttpResponse::Ok().json(faqs)),
+        Err(e) => Ok(HttpResponse::InternalServerError().json(format!("数据库错误: {}", e))),
+    }
+}
+
+pub async fn create_faq(req: web::Json<FaqRequest>) -> Result<HttpResponse> {
+    let db = get_db().await;
+
+    match FaqService::new(db).create(req.into_inner().into()).await {
+        Ok(faq) => Ok(HttpResponse::Created().json(faq)),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("创建失败: {}", e))),
+    }
+}
+
+pub async fn update_faq(path: web::Path<i64>, req: web::Json<FaqRequest>) -> Result<HttpResponse> {
+    let id = path.into_inner();
+    let db = get_db().await;
+
+    match FaqService::new(db).update(id, req.into_inner().into()).await {
+        Ok(faq) => Ok(HttpResponse::Ok().json(faq)),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("更新失败: {}", e))),
+    }
+}
+
+pub async fn delete_faq(path: web::Path<i64>) -> Result<HttpResponse> {
+    let id = path.into_inner();
+    let db = get_db().await;
+
+    match FaqService::new(db).delete(id).await {
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(e) => Ok(HttpResponse::NotFound().json(format!("删除失败: {}", e))),
+    }
+}
+
+/// 测试一条消息会命中哪条 FAQ，返回 null 表示未命中
+pub async fn test_faq(req: web::Json<FaqTestRequest>) -> Result<HttpResponse> {
+    let db = get_db().await;
+
+    match FaqService::new(db).find_answer(&req.text, req.group_id).await {
+        Ok(faq) => Ok(HttpResponse::Ok().json(faq)),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("匹配失败: {}", e))),
+    }
+}
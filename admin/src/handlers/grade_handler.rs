@@ -6,7 +6,7 @@ use qqbot_core::{
 };
 use sea_orm::{EntityTrait, PaginatorTrait, QueryOrder, ColumnTrait, QueryFilter};
 use crate::models::grade::*;
-use crate::handlers::student_handler::{ListQuery, ListResponse};
+use crate::handlers::student_handler::{ImportResponse, ListQuery, ListResponse};
 
 pub async fn list_grades(query: web::Query<ListQuery>) -> Result<HttpResponse> {
     let db = get_db().await;
@@ -16,14 +16,14 @@ pub async fn list_grades(query: web::Query<ListQuery>) -> Result<HttpResponse> {
     
     let paginator = Grade::find()
         .order_by_asc(grade::Column::Id)
-        .paginate(db.as_ref(), limit as u64);
+        .paginate(db.as_ref(), limit);
     
     let total = paginator.num_items().await.map_err(|e| {
         actix_web::error::ErrorInternalServerError(format!("数据库错误: {}", e))
     })?;
     
     let grades = paginator
-        .fetch_page((page - 1) as u64)
+        .fetch_page(page - 1)
         .await
         .map_err(|e| {
             actix_web::error::ErrorInternalServerError(format!("数据库错误: {}", e))
@@ -103,6 +103,35 @@ pub async fn create_grade(req: web::Json<CreateGradeRequest>) -> Result<HttpResp
     }
 }
 
+pub async fn import_grades(req: web::Json<ImportGradesRequest>) -> Result<HttpResponse> {
+    let mut success_count = 0;
+    let mut errors = Vec::new();
+    
+    // 逐条走 create_grade，开启成绩通知时每条成绩都会排队通知学生
+    for (index, grade_req) in req.grades.iter().enumerate() {
+        match grade_service::create_grade(
+            &grade_req.student_name,
+            &grade_req.exam_name,
+            grade_req.course_id,
+            grade_req.course_seq,
+            grade_req.student_id,
+            grade_req.score,
+            &grade_req.category,
+        ).await {
+            Ok(_) => success_count += 1,
+            Err(e) => errors.push(format!("第{}行: {}", index + 1, e)),
+        }
+    }
+    
+    let response = ImportResponse {
+        success_count,
+        total_count: req.grades.len(),
+        errors,
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
 pub async fn update_grade(
     path: web::Path<i64>,
     req: web::Json<UpdateGradeRequest>,
@@ -0,0 +1,41 @@
+use actix_web::{web, HttpResponse, Result};
+use qqbot_core::{config::get_db, service::group_rule_service::GroupRuleService};
+use crate::models::group_rule::*;
+
+pub async fn list_rules(query: web::Query<GroupRuleQuery>) -> Result<HttpResponse> {
+    let db = get_db().await;
+
+    match GroupRuleService::new(db).list(query.group_id).await {
+        Ok(rules) => Ok(HttpResponse::Ok().json(rules)),
+        Err(e) => Ok(HttpResponse::InternalServerError().json(format!("数据库错误: {}", e))),
+    }
+}
+
+pub async fn create_rule(req: web::Json<GroupRuleRequest>) -> Result<HttpResponse> {
+    let db = get_db().await;
+
+    match GroupRuleService::new(db).create(req.into_inner().into()).await {
+        Ok(rule) => Ok(HttpResponse::Created().json(rule)),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("创建失败: {}", e))),
+    }
+}
+
+pub async fn update_rule(path: web::Path<i64>, req: web::Json<GroupRuleRequest>) -> Result<HttpResponse> {
+    let id = path.into_inner();
+    let db = get_db().await;
+
+    match GroupRuleService::new(db).update(id, req.into_inner().into()).await {
+        Ok(rule) => Ok(HttpResponse::Ok().json(rule)),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("更新失败: {}", e))),
+    }
+}
+
+pub async fn delete_rule(path: web::Path<i64>) -> Result<HttpResponse> {
+    let id = path.into_inner();
+    let db = get_db().await;
+
+    match GroupRuleService::new(db).delete(id).await {
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(e) => Ok(HttpResponse::NotFound().json(format!("删除失败: {}", e))),
+    }
+}
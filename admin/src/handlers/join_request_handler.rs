@@ -0,0 +1,45 @@
+use actix_web::{web, HttpResponse, Result};
+use qqbot_core::{config::get_db, service::join_request_service::JoinRequestService};
+use crate::models::join_request::*;
+use crate::handlers::student_handler::ListResponse;
+
+pub async fn list_requests(query: web::Query<JoinRequestQuery>) -> Result<HttpResponse> {
+    let db = get_db().await;
+
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(10);
+    let status = query.status.clone().filter(|s| !s.is_empty());
+
+    let (items, total) = JoinRequestService::new(db)
+        .list(status, page, limit)
+        .await
+        .map_err(|e| actix_web::error::ErrorInternalServerError(format!("数据库错误: {}", e)))?;
+
+    Ok(HttpResponse::Ok().json(ListResponse {
+        data: items,
+        total,
+        page,
+        limit,
+    }))
+}
+
+/// 同意申请，由机器人在下次轮询时执行
+pub async fn approve_request(path: web::Path<i64>) -> Result<HttpResponse> {
+    let id = path.into_inner();
+    let db = get_db().await;
+
+    match JoinRequestService::new(db).decide(id, true, None, None).await {
+        Ok(request) => Ok(HttpResponse::Ok().json(request)),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("审批失败: {}", e))),
+    }
+}
+
+pub async fn reject_request(path: web::Path<i64>, req: web::Json<RejectRequest>) -> Result<HttpResponse> {
+    let id = path.into_inner();
+    let db = get_db().await;
+
+    match JoinRequestService::new(db).decide(id, false, req.into_inner().reason, None).await {
+        Ok(request) => Ok(HttpResponse::Ok().json(request)),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("审批失败: {}", e))),
+    }
+}
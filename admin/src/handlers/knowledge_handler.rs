@@ -0,0 +1,50 @@
+use actix_web::{web, HttpResponse, Result};
+use qqbot_core::{
+    config::{get_db, APPCONFIG},
+    service::knowledge_service::KnowledgeService,
+};
+use crate::models::knowledge::*;
+
+pub async fn list_documents() -> Result<HttpResponse> {
+    let db = get_db().await;
+
+    match KnowledgeService::new(db).list().await {
+        Ok(documents) => Ok(HttpResponse::Ok().json(documents)),
+        Err(e) => Ok(HttpResponse::InternalServerError().json(format!("数据库错误: {}", e))),
+    }
+}
+
+/// 上传文档，请求体为文件原始内容，格式由文件扩展名决定
+pub async fn upload_document(query: web::Query<UploadQuery>, body: web::Bytes) -> Result<HttpResponse> {
+    let query = query.into_inner();
+    let db = get_db().await;
+
+    match KnowledgeService::new(db)
+        .ingest(query.title, query.filename, body.to_vec())
+        .await
+    {
+        Ok(document) => Ok(HttpResponse::Created().json(document)),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("上传失败: {}", e))),
+    }
+}
+
+pub async fn delete_document(path: web::Path<i64>) -> Result<HttpResponse> {
+    let id = path.into_inner();
+    let db = get_db().await;
+
+    match KnowledgeService::new(db).delete(id).await {
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(e) => Ok(HttpResponse::NotFound().json(format!("删除失败: {}", e))),
+    }
+}
+
+/// 试检索，便于确认上传的资料能否被问题命中
+pub async fn search(req: web::Json<SearchRequest>) -> Result<HttpResponse> {
+    let db = get_db().await;
+    let top_k = req.top_k.unwrap_or(APPCONFIG.knowledge.top_k);
+
+    match KnowledgeService::new(db).search(&req.query, top_k).await {
+        Ok(hits) => Ok(HttpResponse::Ok().json(hits)),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("检索失败: {}", e))),
+    }
+}
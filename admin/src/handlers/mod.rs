@@ -1,3 +1,10 @@
 pub mod student_handler;
 pub mod grade_handler;
 pub mod config_handler;
+pub mod notification_handler;
+pub mod template_handler;
+pub mod knowledge_handler;
+pub mod faq_handler;
+pub mod group_rule_handler;
+pub mod moderation_handler;
+pub mod join_request_handler;
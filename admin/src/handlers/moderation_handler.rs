@@ -0,0 +1,47 @@
+use actix_web::{web, HttpResponse, Result};
+use qqbot_core::{
+    config::get_db,
+    service::{content_filter_service::ContentFilterService, moderation_service::ModerationService},
+};
+use crate::models::moderation::*;
+use crate::handlers::student_handler::ListResponse;
+
+/// 群管理操作的审计日志
+pub async fn list_logs(query: web::Query<ModerationLogQuery>) -> Result<HttpResponse> {
+    let db = get_db().await;
+
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(10);
+
+    let (items, total) = ModerationService::new(db)
+        .list(query.group_id, page, limit)
+        .await
+        .map_err(|e| actix_web::error::ErrorInternalServerError(format!("数据库错误: {}", e)))?;
+
+    Ok(HttpResponse::Ok().json(ListResponse {
+        data: items,
+        total,
+        page,
+        limit,
+    }))
+}
+
+/// 内容审核的拦截记录
+pub async fn list_incidents(query: web::Query<ContentIncidentQuery>) -> Result<HttpResponse> {
+    let db = get_db().await;
+
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(10);
+
+    let (items, total) = ContentFilterService::new(db)
+        .list(page, limit)
+        .await
+        .map_err(|e| actix_web::error::ErrorInternalServerError(format!("数据库错误: {}", e)))?;
+
+    Ok(HttpResponse::Ok().json(ListResponse {
+        data: items,
+        total,
+        page,
+        limit,
+    }))
+}
@@ -0,0 +1,57 @@
+use actix_web::{web, HttpResponse, Result};
+use qqbot_core::{
+    config::get_db,
+    models::grade_notification::Model,
+    service::notify_service::GradeNotifyService,
+};
+use crate::models::notification::*;
+use crate::handlers::student_handler::ListResponse;
+
+fn to_dto(n: Model) -> NotificationDto {
+    NotificationDto {
+        id: n.id,
+        grade_id: n.grade_id,
+        student_id: n.student_id,
+        qq_number: n.qq_number,
+        student_name: n.student_name,
+        exam_name: n.exam_name,
+        score: n.score,
+        status: n.status,
+        attempts: n.attempts,
+        last_error: n.last_error,
+        created_at: n.created_at.with_timezone(&chrono::Utc),
+        sent_at: n.sent_at.map(|t| t.with_timezone(&chrono::Utc)),
+    }
+}
+
+pub async fn list_notifications(query: web::Query<NotificationQuery>) -> Result<HttpResponse> {
+    let db = get_db().await;
+    let service = GradeNotifyService::new(db);
+
+    let page = query.page.unwrap_or(1);
+    let limit = query.limit.unwrap_or(10);
+    let status = query.status.clone().filter(|s| !s.is_empty());
+
+    let (items, total) = service.list(status, page, limit).await.map_err(|e| {
+        actix_web::error::ErrorInternalServerError(format!("数据库错误: {}", e))
+    })?;
+
+    let response = ListResponse {
+        data: items.into_iter().map(to_dto).collect::<Vec<_>>(),
+        total,
+        page,
+        limit,
+    };
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub async fn retry_notification(path: web::Path<i64>) -> Result<HttpResponse> {
+    let id = path.into_inner();
+    let db = get_db().await;
+
+    match GradeNotifyService::new(db).retry(id).await {
+        Ok(notification) => Ok(HttpResponse::Ok().json(to_dto(notification))),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("重试失败: {}", e))),
+    }
+}
@@ -14,18 +14,17 @@ pub async fn list_students(query: web::Query<ListQuery>) -> Result<HttpResponse>
     
     let page = query.page.unwrap_or(1);
     let limit = query.limit.unwrap_or(10);
-    let offset = (page - 1) * limit;
     
     let paginator = Student::find()
         .order_by_asc(student::Column::Id)
-        .paginate(db.as_ref(), limit as u64);
+        .paginate(db.as_ref(), limit);
     
     let total = paginator.num_items().await.map_err(|e| {
         actix_web::error::ErrorInternalServerError(format!("数据库错误: {}", e))
     })?;
     
     let students = paginator
-        .fetch_page((page - 1) as u64)
+        .fetch_page(page - 1)
         .await
         .map_err(|e| {
             actix_web::error::ErrorInternalServerError(format!("数据库错误: {}", e))
@@ -170,7 +169,7 @@ pub async fn export_students() -> Result<HttpResponse> {
     })?;
     
     let mut wtr = Writer::from_writer(Cursor::new(Vec::new()));
-    wtr.write_record(&["学号", "姓名", "QQ号", "群号"]).map_err(|e| {
+    wtr.write_record(["学号", "姓名", "QQ号", "群号"]).map_err(|e| {
         actix_web::error::ErrorInternalServerError(format!("CSV写入错误: {}", e))
     })?;
     
@@ -0,0 +1,55 @@
+use actix_web::{web, HttpResponse, Result};
+use qqbot_core::{config::get_db, service::template_service::TemplateService, template};
+use serde_json::json;
+use crate::models::template::*;
+
+pub async fn list_templates() -> Result<HttpResponse> {
+    let db = get_db().await;
+    
+    match TemplateService::new(db).list().await {
+        Ok(templates) => Ok(HttpResponse::Ok().json(templates)),
+        Err(e) => Ok(HttpResponse::InternalServerError().json(format!("数据库错误: {}", e))),
+    }
+}
+
+pub async fn update_template(
+    path: web::Path<String>,
+    req: web::Json<UpdateTemplateRequest>,
+) -> Result<HttpResponse> {
+    let key = path.into_inner();
+    let db = get_db().await;
+    
+    match TemplateService::new(db).save(&key, &req.content).await {
+        Ok(_) => Ok(HttpResponse::Ok().json("模板更新成功")),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("更新失败: {}", e))),
+    }
+}
+
+pub async fn reset_template(path: web::Path<String>) -> Result<HttpResponse> {
+    let key = path.into_inner();
+    let db = get_db().await;
+    
+    match TemplateService::new(db).reset(&key).await {
+        Ok(_) => Ok(HttpResponse::NoContent().finish()),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("重置失败: {}", e))),
+    }
+}
+
+/// 使用示例数据渲染模板，便于在保存前确认效果
+pub async fn preview_template(req: web::Json<PreviewTemplateRequest>) -> Result<HttpResponse> {
+    let sample = json!({
+        "student": { "name": "张三", "student_id": 2023000001i64, "qq_number": 123456789 },
+        "grade": { "id": 1, "exam_name": "Quiz-1", "score": 85, "category": "Quiz-1" },
+        "grades": [
+            { "student_name": "张三", "exam_name": "Quiz-1", "score": 85, "category": "Quiz-1" },
+            { "student_name": "张三", "exam_name": "Mid", "score": 58, "category": "Mid" },
+        ],
+        "message": "示例推送内容",
+        "member": { "qq": 123456789 },
+    });
+    
+    match template::render_str(&req.content, sample) {
+        Ok(output) => Ok(HttpResponse::Ok().json(PreviewTemplateResponse { output })),
+        Err(e) => Ok(HttpResponse::BadRequest().json(format!("{}", e))),
+    }
+}
@@ -1,7 +1,6 @@
 use actix_cors::Cors;
 use actix_files::Files;
 use actix_web::{middleware::Logger, web, App, HttpServer, Result};
-use env_logger;
 use log::info;
 
 mod handlers;
@@ -9,7 +8,7 @@ mod models;
 mod services;
 
 use handlers::*;
-use qqbot_core::config::get_db;
+use qqbot_core::config::{get_db, APPCONFIG};
 
 #[actix_web::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -50,8 +49,59 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             .route("/{id}", web::get().to(grade_handler::get_grade))
                             .route("/{id}", web::put().to(grade_handler::update_grade))
                             .route("/{id}", web::delete().to(grade_handler::delete_grade))
+                            .route("/import", web::post().to(grade_handler::import_grades))
                             .route("/student/{student_id}", web::get().to(grade_handler::get_grades_by_student))
                     )
+                    .service(
+                        web::scope("/notifications")
+                            .route("", web::get().to(notification_handler::list_notifications))
+                            .route("/{id}/retry", web::post().to(notification_handler::retry_notification))
+                    )
+                    .service(
+                        web::scope("/templates")
+                            .route("", web::get().to(template_handler::list_templates))
+                            .route("/preview", web::post().to(template_handler::preview_template))
+                            .route("/{key}", web::put().to(template_handler::update_template))
+                            .route("/{key}", web::delete().to(template_handler::reset_template))
+                    )
+                    .service(
+                        web::scope("/knowledge")
+                            // 文档以原始字节上传，放宽默认的请求体大小限制
+                            .app_data(web::PayloadConfig::new(APPCONFIG.knowledge.max_document_bytes))
+                            .route("", web::get().to(knowledge_handler::list_documents))
+                            .route("", web::post().to(knowledge_handler::upload_document))
+                            .route("/search", web::post().to(knowledge_handler::search))
+                            .route("/{id}", web::delete().to(knowledge_handler::delete_document))
+                    )
+                    .service(
+                        web::scope("/faqs")
+                            .route("", web::get().to(faq_handler::list_faqs))
+                            .route("", web::post().to(faq_handler::create_faq))
+                            .route("/test", web::post().to(faq_handler::test_faq))
+                            .route("/{id}", web::put().to(faq_handler::update_faq))
+                            .route("/{id}", web::delete().to(faq_handler::delete_faq))
+                    )
+                    .service(
+                        web::scope("/group-rules")
+                            .route("", web::get().to(group_rule_handler::list_rules))
+                            .route("", web::post().to(group_rule_handler::create_rule))
+                            .route("/{id}", web::put().to(group_rule_handler::update_rule))
+                            .route("/{id}", web::delete().to(group_rule_handler::delete_rule))
+                    )
+                    .service(
+                        web::scope("/moderation-logs")
+                            .route("", web::get().to(moderation_handler::list_logs))
+                    )
+                    .service(
+                        web::scope("/content-incidents")
+                            .route("", web::get().to(moderation_handler::list_incidents))
+                    )
+                    .service(
+                        web::scope("/join-requests")
+                            .route("", web::get().to(join_request_handler::list_requests))
+                            .route("/{id}/approve", web::post().to(join_request_handler::approve_request))
+                            .route("/{id}/reject", web::post().to(join_request_handler::reject_request))
+                    )
                     .service(
                         web::scope("/config")
                             .route("", web::get().to(config_handler::get_config))
@@ -8,6 +8,8 @@ pub struct ConfigDto {
     pub cache: CacheConfig,
     pub database: DatabaseConfig,
     pub llm: LlmConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<NotifyConfig>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -43,3 +45,12 @@ pub struct LlmConfig {
     pub timeout_seconds: u32,
     pub auto_capture_group_messages: bool,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotifyConfig {
+    pub grade_enabled: bool,
+    pub batch_size: u32,
+    pub batch_interval: String,
+    pub send_delay: String,
+    pub max_attempts: u32,
+}
@@ -0,0 +1,39 @@
+use qqbot_core::repo::faq::FaqFields;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FaqRequest {
+    pub question: String,
+    pub answer: String,
+    pub match_type: String,
+    pub pattern: String,
+    pub group_id: Option<i64>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl From<FaqRequest> for FaqFields {
+    fn from(req: FaqRequest) -> Self {
+        FaqFields {
+            question: req.question,
+            answer: req.answer,
+            match_type: req.match_type,
+            pattern: req.pattern,
+            group_id: req.group_id,
+            priority: req.priority,
+            enabled: req.enabled,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FaqTestRequest {
+    pub text: String,
+    pub group_id: Option<i64>,
+}
@@ -33,3 +33,8 @@ pub struct UpdateGradeRequest {
     pub score: Option<i8>,
     pub category: Option<String>,
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportGradesRequest {
+    pub grades: Vec<CreateGradeRequest>,
+}
@@ -0,0 +1,45 @@
+use qqbot_core::repo::group_rule::GroupRuleFields;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupRuleRequest {
+    pub group_id: i64,
+    pub name: String,
+    pub trigger_type: String,
+    #[serde(default)]
+    pub trigger_value: String,
+    pub action_type: String,
+    #[serde(default)]
+    pub action_value: String,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub stop_processing: bool,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl From<GroupRuleRequest> for GroupRuleFields {
+    fn from(req: GroupRuleRequest) -> Self {
+        GroupRuleFields {
+            group_id: req.group_id,
+            name: req.name,
+            trigger_type: req.trigger_type,
+            trigger_value: req.trigger_value,
+            action_type: req.action_type,
+            action_value: req.action_value,
+            priority: req.priority,
+            stop_processing: req.stop_processing,
+            enabled: req.enabled,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GroupRuleQuery {
+    pub group_id: Option<i64>,
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// 上传文档时的查询参数，文档内容放在请求体中
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UploadQuery {
+    pub filename: String,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchRequest {
+    pub query: String,
+    pub top_k: Option<usize>,
+}
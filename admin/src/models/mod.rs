@@ -1,3 +1,10 @@
 pub mod student;
 pub mod grade;
 pub mod config;
+pub mod notification;
+pub mod template;
+pub mod knowledge;
+pub mod faq;
+pub mod group_rule;
+pub mod moderation;
+pub mod join_request;
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ModerationLogQuery {
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+    pub group_id: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContentIncidentQuery {
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NotificationDto {
+    pub id: i64,
+    pub grade_id: i64,
+    pub student_id: i64,
+    pub qq_number: i64,
+    pub student_name: String,
+    pub exam_name: String,
+    pub score: i8,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationQuery {
+    pub page: Option<u64>,
+    pub limit: Option<u64>,
+    pub status: Option<String>,
+}
@@ -1,6 +1,5 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub struct MessageSender {
@@ -38,7 +37,7 @@ impl MessageSender {
         };
 
         let response = self.client
-            .post(&format!("{}/send_private_msg", self.bot_api_url))
+            .post(format!("{}/send_private_msg", self.bot_api_url))
             .json(&request)
             .send()
             .await?;
@@ -59,7 +58,7 @@ impl MessageSender {
         };
 
         let response = self.client
-            .post(&format!("{}/send_group_msg", self.bot_api_url))
+            .post(format!("{}/send_group_msg", self.bot_api_url))
             .json(&request)
             .send()
             .await?;
@@ -1,4 +1,4 @@
 // Services re-exported from qqbot-core
+// Not wired into any route yet
+#[allow(dead_code)]
 pub mod message_sender;
-
-pub use message_sender::{MessageSender, MessageSenderConfig};
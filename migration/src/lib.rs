@@ -6,6 +6,20 @@ mod m20250422_103936_add_student_id_name;
 mod m20250426_144818_alter_integer;
 mod m20250604_000001_create_user_config;
 mod m20250604_000002_create_group_config;
+mod m20261019_000001_create_grade_notification;
+mod m20261019_000002_create_message_template;
+mod m20261019_000003_add_voice_reply;
+mod m20261019_000004_create_user_memory;
+mod m20261019_000005_create_knowledge;
+mod m20261019_000006_create_faq;
+mod m20261019_000007_create_group_rule;
+mod m20261019_000008_create_moderation_log;
+mod m20261019_000009_create_content_incident;
+mod m20261019_000010_create_join_request;
+mod m20261019_000011_create_onboarding;
+mod m20261019_000012_add_group_command_settings;
+mod m20261019_000013_create_group_command_policy;
+mod m20261019_000014_create_permission_grant;
 
 pub struct Migrator;
 
@@ -19,6 +33,20 @@ impl MigratorTrait for Migrator {
             Box::new(m20250426_144818_alter_integer::Migration),
             Box::new(m20250604_000001_create_user_config::Migration),
             Box::new(m20250604_000002_create_group_config::Migration),
+            Box::new(m20261019_000001_create_grade_notification::Migration),
+            Box::new(m20261019_000002_create_message_template::Migration),
+            Box::new(m20261019_000003_add_voice_reply::Migration),
+            Box::new(m20261019_000004_create_user_memory::Migration),
+            Box::new(m20261019_000005_create_knowledge::Migration),
+            Box::new(m20261019_000006_create_faq::Migration),
+            Box::new(m20261019_000007_create_group_rule::Migration),
+            Box::new(m20261019_000008_create_moderation_log::Migration),
+            Box::new(m20261019_000009_create_content_incident::Migration),
+            Box::new(m20261019_000010_create_join_request::Migration),
+            Box::new(m20261019_000011_create_onboarding::Migration),
+            Box::new(m20261019_000012_add_group_command_settings::Migration),
+            Box::new(m20261019_000013_create_group_command_policy::Migration),
+            Box::new(m20261019_000014_create_permission_grant::Migration),
         ]
     }
 }
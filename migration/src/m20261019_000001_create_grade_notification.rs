@@ -0,0 +1,116 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GradeNotification::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GradeNotification::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeNotification::GradeId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeNotification::StudentId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeNotification::QqNumber)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeNotification::StudentName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeNotification::ExamName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeNotification::Score)
+                            .tiny_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeNotification::Status)
+                            .string()
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(GradeNotification::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(GradeNotification::LastError)
+                            .text()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(GradeNotification::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(GradeNotification::SentAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_grade_notification_status")
+                    .table(GradeNotification::Table)
+                    .col(GradeNotification::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GradeNotification::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GradeNotification {
+    Table,
+    Id,
+    GradeId,
+    StudentId,
+    QqNumber,
+    StudentName,
+    ExamName,
+    Score,
+    Status,
+    Attempts,
+    LastError,
+    CreatedAt,
+    SentAt,
+}
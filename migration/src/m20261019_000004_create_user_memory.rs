@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserMemory::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserMemory::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(UserMemory::UserId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserMemory::Content)
+                            .string_len(500)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserMemory::Source)
+                            .string()
+                            .not_null()
+                            .default("manual"),
+                    )
+                    .col(
+                        ColumnDef::new(UserMemory::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_memory_user_id")
+                    .table(UserMemory::Table)
+                    .col(UserMemory::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserMemory::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum UserMemory {
+    Table,
+    Id,
+    UserId,
+    Content,
+    Source,
+    CreatedAt,
+}
@@ -0,0 +1,111 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(KnowledgeDocument::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(KnowledgeDocument::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(KnowledgeDocument::Title).string().not_null())
+                    .col(ColumnDef::new(KnowledgeDocument::Filename).string().not_null())
+                    .col(ColumnDef::new(KnowledgeDocument::Format).string().not_null())
+                    .col(
+                        ColumnDef::new(KnowledgeDocument::ChunkCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(KnowledgeDocument::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(KnowledgeChunk::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(KnowledgeChunk::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(KnowledgeChunk::DocumentId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(KnowledgeChunk::ChunkIndex).integer().not_null())
+                    .col(ColumnDef::new(KnowledgeChunk::Content).text().not_null())
+                    // 向量按小端 f32 序列存储
+                    .col(ColumnDef::new(KnowledgeChunk::Embedding).blob().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(KnowledgeChunk::Table, KnowledgeChunk::DocumentId)
+                            .to(KnowledgeDocument::Table, KnowledgeDocument::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_knowledge_chunk_document_id")
+                    .table(KnowledgeChunk::Table)
+                    .col(KnowledgeChunk::DocumentId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(KnowledgeChunk::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(KnowledgeDocument::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum KnowledgeDocument {
+    Table,
+    Id,
+    Title,
+    Filename,
+    Format,
+    ChunkCount,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum KnowledgeChunk {
+    Table,
+    Id,
+    DocumentId,
+    ChunkIndex,
+    Content,
+    Embedding,
+}
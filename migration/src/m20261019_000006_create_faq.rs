@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Faq::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Faq::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(Faq::Question).string().not_null())
+                    .col(ColumnDef::new(Faq::Answer).text().not_null())
+                    .col(
+                        ColumnDef::new(Faq::MatchType)
+                            .string()
+                            .not_null()
+                            .default("keyword"),
+                    )
+                    .col(ColumnDef::new(Faq::Pattern).string_len(500).not_null())
+                    // 为空表示对所有群和私聊生效
+                    .col(ColumnDef::new(Faq::GroupId).big_integer().null())
+                    .col(ColumnDef::new(Faq::Priority).integer().not_null().default(0))
+                    .col(ColumnDef::new(Faq::Enabled).boolean().not_null().default(true))
+                    .col(ColumnDef::new(Faq::HitCount).big_integer().not_null().default(0))
+                    .col(
+                        ColumnDef::new(Faq::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(Faq::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Faq::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Faq {
+    Table,
+    Id,
+    Question,
+    Answer,
+    MatchType,
+    Pattern,
+    GroupId,
+    Priority,
+    Enabled,
+    HitCount,
+    CreatedAt,
+    UpdatedAt,
+}
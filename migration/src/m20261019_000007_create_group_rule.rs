@@ -0,0 +1,84 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GroupRule::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GroupRule::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(GroupRule::GroupId).big_integer().not_null())
+                    .col(ColumnDef::new(GroupRule::Name).string().not_null())
+                    .col(ColumnDef::new(GroupRule::TriggerType).string().not_null())
+                    .col(
+                        ColumnDef::new(GroupRule::TriggerValue)
+                            .string_len(500)
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(ColumnDef::new(GroupRule::ActionType).string().not_null())
+                    .col(ColumnDef::new(GroupRule::ActionValue).text().not_null())
+                    .col(ColumnDef::new(GroupRule::Priority).integer().not_null().default(0))
+                    .col(ColumnDef::new(GroupRule::StopProcessing).boolean().not_null().default(false))
+                    .col(ColumnDef::new(GroupRule::Enabled).boolean().not_null().default(true))
+                    .col(
+                        ColumnDef::new(GroupRule::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(GroupRule::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_group_rule_group_id")
+                    .table(GroupRule::Table)
+                    .col(GroupRule::GroupId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GroupRule::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GroupRule {
+    Table,
+    Id,
+    GroupId,
+    Name,
+    TriggerType,
+    TriggerValue,
+    ActionType,
+    ActionValue,
+    Priority,
+    StopProcessing,
+    Enabled,
+    CreatedAt,
+    UpdatedAt,
+}
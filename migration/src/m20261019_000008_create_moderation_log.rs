@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModerationLog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModerationLog::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(ModerationLog::GroupId).big_integer().not_null())
+                    .col(ColumnDef::new(ModerationLog::OperatorId).big_integer().not_null())
+                    .col(ColumnDef::new(ModerationLog::Action).string_len(32).not_null())
+                    .col(ColumnDef::new(ModerationLog::TargetId).big_integer().null())
+                    .col(
+                        ColumnDef::new(ModerationLog::Detail)
+                            .string_len(255)
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(ColumnDef::new(ModerationLog::Success).boolean().not_null())
+                    .col(ColumnDef::new(ModerationLog::Error).string_len(500).null())
+                    .col(
+                        ColumnDef::new(ModerationLog::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_moderation_log_group_id")
+                    .table(ModerationLog::Table)
+                    .col(ModerationLog::GroupId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ModerationLog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ModerationLog {
+    Table,
+    Id,
+    GroupId,
+    OperatorId,
+    Action,
+    TargetId,
+    Detail,
+    Success,
+    Error,
+    CreatedAt,
+}
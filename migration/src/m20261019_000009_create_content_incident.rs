@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ContentIncident::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ContentIncident::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(ContentIncident::UserId).big_integer().not_null())
+                    .col(ColumnDef::new(ContentIncident::GroupId).big_integer().null())
+                    .col(ColumnDef::new(ContentIncident::Stage).string_len(16).not_null())
+                    .col(ColumnDef::new(ContentIncident::Source).string_len(16).not_null())
+                    .col(ColumnDef::new(ContentIncident::Matched).string_len(255).not_null())
+                    .col(ColumnDef::new(ContentIncident::Content).text().not_null())
+                    .col(
+                        ColumnDef::new(ContentIncident::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ContentIncident::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ContentIncident {
+    Table,
+    Id,
+    UserId,
+    GroupId,
+    Stage,
+    Source,
+    Matched,
+    Content,
+    CreatedAt,
+}
@@ -0,0 +1,91 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(JoinRequest::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(JoinRequest::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(JoinRequest::RequestType).string_len(16).not_null())
+                    .col(
+                        ColumnDef::new(JoinRequest::SubType)
+                            .string_len(16)
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(ColumnDef::new(JoinRequest::Flag).string_len(255).not_null())
+                    .col(ColumnDef::new(JoinRequest::UserId).big_integer().not_null())
+                    .col(ColumnDef::new(JoinRequest::GroupId).big_integer().null())
+                    .col(ColumnDef::new(JoinRequest::Comment).text().not_null())
+                    .col(ColumnDef::new(JoinRequest::Status).string_len(16).not_null())
+                    .col(
+                        ColumnDef::new(JoinRequest::Reason)
+                            .string_len(255)
+                            .not_null()
+                            .default(""),
+                    )
+                    .col(ColumnDef::new(JoinRequest::HandledBy).big_integer().null())
+                    .col(ColumnDef::new(JoinRequest::Error).string_len(500).null())
+                    .col(
+                        ColumnDef::new(JoinRequest::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(JoinRequest::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_join_request_status")
+                    .table(JoinRequest::Table)
+                    .col(JoinRequest::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(JoinRequest::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum JoinRequest {
+    Table,
+    Id,
+    RequestType,
+    SubType,
+    Flag,
+    UserId,
+    GroupId,
+    Comment,
+    Status,
+    Reason,
+    HandledBy,
+    Error,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(GroupCommandPolicy::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(GroupCommandPolicy::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(GroupCommandPolicy::GroupId).big_integer().not_null())
+                    .col(ColumnDef::new(GroupCommandPolicy::Command).string_len(32).not_null())
+                    .col(
+                        ColumnDef::new(GroupCommandPolicy::Enabled)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(ColumnDef::new(GroupCommandPolicy::MinRole).string_len(16).null())
+                    .col(ColumnDef::new(GroupCommandPolicy::UpdatedBy).big_integer().null())
+                    .col(
+                        ColumnDef::new(GroupCommandPolicy::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(GroupCommandPolicy::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 每个群的每个命令只有一条策略
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_group_command_policy_group_command")
+                    .table(GroupCommandPolicy::Table)
+                    .col(GroupCommandPolicy::GroupId)
+                    .col(GroupCommandPolicy::Command)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(GroupCommandPolicy::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum GroupCommandPolicy {
+    Table,
+    Id,
+    GroupId,
+    Command,
+    Enabled,
+    MinRole,
+    UpdatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
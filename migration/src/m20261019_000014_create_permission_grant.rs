@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PermissionGrant::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PermissionGrant::Id)
+                            .big_integer()
+                            .not_null()
+                            .primary_key()
+                            .auto_increment(),
+                    )
+                    .col(ColumnDef::new(PermissionGrant::UserId).big_integer().not_null())
+                    .col(ColumnDef::new(PermissionGrant::Role).string_len(16).not_null())
+                    // 教师为课程号，助教为群号，超级管理员为 0
+                    .col(
+                        ColumnDef::new(PermissionGrant::ScopeId)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(PermissionGrant::GrantedBy).big_integer().null())
+                    .col(
+                        ColumnDef::new(PermissionGrant::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_permission_grant_user_role_scope")
+                    .table(PermissionGrant::Table)
+                    .col(PermissionGrant::UserId)
+                    .col(PermissionGrant::Role)
+                    .col(PermissionGrant::ScopeId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PermissionGrant::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PermissionGrant {
+    Table,
+    Id,
+    UserId,
+    Role,
+    ScopeId,
+    GrantedBy,
+    CreatedAt,
+}
@@ -1,35 +1,140 @@
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 
-use kovi::{PluginBuilder as plugin, log::warn, tokio::time::timeout};
+use kovi::{
+    PluginBuilder as plugin, RuntimeBot,
+    log::warn,
+    serde_json::{Value, json},
+    tokio::{self, sync::Notify, time::timeout},
+};
+use qqbot_core::{
+    config::{APPCONFIG, get_db},
+    models::join_request::{Model, STATUS_APPROVING, STATUS_PENDING, TYPE_FRIEND},
+    permission,
+    service::{
+        join_request_service::{self, IncomingRequest, JoinRequestService},
+        onboarding_service::OnboardingService,
+    },
+};
 
 #[kovi::plugin]
 async fn main() {
     let bot = plugin::get_runtime_bot();
+    get_db().await;
+
+    // 自动同意的申请立即唤醒执行，管理员审批的申请按 poll_interval 轮询执行
+    let wake = Arc::new(Notify::new());
+    {
+        let bot = bot.clone();
+        let wake = wake.clone();
+        tokio::spawn(async move {
+            execute_decisions(bot, wake).await;
+        });
+    }
+
     plugin::on_all_request(move |event| {
         let bot = bot.clone();
+        let wake = wake.clone();
         let event = event.clone();
         async move {
-            if event.request_type == "friend" {
-                let flag = event.original_json.get("flag").unwrap().as_str().unwrap();
-                let user_id = event
-                    .original_json
-                    .get("user_id")
-                    .unwrap()
-                    .as_i64()
-                    .unwrap();
-
-                bot.set_friend_add_request(flag, true, "");
-                let res = timeout(Duration::from_secs(3), async {
-                    bot.send_private_msg(user_id, "welcome to use wbot");
-                })
-                .await;
-                if let Err(err) = res {
-                    warn!(
-                        "failed to send message after approval friend request: {}",
-                        err
-                    );
-                }
+            let Some(incoming) = parse_request(&event.request_type, &event.original_json) else {
+                warn!("无法解析的申请: {}", event.original_json);
+                return;
+            };
+            match JoinRequestService::new(get_db().await).receive(incoming).await {
+                Ok(request) if request.status == STATUS_PENDING => notify_admins(&bot, &request).await,
+                Ok(_) => wake.notify_one(),
+                Err(err) => warn!("保存申请失败: {}", err),
             }
         }
     });
 }
+
+fn parse_request(request_type: &str, json: &Value) -> Option<IncomingRequest> {
+    Some(IncomingRequest {
+        request_type: request_type.to_string(),
+        sub_type: json.get("sub_type").and_then(Value::as_str).unwrap_or_default().to_string(),
+        flag: json.get("flag")?.as_str()?.to_string(),
+        user_id: json.get("user_id")?.as_i64()?,
+        group_id: json.get("group_id").and_then(Value::as_i64),
+        comment: json.get("comment").and_then(Value::as_str).unwrap_or_default().to_string(),
+    })
+}
+
+async fn notify_admins(bot: &Arc<RuntimeBot>, request: &Model) {
+    if !APPCONFIG.join_request.notify_admins {
+        return;
+    }
+    let notice = join_request_service::admin_notice(request);
+    for admin in permission::super_admins().await.iter() {
+        bot.send_private_msg(*admin, notice.clone());
+    }
+}
+
+/// 循环执行已审批的申请，结果写回数据库
+async fn execute_decisions(bot: Arc<RuntimeBot>, wake: Arc<Notify>) {
+    let service = JoinRequestService::new(get_db().await);
+
+    loop {
+        match service.next_decided().await {
+            Ok(requests) => {
+                for request in requests {
+                    let result = execute(&bot, &request).await;
+                    if let Err(err) = service.report(&request, result).await {
+                        warn!("记录申请 {} 的处理结果失败: {}", request.id, err);
+                    }
+                }
+            }
+            Err(err) => warn!("读取已审批的申请失败: {}", err),
+        }
+        let _ = timeout(APPCONFIG.join_request.poll_interval, wake.notified()).await;
+    }
+}
+
+async fn execute(bot: &Arc<RuntimeBot>, request: &Model) -> Result<(), String> {
+    let approve = request.status == STATUS_APPROVING;
+    let (api, params) = if request.request_type == TYPE_FRIEND {
+        ("set_friend_add_request", json!({ "flag": request.flag, "approve": approve, "remark": "" }))
+    } else {
+        (
+            "set_group_add_request",
+            json!({
+                "flag": request.flag,
+                "sub_type": request.sub_type,
+                "approve": approve,
+                "reason": request.reason,
+            }),
+        )
+    };
+    bot.send_api_return(api, params)
+        .await
+        .map_err(|ret| format!("{} 调用失败: retcode {}", api, ret.retcode))?;
+
+    if approve && request.request_type == TYPE_FRIEND {
+        let welcome = welcome_message(request.user_id).await;
+        let res = timeout(Duration::from_secs(3), async {
+            bot.send_private_msg(request.user_id, welcome);
+        })
+        .await;
+        if let Err(err) = res {
+            warn!(
+                "failed to send message after approval friend request: {}",
+                err
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 新好友的第一条消息，开启引导时由引导询问学号，后续对话在 reply 插件中继续
+async fn welcome_message(user_id: i64) -> String {
+    if !APPCONFIG.onboarding.enabled {
+        return "welcome to use wbot".to_string();
+    }
+    match OnboardingService::new(get_db().await).start(user_id).await {
+        Ok(message) => message,
+        Err(err) => {
+            warn!("开始新好友引导失败: {}", err);
+            "welcome to use wbot".to_string()
+        }
+    }
+}
@@ -25,6 +25,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         stratege: StrategeType::LlmStrategy,
         model: "test-model".to_string(),
         custom_prompt: Some("你是一个友善的AI助手，会详细回答用户的问题。".to_string()),
+        voice_reply: false,
     };
     BOT_CACHE.insert(user_id, user_data).await;
     
@@ -160,6 +161,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         stratege: StrategeType::LlmStrategy,
         model: "test-model".to_string(),
         custom_prompt: None,
+        voice_reply: false,
     };
     BOT_CACHE.insert(new_user_id, new_user_data).await;
     
@@ -1,16 +1,32 @@
-use kovi::{bot::plugin_builder::event, PluginBuilder as plugin};
+use kovi::{
+    bot::message::{Message, Segment},
+    log::warn,
+    serde_json::{json, Value},
+    PluginBuilder as plugin, RuntimeBot,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
 use qqbot_core::{
-    BOT_CACHE, SessionId, StrategeType,
+    AppError, AppResult, BOT_CACHE, SessionId, StrategeType,
+    action::{self, ActionExecutor, BotAction},
+    cmd::session::{self, SessionKey},
     config::{APPCONFIG, get_db},
     conversation::ConversationManager,
-    reply_strategy::{Env, MessageContent, MessageContext, reply_manager::ReplyManager},
-    service::group_config_service::GROUP_CACHE,
+    reply_strategy::{
+        Delivery, Env, MediaInfo, MessageContent, MessageContext, MessageSegment, QuotedMessage, Reply,
+        cmd::is_command, onebot::to_onebot_segments, reply_manager::ReplyManager,
+    },
+    permission::MemberRole,
+    rules::RuleEvent,
+    speech::{audio::AudioSource, stt, tts},
+    service::{group_config_service::GROUP_CACHE, group_rule_service, onboarding_service},
 };
 
 #[kovi::plugin]
 async fn main() {
     let bot = plugin::get_runtime_bot();
     get_db().await;
+    action::register_executor(Arc::new(KoviExecutor { bot: bot.clone() }));
     
     // 创建回复管理器
     let reply_manager = ReplyManager::new();
@@ -27,34 +43,94 @@ async fn main() {
                 if let Some(group_id) = event.group_id {
                     if let Some(group_data) = GROUP_CACHE.get(&group_id).await {
                         // 使用群组配置
-                        (group_data.stratege, group_data.custom_prompt)
+                        (group_data.stratege, group_data.custom_prompt, group_data.voice_reply)
                     } else {
                         // 群组没有配置，使用用户配置
                         let user_data = BOT_CACHE.get(&sender).await.unwrap_or_default();
-                        (user_data.stratege, user_data.custom_prompt)
+                        (user_data.stratege, user_data.custom_prompt, user_data.voice_reply)
                     }
                 } else {
                     // 没有群组ID，使用用户配置
                     let user_data = BOT_CACHE.get(&sender).await.unwrap_or_default();
-                    (user_data.stratege, user_data.custom_prompt)
+                    (user_data.stratege, user_data.custom_prompt, user_data.voice_reply)
                 }
             } else {
                 // 私聊环境：使用用户配置
                 let user_data = BOT_CACHE.get(&sender).await.unwrap_or_default();
-                (user_data.stratege, user_data.custom_prompt)
+                (user_data.stratege, user_data.custom_prompt, user_data.voice_reply)
             };
 
-            let (strategy, _custom_prompt) = effective_config;
+            let (strategy, _custom_prompt, voice_reply) = effective_config;
 
             // 处理消息 - 解析混合消息内容
-            let message_content = parse_message_content(&event);
+            let message_content = parse_message_content(
+                event.message.iter().map(|m| (m.type_.as_str(), &m.data)),
+            );
+
+            // 群规则先于命令和大模型执行，命中 stop_processing 的规则后不再继续处理
+            let sender_role = MemberRole::parse(event.sender.role.as_deref().unwrap_or("member"));
+            let sender_is_admin = sender_role.is_manager();
+            if let Some(group_id) = event.group_id.filter(|_| event.message_type == "group") {
+                let sender_name = event
+                    .sender
+                    .card
+                    .clone()
+                    .filter(|card| !card.is_empty())
+                    .or_else(|| event.sender.nickname.clone())
+                    .unwrap_or_else(|| format!("用户{}", sender));
+                let outcome = group_rule_service::apply_rules(&RuleEvent {
+                    group_id,
+                    sender_id: sender,
+                    sender_name: &sender_name,
+                    message_id: Some(event.message_id as i64),
+                    content: &message_content,
+                    sender_is_admin,
+                })
+                .await;
+                execute_actions(outcome.actions).await;
+                if outcome.stop {
+                    return;
+                }
+            }
+
+            // 命令前缀和别名按群内设置判断
+            let group_id = event.group_id.filter(|_| event.message_type == "group");
+            let is_cmd = message_content.has_text() && is_command(&message_content, group_id).await;
+
+            // 命令等待输入时，同一用户的下一条非命令消息交给该命令，群聊中不需要再@机器人
+            if message_content.has_text() && !is_cmd {
+                let key = SessionKey::new(sender, group_id);
+                if let Some(result) = session::resume(key, &message_content.get_text()).await {
+                    let reply = match result {
+                        Ok(result) => Reply::from(result),
+                        Err(err) => Reply::in_place(MessageContent::Text(format!("处理失败: {}", err))),
+                    };
+                    let Some(reply_msg) = to_kovi_message(&reply.content) else {
+                        return;
+                    };
+                    match (reply.delivery, group_id) {
+                        (Delivery::Silent, _) => {}
+                        (Delivery::InPlace, Some(group_id)) => bot.send_group_msg(group_id, reply_msg),
+                        _ => bot.send_private_msg(sender, reply_msg),
+                    }
+                    return;
+                }
+            }
+
+            // 正在引导的新好友发来的私聊消息由引导处理，命令仍正常执行
+            if event.message_type == "private" && message_content.has_text() {
+                if let Some(reply) = onboarding_service::intercept(sender, &message_content.get_text()).await {
+                    bot.send_private_msg(sender, reply);
+                    return;
+                }
+            }
+
             let has_text = message_content.has_text();
             let has_image = message_content.has_image();
-            let text_content = message_content.get_text();
+            let has_voice = stt::is_enabled() && message_content.has_record();
 
-            // 如果消息包含文本或图片，则处理
-            if has_text || has_image {
-                let msg = text_content.clone();
+            // 如果消息包含文本、图片或可转写的语音，则处理
+            if has_text || has_image || has_voice {
                 // 检查是否被@了（仅在群聊中有效）
                 let is_mentioned = if event.message_type == "group" {
                     event.message.iter().any(|m| {
@@ -73,14 +149,14 @@ async fn main() {
                 let should_respond = if event.message_type == "private" {
                     // 私聊：根据策略决定
                     match strategy {
-                        StrategeType::CmdStrategy => msg.starts_with(&APPCONFIG.cmd_suffix),
+                        StrategeType::CmdStrategy => is_cmd,
                         StrategeType::LlmStrategy => true,
                     }
                 } else {
                     // 群聊：必须被@才考虑响应
                     if is_mentioned {
                         // 被@了，根据消息内容决定
-                        if msg.starts_with(&APPCONFIG.cmd_suffix) {
+                        if is_cmd {
                             // 以命令前缀开头：按策略处理
                             match strategy {
                                 StrategeType::CmdStrategy => true,
@@ -141,16 +217,10 @@ async fn main() {
                             .unwrap_or_else(|| format!("用户{}", event.sender.user_id)),
                     };
 
-                    // 根据消息类型构建描述
-                    let content_description = if has_image && has_text {
-                        format!("{} [包含图片]", text_content)
-                    } else if has_image {
-                        "[图片消息]".to_string()
-                    } else {
-                        text_content.clone()
-                    };
+                    // 根据消息段构建描述，引用、文件、卡片等以 [类型] 形式记录
+                    let content_description = message_content.describe();
 
-                    ConversationManager::add_user_message_with_info(
+                    ConversationManager::add_user_message_with_id(
                         session_id,
                         content_description,
                         event.sender.user_id,
@@ -158,11 +228,19 @@ async fn main() {
                             Env::Group { .. } => Some(username),
                             Env::Private => None,
                         },
+                        Some(event.message_id as i64),
                     )
                     .await;
                 }
 
                 if should_respond {
+                    // 转写开销较大，只转写需要回复的语音，之后与文本消息一样回复和记录
+                    let message_content = if has_voice {
+                        transcribe_records(&bot, message_content).await
+                    } else {
+                        message_content
+                    };
+
                     let env = if event.message_type == "private" {
                         Env::Private
                     } else if event.message_type == "group" {
@@ -177,13 +255,16 @@ async fn main() {
                         Env::Private
                     };
 
+                    // 引用回复时取回被引用的消息，作为LLM上下文
+                    let quoted = fetch_quoted(&bot, &message_content).await;
+
                     let message_context = MessageContext {
                         env,
                         sender_id: event.sender.user_id,
                         self_id: event.self_id,
+                        message_id: Some(event.message_id as i64),
                         message: message_content.clone(),
-                        group_admin: event.sender.role == Some(String::from("admin"))
-                            || event.sender.role == Some(String::from("owner")),
+                        sender_role,
                         history: vec![], // 未来可以扩展为真实的对话历史
                         sender_name: event
                             .sender
@@ -191,38 +272,85 @@ async fn main() {
                             .clone()
                             .or_else(|| event.sender.nickname.clone())
                             .or_else(|| Some(format!("用户{}", event.sender.user_id))),
+                        quoted,
                     };
                     
                     // 使用统一的回复管理器处理消息
-                    let reply_msg = match reply_manager.reply(&message_context).await {
-                        Ok(MessageContent::Text(res)) => res,
+                    let (reply, delivery) = match reply_manager.reply(&message_context).await {
+                        Ok(Reply { content: MessageContent::Text(res), delivery }) => {
+                            (MessageContent::Text(res.trim().to_string()), delivery)
+                        }
+                        Ok(Reply { content, delivery }) => (content, delivery),
                         Err(err) => {
                             // 根据错误类型提供友好的错误消息
-                            if err.to_string().contains("API") {
+                            let text = if err.to_string().contains("API") {
                                 "抱歉，AI服务暂时不可用，请稍后再试。".to_string()
                             } else if err.to_string().contains("Command") {
                                 "命令执行失败，请检查命令格式。".to_string()
                             } else {
                                 format!("处理失败: {}", err)
-                            }
+                            };
+                            (MessageContent::Text(text), Delivery::InPlace)
                         }
-                        
-                        _ => "收到了不支持的回复类型".to_string(),
                     };
-                    let reply_msg = String::from(reply_msg.trim());
-                    // 发送回复
-                    match event.message_type.as_str() {
-                        "private" => {
-                            bot.send_private_msg(event.sender.user_id, reply_msg);
+                    if delivery == Delivery::Silent {
+                        return;
+                    }
+                    // 大模型回复已写入会话，发出后按文字内容补记 message_id
+                    let reply_text = match &reply {
+                        MessageContent::Text(text) if !is_cmd => Some(text.clone()),
+                        _ => None,
+                    };
+                    // 开启语音回复时，非命令的较短文字回复以语音发送，合成失败时仍发送文字
+                    let reply = match reply {
+                        MessageContent::Text(text) if voice_reply && !is_cmd => {
+                            match tts::synthesize_reply(&text).await {
+                                Ok(Some(voice)) => voice,
+                                Ok(None) => MessageContent::Text(text),
+                                Err(err) => {
+                                    warn!("语音合成失败: {}", err);
+                                    MessageContent::Text(text)
+                                }
+                            }
                         }
-                        "group" => {
-                            if let Some(group_id) = event.group_id {
-                                bot.send_group_msg(group_id, reply_msg);
+                        other => other,
+                    };
+                    let Some(reply_msg) = to_kovi_message(&reply) else {
+                        return;
+                    };
+                    // 发送回复，命令要求私聊时发给发送者
+                    let sent = match (delivery, event.message_type.as_str(), event.group_id) {
+                        (Delivery::Private, _, group_id) => {
+                            let sent = bot.send_private_msg_return(event.sender.user_id, reply_msg).await;
+                            // 不是好友或关闭了临时会话时私聊会失败，在群里提示而不是公开内容
+                            if let (Err(err), Some(group_id)) = (&sent, group_id) {
+                                warn!("私聊 {} 失败: {:?}", event.sender.user_id, err);
+                                let notice = MessageContent::Mixed(vec![
+                                    MessageSegment::At { qq: event.sender.user_id.to_string() },
+                                    MessageSegment::Text {
+                                        text: " 结果需要私聊发送，但私聊发送失败，请先添加机器人为好友后重试".to_string(),
+                                    },
+                                ]);
+                                if let Some(notice) = to_kovi_message(&notice) {
+                                    bot.send_group_msg(group_id, notice);
+                                }
                             }
+                            (SessionId::Private(event.sender.user_id), sent)
                         }
-                        _ => {
-                            // 其他类型的消息，默认发送私聊
-                            bot.send_private_msg(event.sender.user_id, reply_msg);
+                        (_, "group", Some(group_id)) => (
+                            SessionId::Group(group_id),
+                            bot.send_group_msg_return(group_id, reply_msg).await,
+                        ),
+                        (_, "group", None) => return,
+                        // 私聊和其他类型的消息都发送私聊
+                        _ => (
+                            SessionId::Private(event.sender.user_id),
+                            bot.send_private_msg_return(event.sender.user_id, reply_msg).await,
+                        ),
+                    };
+                    if let (Some(text), (session_id, Ok(ret))) = (reply_text, sent) {
+                        if let Some(message_id) = ret.data.get("message_id").and_then(|v| v.as_i64()) {
+                            ConversationManager::set_reply_message_id(session_id, &text, message_id).await;
                         }
                     }
                 }
@@ -231,16 +359,132 @@ async fn main() {
     });
 }
 
-// 解析kovi消息数组为MessageContent
-fn parse_message_content(event: &event::MsgEvent) -> MessageContent {
-    use qqbot_core::reply_strategy::{MessageSegment, ImageInfo};
+// 通过 OneBot 接口执行核心决定的动作
+struct KoviExecutor {
+    bot: Arc<RuntimeBot>,
+}
+
+impl KoviExecutor {
+    async fn call(&self, api: &str, params: Value) -> AppResult<Value> {
+        self.bot
+            .send_api_return(api, params)
+            .await
+            .map(|ret| ret.data)
+            .map_err(|ret| AppError::internal(format!("{} 调用失败: retcode {} {}", api, ret.retcode, ret.data)))
+    }
+}
+
+#[async_trait]
+impl ActionExecutor for KoviExecutor {
+    async fn execute(&self, action: BotAction) -> AppResult<()> {
+        match action {
+            BotAction::SendGroup { group_id, content } => {
+                if let Some(msg) = to_kovi_message(&content) {
+                    self.bot.send_group_msg(group_id, msg);
+                }
+            }
+            BotAction::SendPrivate { user_id, content } => {
+                if let Some(msg) = to_kovi_message(&content) {
+                    self.bot.send_private_msg(user_id, msg);
+                }
+            }
+            BotAction::Recall { message_id } => {
+                self.call("delete_msg", json!({ "message_id": message_id })).await?;
+            }
+            BotAction::Mute { group_id, user_id, duration } => {
+                self.call(
+                    "set_group_ban",
+                    json!({ "group_id": group_id, "user_id": user_id, "duration": duration.as_secs() }),
+                )
+                .await?;
+            }
+            BotAction::Kick { group_id, user_id, reject } => {
+                self.call(
+                    "set_group_kick",
+                    json!({ "group_id": group_id, "user_id": user_id, "reject_add_request": reject }),
+                )
+                .await?;
+            }
+            BotAction::WholeMute { group_id, enable } => {
+                self.call("set_group_whole_ban", json!({ "group_id": group_id, "enable": enable }))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn member_role(&self, group_id: i64, user_id: i64) -> AppResult<MemberRole> {
+        let data = self
+            .call(
+                "get_group_member_info",
+                json!({ "group_id": group_id, "user_id": user_id, "no_cache": true }),
+            )
+            .await?;
+        Ok(MemberRole::parse(data.get("role").and_then(|r| r.as_str()).unwrap_or("member")))
+    }
+}
+
+// 依次执行群规则产生的动作，单个动作失败不影响后续动作
+async fn execute_actions(actions: Vec<BotAction>) {
+    let executor = match action::executor() {
+        Ok(executor) => executor,
+        Err(err) => {
+            warn!("{}", err);
+            return;
+        }
+    };
+    for action in actions {
+        let description = action.describe();
+        if let Err(err) = executor.execute(action).await {
+            warn!("{}失败: {}", description, err);
+        }
+    }
+}
+
+// 将回复内容转换为kovi消息，内容为空时不发送
+fn to_kovi_message(content: &MessageContent) -> Option<Message> {
+    let segments: Vec<Segment> = to_onebot_segments(content)
+        .into_iter()
+        .map(|seg| Segment::new(&seg.type_, seg.data))
+        .collect();
+    if segments.is_empty() {
+        None
+    } else {
+        Some(Message::from(segments))
+    }
+}
+
+// 通过 get_msg 取回被引用的消息，失败时交给核心在会话记录中查找
+async fn fetch_quoted(bot: &Arc<RuntimeBot>, message_content: &MessageContent) -> Option<QuotedMessage> {
+    let reply_id = message_content.reply_id()?;
+    let message_id = reply_id.parse::<i32>().ok()?;
+    let data = bot.get_msg(message_id).await.ok()?.data;
+
+    let segments = data.get("message")?.as_array()?;
+    let content = parse_message_content(segments.iter().filter_map(|seg| {
+        Some((seg.get("type")?.as_str()?, seg.get("data")?))
+    }));
+    let sender = data.get("sender");
+    Some(QuotedMessage {
+        message_id: reply_id.to_string(),
+        sender_id: sender.and_then(|s| s.get("user_id")).and_then(|v| v.as_i64()),
+        sender_name: sender
+            .and_then(|s| str_field(s, "card").filter(|card| !card.is_empty()))
+            .or_else(|| sender.and_then(|s| str_field(s, "nickname"))),
+        content,
+    })
+}
+
+// 解析OneBot消息段数组为MessageContent，参数为 (类型, 数据) 序列
+fn parse_message_content<'a>(message: impl Iterator<Item = (&'a str, &'a Value)>) -> MessageContent {
+    use qqbot_core::reply_strategy::{ImageInfo, MarketFaceInfo};
     
     let mut segments = Vec::new();
     
-    for msg_segment in event.message.iter() {
-        match msg_segment.type_.as_str() {
+    for (type_, data) in message {
+        match type_ {
             "text" => {
-                if let Some(text) = msg_segment.data.get("text").and_then(|v| v.as_str()) {
+                if let Some(text) = data.get("text").and_then(|v| v.as_str()) {
                     if !text.trim().is_empty() {
                         segments.push(MessageSegment::Text { 
                             text: text.to_string() 
@@ -250,54 +494,97 @@ fn parse_message_content(event: &event::MsgEvent) -> MessageContent {
             },
             "image" => {
                 let image_info = ImageInfo {
-                    file: msg_segment.data.get("file")
+                    file: data.get("file")
                         .and_then(|v| v.as_str())
                         .unwrap_or("unknown")
                         .to_string(),
-                    url: msg_segment.data.get("url")
+                    url: data.get("url")
                         .and_then(|v| v.as_str())
                         .map(|s| s.to_string()),
-                    summary: msg_segment.data.get("summary")
+                    summary: data.get("summary")
                         .and_then(|v| v.as_str())
                         .map(|s| s.to_string()),
-                    sub_type: msg_segment.data.get("sub_type")
+                    sub_type: data.get("sub_type")
                         .and_then(|v| v.as_u64())
                         .map(|n| n as u32),
-                    file_size: msg_segment.data.get("file_size")
+                    file_size: data.get("file_size")
                         .and_then(|v| v.as_u64()),
-                    key: msg_segment.data.get("key")
+                    key: data.get("key")
                         .and_then(|v| v.as_str())
                         .map(|s| s.to_string()),
-                    emoji_id: msg_segment.data.get("emoji_id")
+                    emoji_id: data.get("emoji_id")
                         .and_then(|v| v.as_str())
                         .map(|s| s.to_string()),
-                    emoji_package_id: msg_segment.data.get("emoji_package_id")
+                    emoji_package_id: data.get("emoji_package_id")
                         .and_then(|v| v.as_str())
                         .map(|s| s.to_string()),
                 };
                 segments.push(MessageSegment::Image { image_info });
             },
             "at" => {
-                if let Some(qq) = msg_segment.data.get("qq").and_then(|v| v.as_str()) {
+                if let Some(qq) = data.get("qq").and_then(|v| v.as_str()) {
                     segments.push(MessageSegment::At { 
                         qq: qq.to_string() 
                     });
                 }
             },
             "face" => {
-                if let Some(id) = msg_segment.data.get("id").and_then(|v| v.as_u64()) {
+                if let Some(id) = data.get("id").and_then(|v| v.as_u64()) {
                     segments.push(MessageSegment::Face { 
                         id: id as u32 
                     });
                 }
             },
+            "reply" => {
+                if let Some(id) = str_field(data, "id") {
+                    segments.push(MessageSegment::Reply { id });
+                }
+            },
+            "forward" => {
+                if let Some(id) = str_field(data, "id") {
+                    segments.push(MessageSegment::Forward { id });
+                }
+            },
+            "file" => {
+                segments.push(MessageSegment::File { media: parse_media(data) });
+            },
+            "record" => {
+                segments.push(MessageSegment::Record { media: parse_media(data) });
+            },
+            "video" => {
+                segments.push(MessageSegment::Video { media: parse_media(data) });
+            },
+            "json" => {
+                if let Some(data) = str_field(data, "data") {
+                    segments.push(MessageSegment::Json { data });
+                }
+            },
+            "xml" => {
+                if let Some(data) = str_field(data, "data") {
+                    segments.push(MessageSegment::Xml { data });
+                }
+            },
+            "mface" => {
+                let face = MarketFaceInfo {
+                    emoji_id: str_field(data, "emoji_id"),
+                    emoji_package_id: str_field(data, "emoji_package_id"),
+                    key: str_field(data, "key"),
+                    summary: str_field(data, "summary"),
+                    url: str_field(data, "url"),
+                };
+                segments.push(MessageSegment::MFace { face });
+            },
             _ => {
                 // 忽略其他类型的消息段
             }
         }
     }
     
-    // 根据消息内容决定返回类型
+    into_content(segments)
+}
+
+// 根据消息内容决定返回类型
+fn into_content(segments: Vec<MessageSegment>) -> MessageContent {
     if segments.is_empty() {
         // 空消息，返回空文本
         MessageContent::Text(String::new())
@@ -312,3 +599,64 @@ fn parse_message_content(event: &event::MsgEvent) -> MessageContent {
         MessageContent::Mixed(segments)
     }
 }
+
+// 将语音消息段替换为转写出的文字，转写失败的保持原样
+async fn transcribe_records(bot: &Arc<RuntimeBot>, content: MessageContent) -> MessageContent {
+    let MessageContent::Mixed(segments) = content else {
+        return content;
+    };
+    let mut result = Vec::with_capacity(segments.len());
+    for segment in segments {
+        match segment {
+            MessageSegment::Record { media } => match transcribe_record(bot, &media).await {
+                Ok(text) => result.push(MessageSegment::Text { text }),
+                Err(err) => {
+                    warn!("语音转写失败: {}", err);
+                    result.push(MessageSegment::Record { media });
+                }
+            },
+            other => result.push(other),
+        }
+    }
+    into_content(result)
+}
+
+// 优先让协议端通过 get_record 转码为 mp3（silk 解码依赖协议端的 ffmpeg），失败时再直接下载原始文件
+async fn transcribe_record(bot: &Arc<RuntimeBot>, media: &MediaInfo) -> AppResult<String> {
+    let mut sources = Vec::new();
+    if let Ok(ret) = bot
+        .send_api_return("get_record", json!({ "file": media.file, "out_format": "mp3" }))
+        .await
+    {
+        sources.extend(str_field(&ret.data, "base64").map(AudioSource::Base64));
+        sources.extend(str_field(&ret.data, "file").map(AudioSource::Path));
+    }
+    sources.extend(media.url.clone().map(AudioSource::Url));
+
+    let mut last_err = AppError::speech("无法获取语音文件");
+    for source in &sources {
+        match stt::transcribe_voice(source).await {
+            Ok(text) => return Ok(text),
+            Err(err) => last_err = err,
+        }
+    }
+    Err(last_err)
+}
+
+// 读取字符串字段，部分协议端会把 id、大小等以数字形式给出
+fn str_field(data: &Value, key: &str) -> Option<String> {
+    match data.get(key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_media(data: &Value) -> MediaInfo {
+    MediaInfo {
+        file: str_field(data, "file").unwrap_or_else(|| "unknown".to_string()),
+        url: str_field(data, "url"),
+        name: str_field(data, "name"),
+        file_size: str_field(data, "file_size").and_then(|s| s.parse().ok()),
+    }
+}
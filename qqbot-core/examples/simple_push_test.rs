@@ -1,5 +1,5 @@
 use qqbot_core::{
-    cmd::{CMD_REGISTRY, Execute},
+    cmd::{CMD_REGISTRY, CmdContext, Execute},
     config::get_db,
 };
 
@@ -11,10 +11,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     get_db().await;
     
     // 测试push命令解析 - 使用字符串切片而不是临时值
+    let ctx = CmdContext {
+        sender: 123456789,
+        myself: 987654321,
+        ..Default::default()
+    };
     let args = vec![
-        "--sender", "123456789",
-        "--myself", "987654321", 
-        "--env", "private",
         "-g", "111222333",
         "-m", "这是一条测试消息",
         "-l", "111111111", "222222222", "333333333"
@@ -22,10 +24,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     println!("📝 执行push命令");
     
-    match CMD_REGISTRY.execute("push", &args).await {
+    match CMD_REGISTRY.execute(&ctx, "push", &args).await {
         Ok(result) => {
             println!("✅ 命令执行成功:");
-            println!("{}", result.output);
+            println!("{}", result.output());
         }
         Err(e) => {
             println!("❌ 命令执行失败: {}", e);
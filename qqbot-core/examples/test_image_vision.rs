@@ -1,5 +1,6 @@
 use qqbot_core::{
     config::APPCONFIG,
+    permission::MemberRole,
     reply_strategy::{
         llm::SimpleLlmReplyStrategy,
         MessageContent, MessageContext, MessageSegment, ImageInfo, Env, RelyStrategy
@@ -39,10 +40,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         env: Env::Private,
         sender_id: 123456789,
         self_id: 987654321,
+        message_id: None,
         message: message_content,
-        group_admin: false,
+        sender_role: MemberRole::Member,
         history: vec![],
         sender_name: Some("测试用户".to_string()),
+        quoted: None,
     };
     
     // 调用回复策略
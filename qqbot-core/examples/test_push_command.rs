@@ -1,5 +1,5 @@
 use qqbot_core::{
-    cmd::{CMD_REGISTRY, Execute},
+    cmd::{CMD_REGISTRY, CmdContext, Execute},
     config::get_db,
 };
 
@@ -16,12 +16,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let members = vec![111111, 222222, 333333];
     let message = "这是一条测试推送消息";
 
-    // 构建命令参数
+    // 构建命令上下文和参数
+    let ctx = CmdContext {
+        sender: user_id,
+        myself: 987654,
+        ..Default::default()
+    };
+    let group_id_str = group_id.to_string();
     let args = vec![
-        "--sender", &user_id.to_string(),
-        "--myself", "987654",
-        "--env", "private",
-        "-g", &group_id.to_string(),
+        "-g", &group_id_str,
         "-m", message,
         "-l", "111111", "222222", "333333"
     ];
@@ -33,10 +36,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("• 消息内容：{}", message);
 
     // 执行命令
-    match CMD_REGISTRY.execute("push", &args).await {
+    match CMD_REGISTRY.execute(&ctx, "push", &args).await {
         Ok(result) => {
             println!("\n✅ 命令执行成功：");
-            println!("{}", result.output);
+            println!("{}", result.output());
         }
         Err(e) => {
             println!("\n❌ 命令执行失败：{}", e);
@@ -45,19 +48,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // 测试错误情况：在群聊中使用
     println!("\n=== 测试错误情况：在群聊中使用 ===");
+    let group_ctx = CmdContext {
+        group_id,
+        env: "group".to_string(),
+        ..ctx.clone()
+    };
     let group_args = vec![
-        "--sender", &user_id.to_string(),
-        "--myself", "987654",
-        "--group-id", &group_id.to_string(),
-        "--env", "group",
-        "-g", &group_id.to_string(),
+        "-g", &group_id_str,
         "-m", "测试消息",
         "-l", "111111"
     ];
 
-    match CMD_REGISTRY.execute("push", &group_args).await {
+    match CMD_REGISTRY.execute(&group_ctx, "push", &group_args).await {
         Ok(result) => {
-            println!("意外成功：{}", result.output);
+            println!("意外成功：{}", result.output());
         }
         Err(e) => {
             println!("✅ 正确拒绝：{}", e);
@@ -67,9 +71,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 测试帮助信息
     println!("\n=== 测试帮助信息 ===");
     let help_args = vec!["--help"];
-    match CMD_REGISTRY.execute("push", &help_args).await {
+    match CMD_REGISTRY.execute(&ctx, "push", &help_args).await {
         Ok(result) => {
-            println!("帮助信息：\n{}", result.output);
+            println!("帮助信息：\n{}", result.output());
         }
         Err(e) => {
             println!("获取帮助失败：{}", e);
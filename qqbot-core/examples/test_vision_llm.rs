@@ -1,5 +1,6 @@
 // 测试完整的图片识别功能
 use qqbot_core::reply_strategy::{MessageContent, MessageSegment, ImageInfo, MessageContext, Env};
+use qqbot_core::permission::MemberRole;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -35,10 +36,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         env: Env::Private,
         sender_id: 12345,
         self_id: 67890,
+        message_id: None,
         message: mixed_message,
-        group_admin: false,
+        sender_role: MemberRole::Member,
         history: vec![],
         sender_name: Some("测试用户".to_string()),
+        quoted: None,
     };
 
     println!("发送的消息包含:");
@@ -0,0 +1,83 @@
+//! 需要由插件通过 OneBot 接口执行的动作，核心只负责决定做什么
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use once_cell::sync::OnceCell;
+
+use crate::{
+    error::{AppError, AppResult},
+    permission::MemberRole,
+    reply_strategy::MessageContent,
+};
+
+/// QQ 群禁言的最长时长为 30 天
+pub const MAX_MUTE: Duration = Duration::from_secs(30 * 24 * 3600);
+
+#[derive(Debug, Clone)]
+pub enum BotAction {
+    /// 发送群消息
+    SendGroup { group_id: i64, content: MessageContent },
+    /// 发送私聊消息
+    SendPrivate { user_id: i64, content: MessageContent },
+    /// 撤回消息
+    Recall { message_id: i64 },
+    /// 禁言群成员，时长为 0 表示解除禁言
+    Mute { group_id: i64, user_id: i64, duration: Duration },
+    /// 移出群成员，reject 为 true 时拒绝其再次申请加群
+    Kick { group_id: i64, user_id: i64, reject: bool },
+    /// 开启或关闭全员禁言
+    WholeMute { group_id: i64, enable: bool },
+}
+
+impl BotAction {
+    /// 动作的简短描述，用于日志
+    pub fn describe(&self) -> String {
+        match self {
+            BotAction::SendGroup { group_id, .. } => format!("发送群消息到 {}", group_id),
+            BotAction::SendPrivate { user_id, .. } => format!("私聊 {}", user_id),
+            BotAction::Recall { message_id } => format!("撤回消息 {}", message_id),
+            BotAction::Mute { group_id, user_id, duration } => {
+                format!("在群 {} 禁言 {} {}秒", group_id, user_id, duration.as_secs())
+            }
+            BotAction::Kick { group_id, user_id, .. } => format!("将 {} 移出群 {}", user_id, group_id),
+            BotAction::WholeMute { group_id, enable } => {
+                format!("{}群 {} 的全员禁言", if *enable { "开启" } else { "关闭" }, group_id)
+            }
+        }
+    }
+}
+
+/// 解析禁言时长，如 10m、1h30m、1d
+pub fn parse_mute_duration(value: &str) -> AppResult<Duration> {
+    let duration = humantime::parse_duration(value.trim())
+        .map_err(|e| AppError::validation(format!("禁言时长无效: {}", e)))?;
+    if duration.is_zero() || duration > MAX_MUTE {
+        return Err(AppError::validation("禁言时长需在 1 秒到 30 天之间"));
+    }
+    Ok(duration)
+}
+
+/// 动作执行器，由插件在启动时注册，使命令等核心逻辑能够立即执行动作并得到结果
+#[async_trait]
+pub trait ActionExecutor: Send + Sync {
+    async fn execute(&self, action: BotAction) -> AppResult<()>;
+    /// 查询群成员的角色
+    async fn member_role(&self, group_id: i64, user_id: i64) -> AppResult<MemberRole>;
+}
+
+static EXECUTOR: OnceCell<Arc<dyn ActionExecutor>> = OnceCell::new();
+
+/// 注册动作执行器，重复注册时保留第一个
+pub fn register_executor(executor: Arc<dyn ActionExecutor>) {
+    if EXECUTOR.set(executor).is_err() {
+        log::warn!("动作执行器已注册，忽略重复注册");
+    }
+}
+
+pub fn executor() -> AppResult<Arc<dyn ActionExecutor>> {
+    EXECUTOR
+        .get()
+        .cloned()
+        .ok_or_else(|| AppError::internal("动作执行器未注册"))
+}
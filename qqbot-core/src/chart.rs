@@ -0,0 +1,167 @@
+//! 成绩走势图：柱状图为学生每次考试的分数，折线为班级平均分，渲染为 PNG
+
+use std::{collections::HashMap, io::Cursor};
+
+use image::{ExtendedColorType, ImageEncoder, codecs::png::PngEncoder};
+use once_cell::sync::Lazy;
+use plotters::{
+    prelude::*,
+    style::{FontStyle, register_font},
+};
+use sea_orm::Iterable;
+use sea_query::Iden;
+
+use crate::{
+    config::APPCONFIG,
+    error::{AppError, AppResult},
+    models::grade::{Category, Model},
+};
+
+const FONT_FAMILY: &str = "sans-serif";
+
+// 字体只在第一次画图时读取，之后常驻内存
+static FONT: Lazy<Result<(), String>> = Lazy::new(|| {
+    let path = &APPCONFIG.chart.font_path;
+    let bytes = std::fs::read(path).map_err(|err| format!("读取图表字体 {} 失败: {}", path, err))?;
+    register_font(FONT_FAMILY, FontStyle::Normal, Box::leak(bytes.into_boxed_slice()))
+        .map_err(|_| format!("图表字体 {} 无法解析", path))
+});
+
+/// 一次考试在图上的数据
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradePoint {
+    pub exam: String,
+    pub score: f64,
+    // 同课程同考试的班级平均分，没有同学的成绩时为 None
+    pub average: Option<f64>,
+}
+
+/// 按 Quiz-1..Quiz-4、Mid 的顺序整理学生的成绩，其余类型排在最后。
+/// 学生有多门课程时按课程分组，标签带上课程号，避免同名考试的柱子无法区分
+pub fn grade_points(grades: &[Model], class_grades: &[Model]) -> Vec<GradePoint> {
+    let mut totals: HashMap<(&str, i32), (f64, usize)> = HashMap::new();
+    for grade in class_grades {
+        let total = totals.entry((grade.exam_name.as_str(), grade.course_id)).or_default();
+        total.0 += grade.score as f64;
+        total.1 += 1;
+    }
+    let order: Vec<String> = Category::iter().map(|c| c.to_string()).collect();
+    let mut grades: Vec<&Model> = grades.iter().collect();
+    grades.sort_by_key(|g| {
+        let position = order.iter().position(|c| *c == g.category).unwrap_or(order.len());
+        (g.course_id, position, g.exam_name.clone())
+    });
+    let multi_course = grades.iter().any(|g| g.course_id != grades[0].course_id);
+    grades
+        .into_iter()
+        .map(|grade| GradePoint {
+            exam: if multi_course {
+                format!("{} ({})", grade.exam_name, grade.course_id)
+            } else {
+                grade.exam_name.clone()
+            },
+            score: grade.score as f64,
+            average: totals
+                .get(&(grade.exam_name.as_str(), grade.course_id))
+                .map(|(sum, count)| sum / *count as f64),
+        })
+        .collect()
+}
+
+/// 画成绩走势图，返回 PNG 数据
+pub fn render_grade_chart(title: &str, points: &[GradePoint]) -> AppResult<Vec<u8>> {
+    if let Err(err) = &*FONT {
+        return Err(AppError::internal(err.clone()));
+    }
+    let (width, height) = (APPCONFIG.chart.width, APPCONFIG.chart.height);
+    let mut buffer = vec![0u8; (width * height * 3) as usize];
+    draw(&mut buffer, (width, height), title, points).map_err(|err| AppError::internal(format!("绘制图表失败: {}", err)))?;
+
+    let mut png = Cursor::new(Vec::new());
+    PngEncoder::new(&mut png)
+        .write_image(&buffer, width, height, ExtendedColorType::Rgb8)
+        .map_err(|err| AppError::internal(format!("图表编码失败: {}", err)))?;
+    Ok(png.into_inner())
+}
+
+fn draw(
+    buffer: &mut [u8],
+    size: (u32, u32),
+    title: &str,
+    points: &[GradePoint],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let root = BitMapBackend::with_buffer(buffer, size).into_drawing_area();
+    root.fill(&WHITE)?;
+    let max_score = points.iter().map(|p| p.score.max(p.average.unwrap_or(0.0))).fold(100.0, f64::max);
+    let averages: Vec<(f64, f64)> = points
+        .iter()
+        .enumerate()
+        .filter_map(|(i, p)| p.average.map(|average| (i as f64, average)))
+        .collect();
+    let mut chart = ChartBuilder::on(&root)
+        .caption(title, (FONT_FAMILY, 24))
+        .margin(16)
+        .x_label_area_size(36)
+        .y_label_area_size(40)
+        // 第 i 次考试的柱子以 x = i 为中心，顶部留出图例的位置
+        .build_cartesian_2d(-0.5..points.len() as f64 - 0.5, 0.0..max_score * 1.15)?;
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(points.len())
+        .x_label_formatter(&|x| match x.round() {
+            i if (x - i).abs() < 1e-6 && i >= 0.0 => points.get(i as usize).map(|p| p.exam.clone()).unwrap_or_default(),
+            _ => String::new(),
+        })
+        .y_label_formatter(&|y| format!("{:.0}", y))
+        .label_style((FONT_FAMILY, 14))
+        .draw()?;
+
+    let bar_color = BLUE.mix(0.6);
+    chart
+        .draw_series(points.iter().enumerate().map(|(i, p)| {
+            let x = i as f64;
+            Rectangle::new([(x - 0.4, 0.0), (x + 0.4, p.score)], bar_color.filled())
+        }))?
+        .label("我的成绩")
+        .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 14, y + 5)], bar_color.filled()));
+    chart
+        .draw_series(LineSeries::new(averages.clone(), RED.stroke_width(2)))?
+        .label("班级平均")
+        .legend(|(x, y)| PathElement::new([(x, y), (x + 14, y)], RED.stroke_width(2)));
+    // 只有一次考试时折线画不出来，平均分另外用点标出
+    chart.draw_series(averages.into_iter().map(|point| Circle::new(point, 4, RED.filled())))?;
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperRight)
+        .label_font((FONT_FAMILY, 14))
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+    root.present()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::grade;
+
+    #[test]
+    fn test_grade_points() {
+        let grades = [grade(1, 1, "Mid", 80), grade(1, 1, "Quiz-2", 70), grade(1, 1, "Quiz-1", 60)];
+        let class = [grade(1, 1, "Quiz-1", 60), grade(2, 1, "Quiz-1", 90), grade(1, 1, "Mid", 80)];
+        let points = grade_points(&grades, &class);
+        let exams: Vec<&str> = points.iter().map(|p| p.exam.as_str()).collect();
+        assert_eq!(exams, ["Quiz-1", "Quiz-2", "Mid"]);
+        assert_eq!(points[0].average, Some(75.0));
+        assert_eq!(points[1].average, None);
+
+        // 两门课程的同名考试分开显示，平均分各自计算
+        let points = grade_points(&[grade(1, 1, "Mid", 80), grade(1, 2, "Mid", 90)], &class);
+        let exams: Vec<&str> = points.iter().map(|p| p.exam.as_str()).collect();
+        assert_eq!(exams, ["Mid (1)", "Mid (2)"]);
+        assert_eq!(points[0].average, Some(80.0));
+        assert_eq!(points[1].average, None);
+    }
+}
@@ -0,0 +1,104 @@
+use clap::{Parser, Subcommand};
+
+use crate::{
+    cmd::{CmdContext, CmdHandler, CmdResult, HandlerBuilder},
+    config::{APPCONFIG, DB_GLOBAL},
+    error::AppError,
+    models::permission_grant::{ROLE_SUPER_ADMIN, ROLE_TA},
+    permission::check_permission,
+    service::permission_service::PermissionService,
+};
+
+#[derive(Parser, Debug)]
+#[command(name = "admin")]
+#[command(about = "授予或撤销超级管理员、教师、助教权限（仅超级管理员）")]
+pub struct Admin {
+    #[command(subcommand)]
+    command: AdminCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AdminCommand {
+    /// 查看授权，不填QQ号时查看全部
+    #[command(name = "list")]
+    List {
+        #[arg(help = "QQ号")]
+        user_id: Option<i64>,
+    },
+    /// 授权，如 /admin grant 123456 teacher 1001
+    #[command(name = "grant")]
+    Grant {
+        #[arg(help = "QQ号")]
+        user_id: i64,
+        #[arg(help = "super_admin, teacher, ta")]
+        role: String,
+        #[arg(help = "教师为课程号，助教为群号，在群里授权助教时默认本群")]
+        scope: Option<i64>,
+    },
+    /// 撤销授权
+    #[command(name = "revoke")]
+    Revoke {
+        #[arg(help = "QQ号")]
+        user_id: i64,
+        #[arg(help = "super_admin, teacher, ta")]
+        role: String,
+        #[arg(help = "教师为课程号，助教为群号，在群里撤销助教时默认本群")]
+        scope: Option<i64>,
+    },
+}
+
+impl HandlerBuilder for Admin {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                let admin = Admin::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                let operator = ctx.sender();
+                if !check_permission(operator).await {
+                    return Err(AppError::permission("只有超级管理员可以管理权限"));
+                }
+                let db = DB_GLOBAL
+                    .get()
+                    .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
+                let service = PermissionService::new(db.clone());
+                // 在群里授权助教时默认管理本群
+                let current_group = (ctx.env() == "group").then_some(ctx.group_id());
+                let scope_of = |role: &str, scope: Option<i64>| {
+                    if role == ROLE_TA { scope.or(current_group) } else { scope }
+                };
+
+                let output = match admin.command {
+                    AdminCommand::List { user_id } => {
+                        let grants = service.list(user_id).await?;
+                        let mut lines: Vec<String> = APPCONFIG
+                            .admins
+                            .iter()
+                            .filter(|admin| user_id.is_none_or(|user_id| user_id == **admin))
+                            .map(|admin| format!("{}：{}（配置文件）", admin, ROLE_SUPER_ADMIN))
+                            .collect();
+                        lines.extend(grants.iter().map(|g| match g.role.as_str() {
+                            ROLE_SUPER_ADMIN => format!("{}：{}", g.user_id, g.role),
+                            _ => format!("{}：{} {}", g.user_id, g.role, g.scope_id),
+                        }));
+                        if lines.is_empty() {
+                            "没有授权记录".to_string()
+                        } else {
+                            lines.join("\n")
+                        }
+                    }
+                    AdminCommand::Grant { user_id, role, scope } => {
+                        let grant = service.grant(user_id, &role, scope_of(&role, scope), operator).await?;
+                        format!("已授予 {} {} 权限", grant.user_id, grant.role)
+                    }
+                    AdminCommand::Revoke { user_id, role, scope } => {
+                        if user_id == operator && role == ROLE_SUPER_ADMIN {
+                            return Err(AppError::validation("不能撤销自己的超级管理员权限"));
+                        }
+                        service.revoke(user_id, &role, scope_of(&role, scope)).await?;
+                        format!("已撤销 {} 的 {} 权限", user_id, role)
+                    }
+                };
+                Ok(CmdResult::text(output))
+            })
+        })
+    }
+}
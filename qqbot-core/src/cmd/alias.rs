@@ -0,0 +1,255 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use clap::{Parser, Subcommand};
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+
+use crate::{
+    GroupId,
+    cmd::{CMD_REGISTRY, CmdContext, CmdHandler, CmdResult, HandlerBuilder},
+    config::{APPCONFIG, DB_GLOBAL, get_db},
+    error::AppError,
+    repo::group_config::{GroupConfigRepo, GroupConfigRepository},
+    service::group_config_service::GroupConfigService,
+};
+
+/// 每个群最多添加的别名数
+const MAX_GROUP_ALIASES: usize = 50;
+const MAX_ALIAS_CHARS: usize = 16;
+const MAX_PREFIX_CHARS: usize = 8;
+
+/// 群内的命令前缀和别名，未配置的群使用全局设置
+#[derive(Debug, Default)]
+struct GroupCommands {
+    prefix: Option<String>,
+    aliases: BTreeMap<String, String>,
+}
+
+// 每条消息都要判断是否为命令，群内设置单独缓存，未配置的群也缓存空设置
+static GROUP_COMMANDS: Lazy<Cache<GroupId, Arc<GroupCommands>>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(APPCONFIG.cache.cache_capacity / 10)
+        .time_to_live(APPCONFIG.cache.cache_lifetime)
+        .build()
+});
+
+async fn group_commands(group_id: Option<GroupId>) -> Arc<GroupCommands> {
+    let Some(group_id) = group_id else {
+        return Arc::default();
+    };
+    if let Some(commands) = GROUP_COMMANDS.get(&group_id).await {
+        return commands;
+    }
+    let repo = GroupConfigRepo::new(get_db().await.as_ref().clone());
+    let commands = match repo.find_by_group_id(group_id).await {
+        Ok(Some(config)) => {
+            let data = config.to_group_data(&APPCONFIG.llm.model);
+            Arc::new(GroupCommands {
+                prefix: data.cmd_prefix,
+                aliases: data.aliases,
+            })
+        }
+        Ok(None) => Arc::default(),
+        Err(err) => {
+            log::warn!("读取群 {} 的命令设置失败: {}", group_id, err);
+            return Arc::default();
+        }
+    };
+    GROUP_COMMANDS.insert(group_id, commands.clone()).await;
+    commands
+}
+
+/// 群内生效的命令前缀
+pub async fn prefix(group_id: Option<GroupId>) -> String {
+    group_commands(group_id)
+        .await
+        .prefix
+        .clone()
+        .unwrap_or_else(|| APPCONFIG.cmd_suffix.clone())
+}
+
+/// 把消息的第一个词解析为命令，返回展开别名后的命令名和参数，不是命令时返回 None。
+/// alone 表示整条消息只有这一个词
+pub async fn resolve(first: &str, alone: bool, group_id: Option<GroupId>) -> Option<Vec<String>> {
+    let commands = group_commands(group_id).await;
+    let prefix = commands.prefix.as_deref().unwrap_or(&APPCONFIG.cmd_suffix);
+    expand(first, alone, prefix, &commands.aliases, &APPCONFIG.commands.aliases)
+}
+
+/// 别名带前缀时总能触发；不带前缀时整条消息只有别名才触发，避免误伤“成绩怎么算”这类普通聊天。
+/// 群内别名优先；不是别名时必须带前缀
+fn expand(
+    first: &str,
+    alone: bool,
+    prefix: &str,
+    group_aliases: &BTreeMap<String, String>,
+    global_aliases: &HashMap<String, String>,
+) -> Option<Vec<String>> {
+    let (name, prefixed) = match first.strip_prefix(prefix) {
+        Some(name) => (name, true),
+        None => (first, false),
+    };
+    match group_aliases.get(name).or_else(|| global_aliases.get(name)) {
+        Some(command) if prefixed || alone => Some(command.split_whitespace().map(String::from).collect()),
+        None if prefixed && !name.is_empty() => Some(vec![name.to_string()]),
+        _ => None,
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "alias")]
+#[command(about = "管理群内的命令别名和命令前缀（群管理员）")]
+pub struct Alias {
+    #[command(subcommand)]
+    command: AliasCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AliasCommand {
+    /// 查看群内的别名和前缀
+    #[command(name = "list")]
+    List,
+    /// 添加别名，如 /alias add 作业成绩 query grade
+    #[command(name = "add")]
+    Add {
+        #[arg(help = "别名")]
+        name: String,
+        #[arg(required = true, num_args = 1.., help = "对应的命令，不带前缀")]
+        command: Vec<String>,
+    },
+    /// 删除别名
+    #[command(name = "remove")]
+    Remove {
+        #[arg(help = "别名")]
+        name: String,
+    },
+    /// 设置群内的命令前缀，不填时恢复默认
+    #[command(name = "prefix")]
+    Prefix {
+        #[arg(help = "新的命令前缀，如 #")]
+        prefix: Option<String>,
+    },
+}
+
+impl HandlerBuilder for Alias {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                let alias =
+                    Alias::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                if ctx.env() != "group" || ctx.group_id() == 0 {
+                    return Err(AppError::command("只能在群聊中使用"));
+                }
+                let group_id = ctx.group_id();
+                let is_admin = ctx.group_admin();
+                if !matches!(alias.command, AliasCommand::List) && !is_admin {
+                    return Err(AppError::permission("只有群管理员可以修改别名和前缀"));
+                }
+
+                let db = DB_GLOBAL
+                    .get()
+                    .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
+                let service = GroupConfigService::new(db.as_ref().clone());
+                let mut group_data = service.get_group_data(group_id).await?;
+                let output = match alias.command {
+                    AliasCommand::List => {
+                        return Ok(CmdResult::text(describe(&*group_commands(Some(group_id)).await)));
+                    }
+                    AliasCommand::Add { name, command } => {
+                        validate_alias(&name, &command, group_data.cmd_prefix.as_deref())?;
+                        if !group_data.aliases.contains_key(&name) && group_data.aliases.len() >= MAX_GROUP_ALIASES {
+                            return Err(AppError::validation(format!("每个群最多 {} 个别名", MAX_GROUP_ALIASES)));
+                        }
+                        let command = command.join(" ");
+                        let output = format!("已添加别名 {} -> {}", name, command);
+                        group_data.aliases.insert(name, command);
+                        output
+                    }
+                    AliasCommand::Remove { name } => {
+                        if group_data.aliases.remove(&name).is_none() {
+                            return Err(AppError::not_found(format!("别名 {}", name)));
+                        }
+                        format!("已删除别名 {}", name)
+                    }
+                    AliasCommand::Prefix { prefix } => {
+                        let prefix = prefix.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+                        if let Some(prefix) = &prefix
+                            && prefix.chars().count() > MAX_PREFIX_CHARS
+                        {
+                            return Err(AppError::validation(format!("前缀最多 {} 个字符", MAX_PREFIX_CHARS)));
+                        }
+                        let output = match &prefix {
+                            Some(prefix) => format!("本群的命令前缀已改为 {}", prefix),
+                            None => format!("本群的命令前缀已恢复为 {}", APPCONFIG.cmd_suffix),
+                        };
+                        group_data.cmd_prefix = prefix;
+                        output
+                    }
+                };
+                service.save_group_data(group_id, &group_data).await?;
+                GROUP_COMMANDS.invalidate(&group_id).await;
+                Ok(CmdResult::text(output))
+            })
+        })
+    }
+}
+
+fn validate_alias(name: &str, command: &[String], prefix: Option<&str>) -> Result<(), AppError> {
+    let prefix = prefix.unwrap_or(&APPCONFIG.cmd_suffix);
+    if name.chars().count() > MAX_ALIAS_CHARS || name.starts_with(prefix) {
+        return Err(AppError::validation(format!(
+            "别名最多 {} 个字符，且不能以 {} 开头",
+            MAX_ALIAS_CHARS, prefix
+        )));
+    }
+    if CMD_REGISTRY.contains_key(name) {
+        return Err(AppError::validation(format!("{} 已是命令名", name)));
+    }
+    if !CMD_REGISTRY.contains_key(command[0].as_str()) {
+        return Err(AppError::validation(format!("命令 {} 不存在", command[0])));
+    }
+    Ok(())
+}
+
+fn describe(commands: &GroupCommands) -> String {
+    let prefix = commands.prefix.as_deref().unwrap_or(&APPCONFIG.cmd_suffix);
+    let mut lines = vec![format!("命令前缀：{}", prefix)];
+    if commands.aliases.is_empty() {
+        lines.push("本群没有自定义别名".to_string());
+    } else {
+        lines.push("本群别名：".to_string());
+        lines.extend(commands.aliases.iter().map(|(name, command)| format!("{} -> {}", name, command)));
+    }
+    let mut global: Vec<_> = APPCONFIG.commands.aliases.iter().collect();
+    global.sort();
+    lines.push(format!(
+        "全局别名：{}",
+        global.iter().map(|(name, command)| format!("{} -> {}", name, command)).collect::<Vec<_>>().join("，")
+    ));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_alias() {
+        let group = BTreeMap::from([("成绩".to_string(), "query grade --mode mid".to_string())]);
+        let global = HashMap::from([
+            ("成绩".to_string(), "query grade".to_string()),
+            ("查成绩".to_string(), "query grade".to_string()),
+        ]);
+        assert_eq!(expand("#成绩", false, "#", &group, &global).unwrap(), vec!["query", "grade", "--mode", "mid"]);
+        assert_eq!(expand("查成绩", true, "#", &group, &global).unwrap(), vec!["query", "grade"]);
+        assert_eq!(expand("#bind", false, "#", &group, &global).unwrap(), vec!["bind"]);
+        // 不带前缀的别名后面还有其他内容时是普通聊天
+        assert!(expand("成绩", false, "#", &group, &global).is_none());
+        assert!(expand("/bind", true, "#", &group, &global).is_none());
+        assert!(expand("你好", true, "#", &group, &global).is_none());
+        assert!(expand("#", true, "#", &group, &global).is_none());
+    }
+}
@@ -6,61 +6,93 @@ use crate::{
     service::{StuServiceImpl, UserService},
 };
 
-use super::{CmdHandler, CmdResult, CommonArgs, HandlerBuilder};
+use super::{CmdContext, CmdHandler, CmdMeta, CmdResult, HandlerBuilder, session::Awaiting};
 use crate::error::AppError;
 #[derive(Debug, Clone, Parser)]
 pub struct Bind {
-    #[command(flatten)]
-    pub common: CommonArgs,
     #[arg(long, help = "clear qq", default_value_t = false)]
     clear: bool,
-    #[arg(required = false, help = "student number", default_value_t = 0)]
-    id: i64,
+    #[arg(required = false, help = "student number, asked later if omitted")]
+    id: Option<i64>,
 }
 
 impl HandlerBuilder for Bind {
     fn build() -> CmdHandler {
-        Box::new(move |args: Vec<String>| {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
             Box::pin(async move {
                 let bind =
                     Bind::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
-                if bind.common.env != Env::Private.to_string() {
+                if ctx.env != Env::Private.to_string() {
                     return Err(AppError::command(String::from(
                         "only used in private environment",
                     )));
                 }
-                let db = DB_GLOBAL
-                    .get()
-                    .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
-                let ss = StuServiceImpl::new(db.clone());
-                if let Ok(model) = ss.find_by_qq(bind.common.sender).await {
-                    if bind.clear {
-                        ss.update_qq(model.student_id, 0)
-                            .await
-                            .map_err(|_| AppError::command(String::from("success")))?;
-                        return Ok(CmdResult {
-                            output: String::from("clear qq number successfully"),
-                        });
-                    }
-                    return Err(AppError::command(format!(
-                        "the student has been bind to {}",
-                        model.student_id
-                    )));
-                };
-                if let Ok(model) = ss.get(bind.id).await {
-                    if model.qq_number != 0 {
-                        return Err(AppError::command(format!(
-                            "can't bind the student, because he has been bind to other qq"
-                        )));
-                    }
+                let sender = ctx.sender;
+                if bind.clear {
+                    return clear_student(sender).await;
+                }
+                match bind.id {
+                    Some(id) => bind_student(sender, id).await,
+                    // 没有给出学号时等待用户发送
+                    None => Ok(CmdResult::awaiting("请发送你的学号，发送 /cancel 取消", ask_student_id(sender))),
                 }
-                ss.update_qq(bind.id, bind.common.sender)
-                    .await
-                    .map_err(|err| AppError::command(err.to_string()))?;
-                Ok(CmdResult {
-                    output: "success".into(),
-                })
             })
         })
     }
+
+    /// 解除绑定后无法再查询成绩，需要确认。只能在私聊中使用，群聊中直接拒绝
+    fn meta() -> CmdMeta {
+        CmdMeta {
+            confirm: Some(|ctx, args| ctx.env() == "private" && args.iter().any(|arg| arg == "--clear")),
+        }
+    }
+}
+
+fn ask_student_id(sender: i64) -> Awaiting {
+    Awaiting::new(move |input: String| async move {
+        match input.parse::<i64>() {
+            Ok(id) => bind_student(sender, id).await,
+            Err(_) => Ok(CmdResult::awaiting(
+                "学号应为数字，请重新发送，发送 /cancel 取消",
+                ask_student_id(sender),
+            )),
+        }
+    })
+}
+
+fn student_service() -> Result<StuServiceImpl, AppError> {
+    let db = DB_GLOBAL
+        .get()
+        .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
+    Ok(StuServiceImpl::new(db.clone()))
+}
+
+async fn clear_student(sender: i64) -> Result<CmdResult, AppError> {
+    let ss = student_service()?;
+    let model = ss.find_by_qq(sender).await?;
+    ss.update_qq(model.student_id, 0)
+        .await
+        .map_err(|_| AppError::command(String::from("success")))?;
+    Ok(CmdResult::text("clear qq number successfully"))
+}
+
+async fn bind_student(sender: i64, id: i64) -> Result<CmdResult, AppError> {
+    let ss = student_service()?;
+    if let Ok(model) = ss.find_by_qq(sender).await {
+        return Err(AppError::command(format!(
+            "the student has been bind to {}",
+            model.student_id
+        )));
+    };
+    if let Ok(model) = ss.get(id).await
+        && model.qq_number != 0
+    {
+        return Err(AppError::command(
+            "can't bind the student, because he has been bind to other qq".to_string(),
+        ));
+    }
+    ss.update_qq(id, sender)
+        .await
+        .map_err(|err| AppError::command(err.to_string()))?;
+    Ok(CmdResult::text("success"))
 }
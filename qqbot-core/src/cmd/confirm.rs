@@ -0,0 +1,98 @@
+use clap::Parser;
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use rand::Rng;
+
+use crate::{
+    cmd::{
+        CMD_REGISTRY, CmdContext, CmdHandler, CmdResult, CmdStatus, HandlerBuilder, session::SessionKey,
+    },
+    config::APPCONFIG,
+    error::AppError,
+};
+
+/// 等待确认的命令，保存执行时的上下文和用户输入的参数
+#[derive(Debug, Clone)]
+struct Pending {
+    code: String,
+    ctx: CmdContext,
+    args: Vec<String>,
+}
+
+// 每个会话只保留最近一条等待确认的命令，超过 cmd_confirm_timeout 未确认自动作废
+static PENDING: Lazy<Cache<SessionKey, Pending>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(APPCONFIG.cache.cache_capacity)
+        .time_to_live(APPCONFIG.cache.cmd_confirm_timeout)
+        .build()
+});
+
+/// 记下需要确认的命令，返回确认提示。按发送者的会话保存，只有本人能确认
+pub(super) async fn request(ctx: CmdContext, args: Vec<String>) -> Result<CmdResult, AppError> {
+    let code = format!("{:04}", rand::thread_rng().gen_range(0..10000));
+    let command = args.join(" ");
+    PENDING.insert(ctx.session_key(), Pending { code: code.clone(), ctx, args }).await;
+    Ok(CmdResult::text(format!(
+        "⚠️ 即将执行 {}{}\n请在 {} 内发送 {}confirm {} 确认",
+        APPCONFIG.cmd_suffix,
+        command,
+        humantime::format_duration(APPCONFIG.cache.cmd_confirm_timeout),
+        APPCONFIG.cmd_suffix,
+        code
+    ))
+    .with_status(CmdStatus::AwaitingConfirm))
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "confirm")]
+#[command(about = "确认执行上一条需要确认的命令")]
+pub struct Confirm {
+    #[arg(help = "确认码")]
+    code: String,
+}
+
+impl HandlerBuilder for Confirm {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                let confirm =
+                    Confirm::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                let key = ctx.session_key();
+                let pending = PENDING
+                    .get(&key)
+                    .await
+                    .ok_or_else(|| AppError::command("没有等待确认的命令，或确认已超时"))?;
+                if pending.code != confirm.code.trim() {
+                    return Err(AppError::command("确认码不正确"));
+                }
+                PENDING.invalidate(&key).await;
+
+                // 确认后直接调用处理函数，不再重复确认
+                let registered = CMD_REGISTRY
+                    .get(&pending.args[0])
+                    .ok_or_else(|| AppError::command(format!("Command '{}' not found", pending.args[0])))?;
+                (registered.handler)(pending.ctx, pending.args).await
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_keyed_by_sender() {
+        // 参数中的 --sender 只是用户输入，确认记在实际发送者的会话上
+        let ctx = CmdContext {
+            sender: 10,
+            group_id: 20,
+            env: "group".to_string(),
+            ..Default::default()
+        };
+        let args: Vec<String> = ["strategy", "cmd", "--sender", "30"].iter().map(|s| s.to_string()).collect();
+        request(ctx, args).await.unwrap();
+        assert!(PENDING.get(&SessionKey::new(10, Some(20))).await.is_some());
+        assert!(PENDING.get(&SessionKey::new(30, Some(20))).await.is_none());
+    }
+}
@@ -0,0 +1,89 @@
+use clap::{Parser, Subcommand};
+
+use crate::{
+    cmd::{CmdContext, CmdHandler, CmdResult, HandlerBuilder},
+    config::DB_GLOBAL,
+    error::AppError,
+    models::user_memory::SOURCE_MANUAL,
+    service::memory_service::MemoryService,
+};
+
+#[derive(Parser, Debug)]
+#[command(name = "memory")]
+#[command(about = "管理机器人对你的长期记忆")]
+pub struct Memory {
+    #[command(subcommand)]
+    command: MemoryCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum MemoryCommand {
+    /// 添加一条记忆，例如 /memory add 我在三班
+    #[command(name = "add")]
+    Add {
+        #[arg(required = true, num_args = 1.., help = "记忆内容")]
+        content: Vec<String>,
+    },
+    /// 查看所有记忆
+    #[command(name = "list")]
+    List,
+    /// 删除记忆，传入编号或 all
+    #[command(name = "forget")]
+    Forget {
+        #[arg(help = "记忆编号，all 表示全部删除")]
+        target: String,
+    },
+}
+
+impl HandlerBuilder for Memory {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                let memory =
+                    Memory::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                let db = DB_GLOBAL
+                    .get()
+                    .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
+                let service = MemoryService::new(db.clone());
+                let user_id = ctx.sender();
+
+                let output = match memory.command {
+                    MemoryCommand::Add { content } => {
+                        let model = service.add(user_id, &content.join(" "), SOURCE_MANUAL).await?;
+                        format!("已记住 #{}: {}", model.id, model.content)
+                    }
+                    MemoryCommand::List => {
+                        let memories = service.list(user_id).await?;
+                        if memories.is_empty() {
+                            "还没有关于你的记忆，使用 /memory add <内容> 添加".to_string()
+                        } else {
+                            let lines = memories
+                                .iter()
+                                .map(|m| {
+                                    let source = if m.source == SOURCE_MANUAL { "" } else { "（自动）" };
+                                    format!("#{} {}{}", m.id, m.content, source)
+                                })
+                                .collect::<Vec<_>>()
+                                .join("\n");
+                            format!("关于你的记忆：\n{}", lines)
+                        }
+                    }
+                    MemoryCommand::Forget { target } => {
+                        if target == "all" {
+                            let count = service.forget_all(user_id).await?;
+                            format!("已删除全部 {} 条记忆", count)
+                        } else {
+                            let id = target
+                                .trim_start_matches('#')
+                                .parse::<i64>()
+                                .map_err(|_| AppError::command("请输入记忆编号或 all"))?;
+                            service.forget(user_id, id).await?;
+                            format!("已删除记忆 #{}", id)
+                        }
+                    }
+                };
+                Ok(CmdResult::text(output))
+            })
+        })
+    }
+}
@@ -1,56 +1,187 @@
+pub mod admin;
+pub mod alias;
 pub mod bind;
+pub mod confirm;
+pub mod memory;
+pub mod moderation;
+pub mod policy;
 pub mod query;
+pub mod request;
+pub mod session;
 pub mod strategy;
 pub mod push;
 
+use admin::Admin;
+use alias::Alias;
 use bind::Bind;
+use confirm::Confirm;
+use memory::Memory;
+use moderation::{Kick, Mute, Recall, Unmute, WholeMute};
 use once_cell::sync::Lazy;
+use policy::CmdPolicy;
 use query::Query;
+use request::Request;
+use session::{Awaiting, Cancel, SessionKey};
 use strategy::Strategy;
 use self::push::Push;
 // Assuming query module exists and defines Query structuse clap::Parser;
 use crate::error::AppError;
-use clap::Parser;
+use crate::permission::Role;
+use crate::reply_strategy::{Delivery, MessageContent, Reply};
 use std::{collections::HashMap, future::Future, pin::Pin}; // Added Arc]
 
 // --- Data Structures and Errors (Keep as is) ---
-pub type CmdRegistry = HashMap<String, CmdHandler>; // Use Arc for potential sharing
-#[derive(Parser, Debug, Clone)]
-pub struct CommonArgs {
-    #[arg(long, global = true, required = false, help = "sender id(auto)")]
-    sender: i64,
-    #[arg(long, global = true, required = false, help = "self id(auto)")]
-    myself: i64,
-    #[arg(long, global = true, required = false, help = "group id(auto)", default_value_t = 0)]
-    group_id: i64,
-    #[arg(long,global = true,required = false, help = "env private, group(auto)",default_value_t=String::from("private"))]
-    env: String,
-    #[arg(
-        long,
-        global = true,
-        required = false,
-        help = "是否是群管理(auto)",
-        default_value_t = false
-    )]
-    group_admin: bool,
+pub type CmdRegistry = HashMap<String, RegisteredCmd>; // Use Arc for potential sharing
+/// 执行命令的上下文，由收到的消息得到，不从用户输入的参数中解析，
+/// 因此无法通过参数冒充其他用户或角色
+#[derive(Debug, Clone)]
+pub struct CmdContext {
+    pub sender: i64,
+    pub myself: i64,
+    // 私聊时为 0
+    pub group_id: i64,
+    // private 或 group
+    pub env: String,
+    pub role: Role,
+    // 被引用的消息id
+    pub reply_to: Option<i64>,
 }
+
+impl Default for CmdContext {
+    fn default() -> Self {
+        Self {
+            sender: 0,
+            myself: 0,
+            group_id: 0,
+            env: String::from("private"),
+            role: Role::Member,
+            reply_to: None,
+        }
+    }
+}
+/// 命令的执行状态，记录在日志中便于统计
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CmdStatus {
+    #[default]
+    Success,
+    /// 等待用户继续输入
+    AwaitingInput,
+    /// 等待用户 /confirm 确认
+    AwaitingConfirm,
+    /// 用户取消
+    Cancelled,
+}
+
+impl CmdStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CmdStatus::Success => "success",
+            CmdStatus::AwaitingInput => "awaiting_input",
+            CmdStatus::AwaitingConfirm => "awaiting_confirm",
+            CmdStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
 #[derive(Debug)] // Added Debug for easier printing
 pub struct CmdResult {
-    pub output: String,
-    // 可以扩展更多字段，如状态码等
+    pub content: MessageContent,
+    pub delivery: Delivery,
+    pub status: CmdStatus,
+    // 需要用户继续输入时，下一条消息交给该步骤处理
+    pub awaiting: Option<Awaiting>,
+}
+
+impl CmdResult {
+    pub fn new(content: MessageContent) -> Self {
+        Self {
+            content,
+            delivery: Delivery::InPlace,
+            status: CmdStatus::Success,
+            awaiting: None,
+        }
+    }
+
+    pub fn text(output: impl Into<String>) -> Self {
+        Self::new(MessageContent::Text(output.into()))
+    }
+
+    /// 发送提示并等待用户的下一条消息
+    pub fn awaiting(prompt: impl Into<String>, step: Awaiting) -> Self {
+        Self {
+            status: CmdStatus::AwaitingInput,
+            awaiting: Some(step),
+            ..Self::text(prompt)
+        }
+    }
+
+    pub fn with_delivery(self, delivery: Delivery) -> Self {
+        Self { delivery, ..self }
+    }
+
+    pub fn with_status(self, status: CmdStatus) -> Self {
+        Self { status, ..self }
+    }
+
+    /// 回复的文字部分
+    pub fn output(&self) -> String {
+        self.content.get_text()
+    }
+}
+
+impl From<CmdResult> for Reply {
+    fn from(result: CmdResult) -> Self {
+        Reply {
+            content: result.content,
+            delivery: result.delivery,
+        }
+    }
 }
 
 type CmdHandler = Box<
-    dyn Fn(Vec<String>) -> Pin<Box<dyn Future<Output = Result<CmdResult, AppError>> + Send>>
+    dyn Fn(CmdContext, Vec<String>) -> Pin<Box<dyn Future<Output = Result<CmdResult, AppError>> + Send>>
         + Sync
         + Send,
 >;
 
+/// 命令的元数据，注册时与处理函数一起保存
+#[derive(Clone, Copy, Default)]
+pub struct CmdMeta {
+    // 根据上下文和用户输入的参数判断是否需要先 /confirm 确认，如 /bind --clear。
+    // 确认码在处理函数之前发出，没有权限执行的用户应返回 false，交给处理函数拒绝
+    pub confirm: Option<fn(&CmdContext, &[String]) -> bool>,
+}
+
+impl CmdMeta {
+    pub fn needs_confirm(&self, ctx: &CmdContext, args: &[String]) -> bool {
+        self.confirm.is_some_and(|confirm| confirm(ctx, args))
+    }
+}
+
+pub struct RegisteredCmd {
+    pub handler: CmdHandler,
+    pub meta: CmdMeta,
+}
+
 pub trait HandlerBuilder {
     fn build() -> CmdHandler;
+
+    fn meta() -> CmdMeta {
+        CmdMeta::default()
+    }
+
+    fn register() -> RegisteredCmd
+    where
+        Self: Sized,
+    {
+        RegisteredCmd {
+            handler: Self::build(),
+            meta: Self::meta(),
+        }
+    }
 }
 
-impl CommonArgs {
+impl CmdContext {
     pub fn env(&self) -> &str {
         &self.env
     }
@@ -63,37 +194,62 @@ impl CommonArgs {
         self.group_id
     }
     
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// 群管理员、群主或机器人管理员
     pub fn group_admin(&self) -> bool {
-        self.group_admin
+        self.role().is_manager()
+    }
+
+    pub fn myself(&self) -> i64 {
+        self.myself
+    }
+
+    pub fn reply_to(&self) -> Option<i64> {
+        self.reply_to
+    }
+
+    /// 多轮命令的会话：私聊按用户区分，群聊按群内的用户区分
+    pub fn session_key(&self) -> SessionKey {
+        let group_id = (self.env == "group").then_some(self.group_id);
+        SessionKey::new(self.sender, group_id)
     }
 }
 
 // --- Execute Trait (Keep as is) ---
 pub trait Execute {
-    // Takes &Vec<&str> which is fine for passing arguments *to* execute
+    // Takes &[&str] which is fine for passing arguments *to* execute
     fn execute(
         &self,
+        ctx: &CmdContext,
         cmd: &str,
-        args: &Vec<&str>,
+        args: &[&str],
     ) -> impl std::future::Future<Output = Result<CmdResult, AppError>> + Send;
 }
 
 // --- Implementation of Execute for CmdRegistry ---
 impl Execute for CmdRegistry {
-    async fn execute(&self, cmd: &str, args: &Vec<&str>) -> Result<CmdResult, AppError> {
+    async fn execute(&self, ctx: &CmdContext, cmd: &str, args: &[&str]) -> Result<CmdResult, AppError> {
         // 1. Find the handler in the registry
-        if let Some(handler) = self.get(cmd) {
+        if let Some(registered) = self.get(cmd) {
             // 2. Prepare arguments for the handler's `run` method.
             //    The `run` method now expects `Vec<String>`.
-            //    We convert the input `&Vec<&str>` to `Vec<String>`.
+            //    We convert the input `&[&str]` to `Vec<String>`.
             //    We also need to prepend the command name itself, as clap often expects it.
             let mut full_args: Vec<String> = Vec::with_capacity(args.len() + 1);
             full_args.push(cmd.into()); // Add command name as first arg for clap parsing
             full_args.extend(args.iter().map(|s| s.to_string())); // Convert &str to String
 
+            // 需要确认的命令先记下，用户发送 /confirm 后再执行
+            if registered.meta.needs_confirm(ctx, &full_args) {
+                return confirm::request(ctx.clone(), full_args).await;
+            }
+
             // 3. Call the handler's `run` instance method
             //    Since handler is Arc<dyn CmdHandler<T>>, we call run on the dereferenced trait object.
-            let future = handler(full_args);
+            let future = (registered.handler)(ctx.clone(), full_args);
 
             // 4. Await the future returned by run
             future.await // This returns Result<CmdResult, AppError>
@@ -105,9 +261,60 @@ impl Execute for CmdRegistry {
 }
 pub static CMD_REGISTRY: Lazy<CmdRegistry> = Lazy::new(|| {
     let mut m = HashMap::new();
-    m.insert("query".into(), Query::build());
-    m.insert("bind".into(), Bind::build());
-    m.insert("strategy".into(), Strategy::build());
-    m.insert("push".into(), Push::build());
+    m.insert("query".into(), Query::register());
+    m.insert("bind".into(), Bind::register());
+    m.insert("strategy".into(), Strategy::register());
+    m.insert("push".into(), Push::register());
+    m.insert("memory".into(), Memory::register());
+    m.insert("mute".into(), Mute::register());
+    m.insert("unmute".into(), Unmute::register());
+    m.insert("kick".into(), Kick::register());
+    m.insert("recall".into(), Recall::register());
+    m.insert("whole-mute".into(), WholeMute::register());
+    m.insert("request".into(), Request::register());
+    m.insert("cancel".into(), Cancel::register());
+    m.insert("confirm".into(), Confirm::register());
+    m.insert("alias".into(), Alias::register());
+    m.insert("cmd".into(), CmdPolicy::register());
+    m.insert("admin".into(), Admin::register());
     m
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_identity_not_from_args() {
+        // 用户在参数里伪造 --sender 或用 -- 截断参数，都不能操作其他人的会话
+        let victim = SessionKey::new(2, None);
+        let echo = Awaiting::new(|input: String| async move { Ok(CmdResult::text(input)) });
+        session::track(victim, &mut CmdResult::awaiting("请输入", echo)).await;
+
+        let ctx = CmdContext {
+            sender: 1,
+            ..Default::default()
+        };
+        for args in [vec!["--sender", "2"], vec!["--sender", "2", "--"], vec!["--", "--sender", "2"]] {
+            assert!(CMD_REGISTRY.execute(&ctx, "cancel", &args).await.is_err());
+        }
+        assert!(session::cancel(victim).await);
+    }
+
+    #[test]
+    fn test_confirm_requires_permission() {
+        // 普通成员在群里修改策略会被直接拒绝，不发确认码
+        let member = CmdContext {
+            group_id: 20,
+            env: "group".to_string(),
+            ..Default::default()
+        };
+        let admin = CmdContext {
+            role: Role::Admin,
+            ..member.clone()
+        };
+        let args = vec!["strategy".to_string(), "cmd".to_string()];
+        assert!(!Strategy::meta().needs_confirm(&member, &args));
+        assert!(Strategy::meta().needs_confirm(&admin, &args));
+    }
+}
@@ -0,0 +1,147 @@
+use clap::Parser;
+
+use crate::{
+    action,
+    cmd::{CmdContext, CmdHandler, CmdResult, HandlerBuilder},
+    config::DB_GLOBAL,
+    error::AppError,
+    reply_strategy::{Delivery, Env, MessageContent, MessageSegment},
+    service::moderation_service::{Moderation, ModerationService, Operator},
+};
+
+#[derive(Parser, Debug)]
+#[command(name = "mute")]
+#[command(about = "禁言群成员，例如 /mute @张三 10m")]
+pub struct Mute {
+    #[arg(help = "要禁言的成员，@ 或 QQ 号")]
+    target: i64,
+
+    #[arg(default_value = "10m", help = "禁言时长，如 10m、1h、1d")]
+    duration: String,
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "unmute")]
+#[command(about = "解除群成员的禁言")]
+pub struct Unmute {
+    #[arg(help = "要解除禁言的成员，@ 或 QQ 号")]
+    target: i64,
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "kick")]
+#[command(about = "将成员移出本群")]
+pub struct Kick {
+    #[arg(help = "要移出的成员，@ 或 QQ 号")]
+    target: i64,
+
+    #[arg(long, default_value_t = false, help = "拒绝其再次申请加群")]
+    reject: bool,
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "recall")]
+#[command(about = "撤回被引用的消息，引用要撤回的消息后发送 /recall")]
+pub struct Recall {}
+
+#[derive(Parser, Debug)]
+#[command(name = "whole-mute")]
+#[command(about = "开启或关闭全员禁言")]
+pub struct WholeMute {
+    #[arg(value_parser = ["on", "off"], help = "on 开启，off 关闭")]
+    state: String,
+}
+
+/// 在当前群执行管理操作
+async fn moderate(ctx: &CmdContext, moderation: Moderation) -> Result<(), AppError> {
+    if ctx.env() == Env::Private.to_string() {
+        return Err(AppError::command("该命令只能在群聊中使用"));
+    }
+    let db = DB_GLOBAL
+        .get()
+        .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
+    let operator = Operator {
+        group_id: ctx.group_id(),
+        user_id: ctx.sender(),
+        self_id: ctx.myself(),
+        role: ctx.role(),
+    };
+    ModerationService::new(db.clone())
+        .moderate(operator, moderation)
+        .await?;
+    Ok(())
+}
+
+impl HandlerBuilder for Mute {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                let mute = Mute::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                let duration = action::parse_mute_duration(&mute.duration)?;
+                moderate(&ctx, Moderation::Mute { user_id: mute.target, duration }).await?;
+                Ok(mention(mute.target, format!(" 已被禁言 {}", humantime::format_duration(duration))))
+            })
+        })
+    }
+}
+
+impl HandlerBuilder for Unmute {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                let unmute = Unmute::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                moderate(&ctx, Moderation::Unmute { user_id: unmute.target }).await?;
+                Ok(mention(unmute.target, " 已解除禁言".to_string()))
+            })
+        })
+    }
+}
+
+impl HandlerBuilder for Kick {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                let kick = Kick::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                moderate(&ctx, Moderation::Kick { user_id: kick.target, reject: kick.reject }).await?;
+                Ok(CmdResult::text(format!("已将 {} 移出本群", kick.target)))
+            })
+        })
+    }
+}
+
+impl HandlerBuilder for Recall {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                Recall::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                let message_id = ctx
+                    .reply_to()
+                    .ok_or_else(|| AppError::command("请引用要撤回的消息后发送 /recall"))?;
+                moderate(&ctx, Moderation::Recall { message_id }).await?;
+                // 撤回成功后不再回复，避免刷屏
+                Ok(CmdResult::text("已撤回该消息").with_delivery(Delivery::Silent))
+            })
+        })
+    }
+}
+
+impl HandlerBuilder for WholeMute {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                let whole_mute = WholeMute::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                let enable = whole_mute.state == "on";
+                moderate(&ctx, Moderation::WholeMute { enable }).await?;
+                Ok(CmdResult::text(if enable { "已开启全员禁言" } else { "已关闭全员禁言" }.to_string()))
+            })
+        })
+    }
+}
+
+/// @ 被处理的成员并附上说明
+fn mention(user_id: i64, text: String) -> CmdResult {
+    CmdResult::new(MessageContent::Mixed(vec![
+        MessageSegment::At { qq: user_id.to_string() },
+        MessageSegment::Text { text },
+    ]))
+}
@@ -0,0 +1,163 @@
+use clap::{Parser, Subcommand};
+
+use crate::{
+    cmd::{CMD_REGISTRY, CmdContext, CmdHandler, CmdResult, HandlerBuilder},
+    config::DB_GLOBAL,
+    error::AppError,
+    models::group_command_policy::COMMAND_LLM,
+    permission::Role,
+    service::group_command_policy_service::GroupCommandPolicyService,
+};
+
+/// 本命令不能停用，否则群管理员无法再恢复
+const COMMAND_SELF: &str = "cmd";
+
+#[derive(Parser, Debug)]
+#[command(name = "cmd")]
+#[command(about = "管理本群可用的命令及使用所需的角色（群管理员）")]
+pub struct CmdPolicy {
+    #[command(subcommand)]
+    command: PolicyCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PolicyCommand {
+    /// 查看本群的命令策略
+    #[command(name = "list")]
+    List,
+    /// 在本群启用命令，llm 表示大模型聊天
+    #[command(name = "enable")]
+    Enable {
+        #[arg(help = "命令名，不带前缀")]
+        name: String,
+    },
+    /// 在本群停用命令，llm 表示大模型聊天
+    #[command(name = "disable")]
+    Disable {
+        #[arg(help = "命令名，不带前缀")]
+        name: String,
+    },
+    /// 设置使用命令所需的最低角色，不填时不限制
+    #[command(name = "role")]
+    Role {
+        #[arg(help = "命令名，不带前缀")]
+        name: String,
+        #[arg(help = "member, admin, owner, bot_admin")]
+        role: Option<String>,
+    },
+    /// 删除命令的策略，恢复默认
+    #[command(name = "reset")]
+    Reset {
+        #[arg(help = "命令名，不带前缀")]
+        name: String,
+    },
+}
+
+impl PolicyCommand {
+    fn target(&self) -> Option<&str> {
+        match self {
+            PolicyCommand::List => None,
+            PolicyCommand::Enable { name }
+            | PolicyCommand::Disable { name }
+            | PolicyCommand::Role { name, .. }
+            | PolicyCommand::Reset { name } => Some(name),
+        }
+    }
+}
+
+impl HandlerBuilder for CmdPolicy {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                let policy =
+                    CmdPolicy::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                if ctx.env() != "group" || ctx.group_id() == 0 {
+                    return Err(AppError::command("只能在群聊中使用"));
+                }
+                let group_id = ctx.group_id();
+                let operator = ctx.sender();
+                let role = ctx.role();
+                if !role.is_manager() {
+                    return Err(AppError::permission("只有群管理员可以管理命令"));
+                }
+
+                let db = DB_GLOBAL
+                    .get()
+                    .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
+                let service = GroupCommandPolicyService::new(db.clone());
+
+                if let Some(name) = policy.command.target() {
+                    if name != COMMAND_LLM && !CMD_REGISTRY.contains_key(name) {
+                        return Err(AppError::validation(format!("命令 {} 不存在", name)));
+                    }
+                    // 不能修改限制在自己角色之上的命令
+                    let current = service.policy(group_id, name).await?;
+                    if let Some(min_role) = current.min_role.filter(|min_role| *min_role > role) {
+                        return Err(AppError::permission(format!(
+                            "{} 命令需要 {} 及以上角色才能修改",
+                            name,
+                            min_role.as_str()
+                        )));
+                    }
+                }
+
+                let output = match policy.command {
+                    PolicyCommand::List => {
+                        let policies = service.list(group_id).await?;
+                        if policies.is_empty() {
+                            "本群所有命令均为默认设置".to_string()
+                        } else {
+                            let lines = policies
+                                .iter()
+                                .map(|p| {
+                                    format!(
+                                        "{}：{}，{}",
+                                        p.command,
+                                        if p.enabled { "启用" } else { "停用" },
+                                        p.min_role.as_deref().unwrap_or("不限角色")
+                                    )
+                                })
+                                .collect::<Vec<_>>()
+                                .join("\n");
+                            format!("本群的命令策略：\n{}", lines)
+                        }
+                    }
+                    PolicyCommand::Enable { name } => {
+                        service.set_enabled(group_id, &name, true, operator).await?;
+                        format!("已在本群启用 {}", name)
+                    }
+                    PolicyCommand::Disable { name } => {
+                        if name == COMMAND_SELF {
+                            return Err(AppError::validation("不能停用 cmd 命令"));
+                        }
+                        service.set_enabled(group_id, &name, false, operator).await?;
+                        format!("已在本群停用 {}", name)
+                    }
+                    PolicyCommand::Role { name, role: min_role } => {
+                        let min_role = match min_role {
+                            Some(min_role) => Some(Role::parse(&min_role).ok_or_else(|| {
+                                AppError::validation(format!("未知角色 {}，可选 member, admin, owner, bot_admin", min_role))
+                            })?),
+                            None => None,
+                        };
+                        if min_role.is_some_and(|min_role| min_role > role) {
+                            return Err(AppError::permission("不能设置高于自己的角色"));
+                        }
+                        service.set_min_role(group_id, &name, min_role, operator).await?;
+                        match min_role {
+                            Some(min_role) => format!("本群中 {} 需要 {} 及以上角色", name, min_role.as_str()),
+                            None => format!("本群中 {} 不再限制角色", name),
+                        }
+                    }
+                    PolicyCommand::Reset { name } => {
+                        if !service.reset(group_id, &name).await? {
+                            return Err(AppError::not_found(format!("{} 的命令策略", name)));
+                        }
+                        format!("{} 已恢复默认设置", name)
+                    }
+                };
+                Ok(CmdResult::text(output))
+            })
+        })
+    }
+}
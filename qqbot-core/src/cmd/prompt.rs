@@ -1,6 +1,6 @@
 use crate::{
     BOT_CACHE, UserData,
-    cmd::{CmdHandler, CmdResult, CommonArgs, HandlerBuilder},
+    cmd::{CmdContext, CmdHandler, CmdResult, HandlerBuilder},
     config::APPCONFIG,
     error::AppError,
 };
@@ -10,9 +10,6 @@ use clap::Parser;
 #[command(name = "prompt")]
 #[command(about = "设置或查看当前用户的自定义提示词")]
 pub struct Prompt {
-    #[command(flatten)]
-    pub common: CommonArgs,
-
     #[arg(help = "设置提示词内容，留空则查看当前提示词")]
     content: Option<String>,
     
@@ -22,27 +19,25 @@ pub struct Prompt {
 
 impl HandlerBuilder for Prompt {
     fn build() -> CmdHandler {
-        Box::new(move |args: Vec<String>| {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
             Box::pin(async move {
                 let prompt = Prompt::try_parse_from(args)
                     .map_err(|err| AppError::command(err.to_string()))?;
 
                 // 检查权限：群聊中只有管理员能使用，私聊中用户自己可以使用
-                if !prompt.common.group_admin && prompt.common.env == String::from("group") {
+                if !ctx.group_admin() && ctx.env == String::from("group") {
                     return Err(AppError::command("群聊中只有管理员能使用此命令".to_string()));
                 }
 
                 // 获取当前用户数据
-                let mut user_data = BOT_CACHE.get(&prompt.common.sender).await.unwrap_or_default();
+                let mut user_data = BOT_CACHE.get(&ctx.sender).await.unwrap_or_default();
 
                 if prompt.reset {
                     // 重置提示词
                     user_data.custom_prompt = None;
-                    BOT_CACHE.insert(prompt.common.sender, user_data).await;
+                    BOT_CACHE.insert(ctx.sender, user_data).await;
                     
-                    Ok(CmdResult {
-                        output: "✅ 已重置为默认系统提示词".to_string(),
-                    })
+                    Ok(CmdResult::text("✅ 已重置为默认系统提示词".to_string()))
                 } else if let Some(content) = prompt.content {
                     // 设置新的提示词
                     if content.trim().is_empty() {
@@ -54,23 +49,17 @@ impl HandlerBuilder for Prompt {
                     }
                     
                     user_data.custom_prompt = Some(content.clone());
-                    BOT_CACHE.insert(prompt.common.sender, user_data).await;
+                    BOT_CACHE.insert(ctx.sender, user_data).await;
                     
-                    Ok(CmdResult {
-                        output: format!("✅ 提示词设置成功！\n\n📝 当前提示词:\n{}", content),
-                    })
+                    Ok(CmdResult::text(format!("✅ 提示词设置成功！\n\n📝 当前提示词:\n{}", content)))
                 } else {
                     // 查看当前提示词
                     match user_data.custom_prompt {
                         Some(custom_prompt) => {
-                            Ok(CmdResult {
-                                output: format!("📝 当前自定义提示词:\n{}\n\n💡 使用 /prompt --reset 可重置为默认提示词", custom_prompt),
-                            })
+                            Ok(CmdResult::text(format!("📝 当前自定义提示词:\n{}\n\n💡 使用 /prompt --reset 可重置为默认提示词", custom_prompt)))
                         }
                         None => {
-                            Ok(CmdResult {
-                                output: format!("📝 当前使用默认系统提示词:\n{}\n\n💡 使用 /prompt <内容> 可设置自定义提示词", APPCONFIG.llm.system_prompt),
-                            })
+                            Ok(CmdResult::text(format!("📝 当前使用默认系统提示词:\n{}\n\n💡 使用 /prompt <内容> 可设置自定义提示词", APPCONFIG.llm.system_prompt)))
                         }
                     }
                 }
@@ -1,65 +1,254 @@
+use std::time::Duration;
+
 use clap::Parser;
+
 use crate::{
-    cmd::{CmdResult, CommonArgs, HandlerBuilder},
+    action::{self, BotAction},
+    cmd::{CmdContext, CmdHandler, CmdResult, CmdStatus, HandlerBuilder, session::Awaiting},
+    config::DB_GLOBAL,
     error::AppError,
+    permission::check_permission,
+    reply_strategy::MessageContent,
+    repo::student::{StudentRepo, StudentRepository},
+    service::template_service::TemplateService,
 };
-use std::{future::Future, pin::Pin};
+
+/// 逐条发送之间的间隔，避免发送过快
+const SEND_INTERVAL: Duration = Duration::from_millis(100);
 
 #[derive(Parser, Debug, Clone)]
 #[command(name = "push")]
-#[command(about = "推送消息到群成员（私聊中使用，需要群管理员权限）")]
+#[command(about = "推送消息到群成员（私聊中使用，需要群管理员权限），缺少的参数会逐步询问")]
 pub struct Push {
-    #[command(flatten)]
-    pub common: CommonArgs,
-
     #[arg(short = 'g', long, help = "目标群号")]
-    pub group_id: i64,
+    pub group_id: Option<i64>,
 
     #[arg(short = 'm', long, help = "消息内容")]
-    pub message: String,
+    pub message: Option<String>,
 
     #[arg(short = 'l', long, help = "目标成员QQ号列表", num_args = 1..)]
     pub members: Vec<i64>,
 }
 
+/// 推送草稿：依次补齐目标群、成员和消息内容，确认后发送
+#[derive(Debug, Clone, Default)]
+struct Draft {
+    sender: i64,
+    group_id: Option<i64>,
+    members: Vec<i64>,
+    message: Option<String>,
+}
+
 impl HandlerBuilder for Push {
-    fn build() -> crate::cmd::CmdHandler {
-        Box::new(|args: Vec<String>| {
+    fn build() -> CmdHandler {
+        Box::new(|ctx: CmdContext, args: Vec<String>| {
             Box::pin(async move {
-                let push = Push::try_parse_from(std::iter::once("push".to_string()).chain(args))
-                    .map_err(|e| AppError::command(e.to_string()))?;
+                let push = Push::try_parse_from(args).map_err(|e| AppError::command(e.to_string()))?;
 
                 // 只能在私聊中使用
-                if push.common.env() != "private" {
+                if ctx.env() != "private" {
                     return Err(AppError::command("❌ 此命令只能在私聊中使用".to_string()));
                 }
-
-                // 验证参数
-                if push.group_id <= 0 {
-                    return Err(AppError::command("❌ 请指定有效的群号".to_string()));
+                if let Some(group_id) = push.group_id {
+                    check_group(ctx.sender(), group_id).await?;
                 }
 
-                if push.message.trim().is_empty() {
-                    return Err(AppError::command("❌ 消息内容不能为空".to_string()));
+                let draft = Draft {
+                    sender: ctx.sender(),
+                    group_id: push.group_id,
+                    members: push.members,
+                    message: push.message.filter(|message| !message.trim().is_empty()),
+                };
+                Ok(next_step(draft))
+            })
+        })
+    }
+}
+
+/// 询问草稿中缺少的下一项，全部齐全后请求确认
+fn next_step(draft: Draft) -> CmdResult {
+    if draft.group_id.is_none() {
+        CmdResult::awaiting("请发送目标群号，发送 /cancel 取消", ask_group(draft))
+    } else if draft.members.is_empty() {
+        CmdResult::awaiting("请发送目标成员的QQ号，多个用空格分隔", ask_members(draft))
+    } else if draft.message.is_none() {
+        CmdResult::awaiting("请发送要推送的消息内容", ask_message(draft))
+    } else {
+        let prompt = format!(
+            "将向群 {} 的 {} 名成员推送：\n{}\n\n回复“确认”发送，回复其他内容取消",
+            draft.group_id.unwrap_or_default(),
+            draft.members.len(),
+            draft.message.as_deref().unwrap_or_default()
+        );
+        CmdResult::awaiting(prompt, ask_confirm(draft))
+    }
+}
+
+fn ask_group(draft: Draft) -> Awaiting {
+    Awaiting::new(move |input: String| {
+        let mut draft = draft.clone();
+        async move {
+            match input.parse::<i64>() {
+                Ok(group_id) if group_id > 0 => {
+                    check_group(draft.sender, group_id).await?;
+                    draft.group_id = Some(group_id);
+                    Ok(next_step(draft))
                 }
+                _ => Ok(CmdResult::awaiting("群号应为数字，请重新发送，发送 /cancel 取消", ask_group(draft))),
+            }
+        }
+    })
+}
 
-                if push.members.is_empty() {
-                    return Err(AppError::command("❌ 请指定至少一个目标成员QQ号".to_string()));
+fn ask_members(draft: Draft) -> Awaiting {
+    Awaiting::new(move |input: String| {
+        let mut draft = draft.clone();
+        async move {
+            match parse_members(&input) {
+                Some(members) => {
+                    draft.members = members;
+                    Ok(next_step(draft))
                 }
+                None => Ok(CmdResult::awaiting(
+                    "QQ号应为数字，多个用空格分隔，请重新发送，发送 /cancel 取消",
+                    ask_members(draft),
+                )),
+            }
+        }
+    })
+}
+
+fn ask_message(draft: Draft) -> Awaiting {
+    Awaiting::new(move |input: String| {
+        let mut draft = draft.clone();
+        async move {
+            if input.is_empty() {
+                return Ok(CmdResult::awaiting("消息内容不能为空，请重新发送", ask_message(draft)));
+            }
+            draft.message = Some(input);
+            Ok(next_step(draft))
+        }
+    })
+}
+
+fn ask_confirm(draft: Draft) -> Awaiting {
+    Awaiting::new(move |input: String| {
+        let draft = draft.clone();
+        async move {
+            if !matches!(input.to_lowercase().as_str(), "确认" | "yes" | "y") {
+                return Ok(CmdResult::text("已取消推送").with_status(CmdStatus::Cancelled));
+            }
+            send(draft).await
+        }
+    })
+}
+
+/// 解析空格或逗号分隔的QQ号，含有非数字时返回 None
+fn parse_members(input: &str) -> Option<Vec<i64>> {
+    let members: Vec<i64> = input
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '，')
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse().ok().filter(|&qq: &i64| qq > 0))
+        .collect::<Option<_>>()?;
+    (!members.is_empty()).then_some(members)
+}
+
+/// 只有目标群的管理员或机器人管理员可以推送
+async fn check_group(sender: i64, group_id: i64) -> Result<(), AppError> {
+    if check_permission(sender).await {
+        return Ok(());
+    }
+    let role = action::executor()?.member_role(group_id, sender).await?;
+    if !role.is_manager() {
+        return Err(AppError::permission("❌ 您不是该群的管理员，无法使用此功能"));
+    }
+    Ok(())
+}
+
+/// 按 push.message 模板逐个私聊成员
+async fn send(draft: Draft) -> Result<CmdResult, AppError> {
+    let (Some(group_id), Some(message)) = (draft.group_id, draft.message) else {
+        return Err(AppError::command("推送信息不完整，请重新发送 /push"));
+    };
+    let db = DB_GLOBAL
+        .get()
+        .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
+    let executor = action::executor()?;
+    let students = StudentRepo::new(db.clone());
+    let templates = TemplateService::new(db.clone());
+
+    let mut failed_members = Vec::new();
+    for &member_qq in &draft.members {
+        let student = students.find_by_qq(member_qq).await.ok().flatten();
+        let result = match templates.render_push(&message, member_qq, student).await {
+            Ok(text) => {
+                executor
+                    .execute(BotAction::SendPrivate {
+                        user_id: member_qq,
+                        content: MessageContent::Text(text),
+                    })
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            failed_members.push(format!("QQ{}: {}", member_qq, err));
+        }
+        tokio::time::sleep(SEND_INTERVAL).await;
+    }
+
+    let total_count = draft.members.len();
+    Ok(CmdResult::text(format!(
+        "📤 推送完成！\n\n📊 统计信息：\n• 目标群：{}\n• 成功：{}条\n• 失败：{}条\n• 总计：{}条{}",
+        group_id,
+        total_count - failed_members.len(),
+        failed_members.len(),
+        total_count,
+        if failed_members.is_empty() {
+            String::new()
+        } else {
+            format!("\n❌ 失败详情：\n{}", failed_members.join("\n"))
+        }
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_members() {
+        assert_eq!(parse_members("10001 10002，10003"), Some(vec![10001, 10002, 10003]));
+        assert_eq!(parse_members("10001 abc"), None);
+        assert_eq!(parse_members("  "), None);
+    }
+
+    #[tokio::test]
+    async fn test_push_asks_missing_fields() {
+        let ctx = CmdContext {
+            sender: 1,
+            ..Default::default()
+        };
+        // 命令名已在参数中，不能再重复添加
+        let args = vec!["push".to_string(), "-l".to_string(), "10001".to_string()];
+        let result = (Push::build())(ctx.clone(), args).await.unwrap();
+        assert_eq!(result.status, CmdStatus::AwaitingInput);
+        assert!(result.output().contains("群号"));
+
+        // 已知群号时问成员，成员齐全时问内容，最后请求确认
+        let draft = Draft { sender: 1, group_id: Some(2), ..Default::default() };
+        assert!(next_step(draft.clone()).output().contains("QQ号"));
+        let draft = Draft { members: vec![10001], ..draft };
+        assert!(next_step(draft.clone()).output().contains("消息内容"));
+        let draft = Draft { message: Some("明天交作业".to_string()), ..draft };
+        let result = next_step(draft);
+        assert!(result.output().contains("确认") && result.awaiting.is_some());
 
-                // 返回说明信息，实际的消息发送由push插件处理
-                Ok(CmdResult {
-                    output: format!(
-                        "📝 Push命令已记录，但实际的消息发送需要通过push插件处理。\n\n参数信息：\n• 群号：{}\n• 消息：\"{}\"\n• 目标成员：{:?}\n\n💡 请使用插件格式: /push -g {} -m \"{}\" -l {}",
-                        push.group_id,
-                        push.message,
-                        push.members,
-                        push.group_id,
-                        push.message,
-                        push.members.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(" ")
-                    ),
-                })
-            }) as Pin<Box<dyn Future<Output = Result<CmdResult, AppError>> + Send>>
-        }) as crate::cmd::CmdHandler
+        // 回复确认以外的内容时取消，不发送
+        let key = crate::cmd::session::SessionKey::new(1, None);
+        let mut result = CmdResult::awaiting("确认？", ask_confirm(Draft::default()));
+        crate::cmd::session::track(key, &mut result).await;
+        let result = crate::cmd::session::resume(key, "不了").await.unwrap().unwrap();
+        assert_eq!(result.status, CmdStatus::Cancelled);
     }
 }
@@ -2,12 +2,20 @@ use std::pin::Pin;
 
 // 1. 修正 use 语句：不需要 clap_derive::Parser，只需要 clap::Parser trait
 use crate::{
+    chart,
     config::DB_GLOBAL,
-    service::grade_service::{GradeService, GradeServiceImpl},
+    models::grade::Model as GradeModel,
+    reply_strategy::{Delivery, FileAttachment, MessageContent},
+    service::{
+        StuServiceImpl, UserService,
+        grade_service::{CourseStats, GradeService, GradeServiceImpl},
+        template_service::TemplateService,
+    },
+    template::QUERY_GRADE_SUMMARY,
 };
 use clap::{Parser, Subcommand, ValueEnum};
 
-use super::{CmdHandler, CmdResult, CommonArgs, HandlerBuilder}; // 移除 clap_derive::Parser
+use super::{CmdContext, CmdHandler, CmdResult, HandlerBuilder}; // 移除 clap_derive::Parser
 use crate::error::AppError;
 
 #[derive(Parser, Debug)]
@@ -18,8 +26,6 @@ use crate::error::AppError;
     about = "query grade for students"
 )]
 pub struct Query {
-    #[command(flatten)]
-    pub common: CommonArgs,
     #[command(subcommand)]
     commands: QueryItem, // 子命令字段
 }
@@ -49,12 +55,25 @@ pub enum QueryItem {
             ignore_case = true,
             value_enum,
             default_value_t = GradeQueryMode::Summary, // 提供默认值
+            // 查看他人成绩和画图时不区分模式，显式指定时报错而不是悄悄忽略
+            conflicts_with_all = ["student", "chart"],
             help="mode: Summary,Quiv1,Quiv2,Quiv3,Quiv4,Mid"
         )]
         mode: GradeQueryMode,
+        /// 查看指定学号的成绩（教师、助教或管理员）
+        #[arg(long, help = "学号，不填时查询自己")]
+        student: Option<i64>,
+        /// 以图片发送各次考试的成绩走势及班级平均分
+        #[arg(long, default_value_t = false)]
+        chart: bool,
+    },
+    /// 考试成绩统计（教师、助教或管理员），只统计有权查看的课程和学生
+    Stats {
+        #[arg(long, help = "考试名称，如 Mid")]
+        exam: String,
+        #[arg(long, help = "课程号，不填时统计全部课程")]
+        course: Option<i32>,
     },
-    // 如果有其他子命令，可以继续在这里添加
-    // OtherSubcommand { ... }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -68,41 +87,76 @@ pub enum GradeQueryMode {
 }
 impl HandlerBuilder for Query {
     fn build() -> CmdHandler {
-        Box::new(move |args: Vec<String>| {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
             // 将 async 块的结果明确赋值给一个变量
             let fut = async move {
                 let query = Query::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                // 成绩只私聊发送，在群里查询也不会公开
+                let delivery = if ctx.env() == "group" { Delivery::Private } else { Delivery::InPlace };
                 match query.commands {
-                    QueryItem::Grade {  mode } => {                        let conn = DB_GLOBAL
+                    QueryItem::Grade { chart: true, student, .. } => {
+                        let conn = DB_GLOBAL
+                            .get()
+                            .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
+                        let (student, points) = GradeServiceImpl::new(conn.clone())
+                            .grade_trend(ctx.sender, student)
+                            .await?;
+                        // 画图和 PNG 编码是 CPU 密集的同步操作，放到阻塞线程池中执行
+                        let title = format!("{} 的成绩走势", student.name);
+                        let png = tokio::task::spawn_blocking(move || chart::render_grade_chart(&title, &points))
+                            .await
+                            .map_err(|err| AppError::internal(format!("绘制图表失败: {}", err)))??;
+                        let image = MessageContent::Image(FileAttachment {
+                            name: format!("grade_{}.png", student.student_id),
+                            content: png,
+                            mime_type: Some("image/png".to_string()),
+                        });
+                        Ok(CmdResult::new(image).with_delivery(delivery))
+                    }
+                    QueryItem::Grade { student: Some(student_id), .. } => {
+                        let conn = DB_GLOBAL
+                            .get()
+                            .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
+                        let grades = GradeServiceImpl::new(conn.clone())
+                            .find_grades(ctx.sender, Some(student_id))
+                            .await?;
+                        Ok(CmdResult::text(grade_table(student_id, &grades)).with_delivery(delivery))
+                    }
+                    QueryItem::Stats { exam, course } => {
+                        let conn = DB_GLOBAL
+                            .get()
+                            .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
+                        let stats = GradeServiceImpl::new(conn.clone())
+                            .exam_stats(ctx.sender, &exam, course)
+                            .await?;
+                        Ok(CmdResult::text(stats_table(&exam, &stats)).with_delivery(delivery))
+                    }
+                    QueryItem::Grade { mode, student: None, .. } => {
+                        let conn = DB_GLOBAL
                             .get()
                             .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
 
                         let grade_repo = GradeServiceImpl::new(conn.clone()); // 确保 conn 可以 clone
                         let grades = grade_repo
-                        .find_grades(query.common.sender)
+                        .find_grades(ctx.sender, None)
                         .await
-                        .map_err(|err|AppError::command(format!("bind your student number with /bind (id) first:{}",err.to_string())))?;
+                        .map_err(|err|AppError::command(format!("bind your student number with /bind (id) first:{}", err)))?;
 
                         match mode {
                             GradeQueryMode::Summary => {
-                                // 优化: 使用 String::new() 和 push_str 效率更高
-                                let mut report_str = String::new();
-                                for record in grades {
-                                    // 假设 record 有 student_name, exam_name, score 字段
-                                    use std::fmt::Write; // 引入 Write trait 以使用 write! 宏
-                                    // 使用 write! 宏避免多次分配 String
-
-                                    write!(
-                                         &mut report_str,
-                                         "{} 在 {} 中获得 {} 分;\n",
-                                         record.student_name, record.exam_name, record.score
-                                     ).map_err(|_| AppError::command(String::from("failed to format report")))?; // 处理 write! 可能的错误
-                                     if record.score<60 {
-                                         write!(&mut report_str,"请多花时间，微积分对未来的课程非常重要，务必掌握\n").map_err(|_| AppError::command(String::from("failed to format report")))?; // 处理 write! 可能的错误
-                                     }
-                                }
-                                // 移除不必要的字节转换和 UTF-8 转换
-                                Ok(CmdResult { output: report_str })
+                                // 成绩汇总的文案由消息模板决定，可在管理后台修改
+                                let student = StuServiceImpl::new(conn.clone())
+                                    .find_by_qq(ctx.sender)
+                                    .await
+                                    .map_err(|err| AppError::command(err.to_string()))?;
+                                let report_str = TemplateService::new(conn.clone())
+                                    .render(
+                                        QUERY_GRADE_SUMMARY,
+                                        minijinja::context! { student => student, grades => grades },
+                                    )
+                                    .await
+                                    .map_err(|err| AppError::command(err.to_string()))?;
+                                Ok(CmdResult::text(report_str).with_delivery(delivery))
                             }
                             _ => Err(AppError::command(format!("Query mode {:?} not supported yet.", mode))), // 提供更具体的错误信息
                         }
@@ -136,3 +190,53 @@ impl HandlerBuilder for Query {
            as CmdHandler
     }
 }
+
+/// 指定学生的成绩表
+fn grade_table(student_id: i64, grades: &[GradeModel]) -> String {
+    let Some(first) = grades.first() else {
+        return format!("没有学号 {} 中你有权查看的成绩", student_id);
+    };
+    let mut lines = vec![
+        format!("学号 {} {}", student_id, first.student_name),
+        "考试 | 课程 | 分数".to_string(),
+    ];
+    lines.extend(
+        grades
+            .iter()
+            .map(|g| format!("{} | {} | {}", g.exam_name, g.course_id, g.score)),
+    );
+    lines.join("\n")
+}
+
+/// 考试各课程的统计表
+fn stats_table(exam: &str, stats: &[CourseStats]) -> String {
+    let mut lines = vec![
+        format!("{} 成绩统计", exam),
+        "课程 | 人数 | 平均 | 最高 | 最低 | 及格率".to_string(),
+    ];
+    lines.extend(stats.iter().map(|s| {
+        format!(
+            "{} | {} | {:.1} | {} | {} | {:.0}%",
+            s.course_id,
+            s.count,
+            s.average,
+            s.max,
+            s.min,
+            s.passed as f64 * 100.0 / s.count as f64
+        )
+    }));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_conflicts() {
+        assert!(Query::try_parse_from(["query", "grade", "--mode", "mid"]).is_ok());
+        assert!(Query::try_parse_from(["query", "grade", "--student", "1"]).is_ok());
+        assert!(Query::try_parse_from(["query", "grade", "--student", "1", "--mode", "mid"]).is_err());
+        assert!(Query::try_parse_from(["query", "grade", "--chart", "--mode", "summary"]).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use clap::{Parser, Subcommand};
+
+use crate::{
+    cmd::{CmdContext, CmdHandler, CmdResult, HandlerBuilder},
+    config::DB_GLOBAL,
+    error::AppError,
+    models::join_request::STATUS_PENDING,
+    permission::check_permission,
+    service::join_request_service::JoinRequestService,
+};
+
+#[derive(Parser, Debug)]
+#[command(name = "request")]
+#[command(about = "审批好友和加群申请（仅机器人管理员）")]
+pub struct Request {
+    #[command(subcommand)]
+    command: RequestCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RequestCommand {
+    /// 查看等待审批的申请
+    #[command(name = "list")]
+    List,
+    /// 同意申请
+    #[command(name = "approve")]
+    Approve {
+        #[arg(help = "申请编号")]
+        id: i64,
+    },
+    /// 拒绝申请，可附上理由
+    #[command(name = "reject")]
+    Reject {
+        #[arg(help = "申请编号")]
+        id: i64,
+        #[arg(num_args = 0.., help = "拒绝理由")]
+        reason: Vec<String>,
+    },
+}
+
+impl HandlerBuilder for Request {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                let request =
+                    Request::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                let operator = ctx.sender();
+                if !check_permission(operator).await {
+                    return Err(AppError::permission("只有机器人管理员可以审批申请"));
+                }
+                let db = DB_GLOBAL
+                    .get()
+                    .ok_or_else(|| AppError::command(String::from("failed to connect database")))?;
+                let service = JoinRequestService::new(db.clone());
+
+                let output = match request.command {
+                    RequestCommand::List => {
+                        let (requests, total) = service.list(Some(STATUS_PENDING.to_string()), 1, 10).await?;
+                        if requests.is_empty() {
+                            "没有等待审批的申请".to_string()
+                        } else {
+                            let lines = requests
+                                .iter()
+                                .map(|r| format!("#{} QQ {}：{}（{}）", r.id, r.user_id, r.comment, r.reason))
+                                .collect::<Vec<_>>()
+                                .join("\n");
+                            format!("等待审批的申请（共 {} 条）：\n{}", total, lines)
+                        }
+                    }
+                    RequestCommand::Approve { id } => {
+                        service.decide(id, true, None, Some(operator)).await?;
+                        format!("已同意申请 #{}，稍后执行", id)
+                    }
+                    RequestCommand::Reject { id, reason } => {
+                        service.decide(id, false, Some(reason.join(" ")), Some(operator)).await?;
+                        format!("已拒绝申请 #{}，稍后执行", id)
+                    }
+                };
+                Ok(CmdResult::text(output))
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reject_reason_cannot_set_sender() {
+        // 审批人来自消息的发送者，理由里的 --sender 不会被当作身份
+        assert!(Request::try_parse_from(["request", "reject", "5", "--sender", "10001", "--"]).is_err());
+        let request = Request::try_parse_from(["request", "reject", "5", "--", "--sender", "10001"]).unwrap();
+        match request.command {
+            RequestCommand::Reject { id, reason } => {
+                assert_eq!(id, 5);
+                assert_eq!(reason, ["--sender", "10001"]);
+            }
+            _ => panic!("expected reject"),
+        }
+    }
+}
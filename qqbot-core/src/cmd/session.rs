@@ -0,0 +1,129 @@
+use std::{fmt, future::Future, pin::Pin, sync::Arc};
+
+use clap::Parser;
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+
+use crate::{
+    GroupId, UserId,
+    cmd::{CmdContext, CmdHandler, CmdResult, CmdStatus, HandlerBuilder},
+    config::APPCONFIG,
+    error::AppError,
+};
+
+type StepFuture = Pin<Box<dyn Future<Output = Result<CmdResult, AppError>> + Send>>;
+
+/// 多轮命令的会话，同一用户在私聊和不同群里的会话互不影响
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionKey {
+    pub user_id: UserId,
+    pub group_id: Option<GroupId>,
+}
+
+impl SessionKey {
+    pub fn new(user_id: UserId, group_id: Option<GroupId>) -> Self {
+        Self { user_id, group_id }
+    }
+}
+
+/// 命令等待用户输入时的下一步，参数为用户发来的文字。
+/// 输入有误需要重新输入时，返回带有 awaiting 的结果即可继续等待
+#[derive(Clone)]
+pub struct Awaiting(Arc<dyn Fn(String) -> StepFuture + Send + Sync>);
+
+impl Awaiting {
+    pub fn new<F, Fut>(step: F) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<CmdResult, AppError>> + Send + 'static,
+    {
+        Self(Arc::new(move |input| Box::pin(step(input))))
+    }
+}
+
+impl fmt::Debug for Awaiting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Awaiting")
+    }
+}
+
+// 等待输入的会话，超过 cmd_session_timeout 未回复自动结束
+static SESSIONS: Lazy<Cache<SessionKey, Awaiting>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(APPCONFIG.cache.cache_capacity)
+        .time_to_live(APPCONFIG.cache.cmd_session_timeout)
+        .build()
+});
+
+/// 命令结果需要继续输入时开始等待，会覆盖该会话之前未完成的命令
+pub async fn track(key: SessionKey, result: &mut CmdResult) {
+    if let Some(awaiting) = result.awaiting.take() {
+        SESSIONS.insert(key, awaiting).await;
+    }
+}
+
+/// 把用户的下一条消息交给等待中的命令，没有等待中的命令时返回 None。
+/// 出错时会话结束，用户需要重新发送命令
+pub async fn resume(key: SessionKey, input: &str) -> Option<Result<CmdResult, AppError>> {
+    let awaiting = SESSIONS.remove(&key).await?;
+    let mut result = (awaiting.0)(input.trim().to_string()).await;
+    if let Ok(result) = &mut result {
+        track(key, result).await;
+    }
+    Some(result)
+}
+
+pub async fn cancel(key: SessionKey) -> bool {
+    SESSIONS.remove(&key).await.is_some()
+}
+
+#[derive(Parser, Debug)]
+#[command(name = "cancel")]
+#[command(about = "取消正在等待输入的命令")]
+pub struct Cancel {}
+
+impl HandlerBuilder for Cancel {
+    fn build() -> CmdHandler {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
+            Box::pin(async move {
+                Cancel::try_parse_from(args).map_err(|err| AppError::command(err.to_string()))?;
+                if cancel(ctx.session_key()).await {
+                    Ok(CmdResult::text("已取消").with_status(CmdStatus::Cancelled))
+                } else {
+                    Ok(CmdResult::text("当前没有等待输入的命令"))
+                }
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo() -> Awaiting {
+        Awaiting::new(|input: String| async move {
+            if input == "again" {
+                Ok(CmdResult::awaiting("再说一次", echo()))
+            } else {
+                Ok(CmdResult::text(input))
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_session_resume_and_cancel() {
+        let key = SessionKey::new(1, None);
+        let mut start = CmdResult::awaiting("请输入", echo());
+        track(key, &mut start).await;
+        assert!(start.awaiting.is_none());
+
+        assert!(resume(key, " again ").await.unwrap().is_ok());
+        assert_eq!(resume(key, "done").await.unwrap().unwrap().output(), "done");
+        assert!(resume(key, "ignored").await.is_none());
+
+        track(key, &mut CmdResult::awaiting("请输入", echo())).await;
+        assert!(cancel(key).await);
+        assert!(!cancel(key).await);
+    }
+}
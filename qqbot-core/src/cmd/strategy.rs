@@ -1,10 +1,11 @@
 use crate::{
     StrategeType,
-    cmd::{CmdHandler, CmdResult, CommonArgs, HandlerBuilder},
+    cmd::{CmdContext, CmdHandler, CmdMeta, CmdResult, HandlerBuilder},
     config::APPCONFIG,
     error::AppError,
     service::user_config_service::UserConfigService,
     service::group_config_service::GroupConfigService,
+    speech::tts,
 };
 use clap::{Parser, Subcommand};
 use sea_orm::Database;
@@ -13,9 +14,6 @@ use sea_orm::Database;
 #[command(name = "strategy")]
 #[command(about = "切换回复策略")]
 pub struct Strategy {
-    #[command(flatten)]
-    pub common: CommonArgs,
-
     #[command(subcommand)]
     command: StrategyCommand,
 }
@@ -38,6 +36,12 @@ pub enum StrategyCommand {
         #[arg(long, help = "重置为默认提示词")]
         reset_prompt: bool,
     },
+    /// 开启或关闭语音回复
+    #[command(name = "voice")]
+    Voice {
+        #[arg(value_parser = ["on", "off"], help = "on 开启，off 关闭")]
+        state: String,
+    },
     /// 查询当前配置
     #[command(name = "query")]
     Query,
@@ -45,26 +49,26 @@ pub enum StrategyCommand {
 
 impl HandlerBuilder for Strategy {
     fn build() -> CmdHandler {
-        Box::new(move |args: Vec<String>| {
+        Box::new(move |ctx: CmdContext, args: Vec<String>| {
             Box::pin(async move {
                 let strategy = Strategy::try_parse_from(args)
                     .map_err(|err| AppError::command(err.to_string()))?;
 
                 // 检查是否为管理员或者用户自己
-                if !strategy.common.group_admin && strategy.common.env == String::from("group") {
+                if !ctx.group_admin() && ctx.env == "group" {
                     return Err(AppError::command("群聊中只有管理员能使用".to_string()));
                 }
 
                 // 初始化数据库连接
                 let database_url = &APPCONFIG.database.url;
                 let db = Database::connect(database_url).await
-                    .map_err(|e| AppError::Database(e))?;
+                    .map_err(AppError::Database)?;
                 
                 // 根据环境类型决定操作用户配置还是群组配置
-                if strategy.common.env == String::from("group") {
+                if ctx.env == "group" {
                     // 群聊环境：操作群组配置
                     let group_config_service = GroupConfigService::new(db);
-                    let group_id = strategy.common.group_id;
+                    let group_id = ctx.group_id;
                     
                     if group_id == 0 {
                         return Err(AppError::command("群组ID无效".to_string()));
@@ -82,9 +86,7 @@ impl HandlerBuilder for Strategy {
                             group_config_service.save_group_data(group_id, &group_data).await
                                 .map_err(|e| AppError::command(format!("保存群组配置失败: {}", e)))?;
 
-                            Ok(CmdResult {
-                                output: "✅ 已成功切换群组到命令模式！".to_string(),
-                            })
+                            Ok(CmdResult::text("✅ 已成功切换群组到命令模式！".to_string()))
                         }
                         StrategyCommand::Llm { model, prompt, reset_prompt } => {
                             // 切换群组到大模型聊天模式
@@ -134,9 +136,14 @@ impl HandlerBuilder for Strategy {
                                 }
                             }
 
-                            Ok(CmdResult {
-                                output: messages.join("\n\n"),
-                            })
+                            Ok(CmdResult::text(messages.join("\n\n")))
+                        }
+                        StrategyCommand::Voice { state } => {
+                            group_data.voice_reply = state == "on";
+                            group_config_service.save_group_data(group_id, &group_data).await
+                                .map_err(|e| AppError::command(format!("保存群组配置失败: {}", e)))?;
+
+                            Ok(CmdResult::text(voice_reply_message(group_data.voice_reply)))
                         }
                         StrategyCommand::Query => {
                             // 查询群组当前配置
@@ -148,6 +155,7 @@ impl HandlerBuilder for Strategy {
                                 StrategeType::LlmStrategy => "大模型聊天模式",
                             };
                             messages.push(format!("🔧 回复策略: {}", strategy_name));
+                            messages.push(format!("🔊 语音回复: {}", if group_data.voice_reply { "开启" } else { "关闭" }));
                             
                             // 如果是 LLM 模式，显示模型和提示词信息
                             if matches!(group_data.stratege, StrategeType::LlmStrategy) {
@@ -163,15 +171,13 @@ impl HandlerBuilder for Strategy {
                                 }
                             }
                             
-                            Ok(CmdResult {
-                                output: messages.join("\n\n"),
-                            })
+                            Ok(CmdResult::text(messages.join("\n\n")))
                         }
                     }
                 } else {
                     // 私聊环境：操作用户配置
                     let user_config_service = UserConfigService::new(db);
-                    let user_id = strategy.common.sender;
+                    let user_id = ctx.sender;
 
                     // 获取当前用户配置
                     let mut user_data = user_config_service.get_user_data(user_id).await
@@ -186,9 +192,7 @@ impl HandlerBuilder for Strategy {
                             user_config_service.save_user_data(user_id, &user_data).await
                                 .map_err(|e| AppError::command(format!("保存用户配置失败: {}", e)))?;
 
-                            Ok(CmdResult {
-                                output: "✅ 已成功切换到命令模式！".to_string(),
-                            })
+                            Ok(CmdResult::text("✅ 已成功切换到命令模式！".to_string()))
                         }
                         StrategyCommand::Llm { model, prompt, reset_prompt } => {
                             // 切换到大模型聊天模式
@@ -238,9 +242,14 @@ impl HandlerBuilder for Strategy {
                                 }
                             }
 
-                            Ok(CmdResult {
-                                output: messages.join("\n\n"),
-                            })
+                            Ok(CmdResult::text(messages.join("\n\n")))
+                        }
+                        StrategyCommand::Voice { state } => {
+                            user_data.voice_reply = state == "on";
+                            user_config_service.save_user_data(user_id, &user_data).await
+                                .map_err(|e| AppError::command(format!("保存用户配置失败: {}", e)))?;
+
+                            Ok(CmdResult::text(voice_reply_message(user_data.voice_reply)))
                         }
                         StrategyCommand::Query => {
                             // 查询用户当前配置
@@ -252,6 +261,7 @@ impl HandlerBuilder for Strategy {
                                 StrategeType::LlmStrategy => "大模型聊天模式",
                             };
                             messages.push(format!("🔧 回复策略: {}", strategy_name));
+                            messages.push(format!("🔊 语音回复: {}", if user_data.voice_reply { "开启" } else { "关闭" }));
                             
                             // 如果是 LLM 模式，显示模型和提示词信息
                             if matches!(user_data.stratege, StrategeType::LlmStrategy) {
@@ -267,13 +277,30 @@ impl HandlerBuilder for Strategy {
                                 }
                             }
                             
-                            Ok(CmdResult {
-                                output: messages.join("\n\n"),
-                            })
+                            Ok(CmdResult::text(messages.join("\n\n")))
                         }
                     }
                 }
             })
         })
     }
+
+    /// 群聊中修改会影响全群，需要确认，查询不需要。非管理员不发确认码，直接拒绝
+    fn meta() -> CmdMeta {
+        CmdMeta {
+            confirm: Some(|ctx, args| {
+                ctx.env() == "group" && ctx.group_admin() && args.get(1).map(String::as_str) != Some("query")
+            }),
+        }
+    }
+}
+
+fn voice_reply_message(enabled: bool) -> String {
+    if !enabled {
+        "🔇 已关闭语音回复".to_string()
+    } else if tts::is_enabled() {
+        "🔊 已开启语音回复，较短的回复将以语音发送".to_string()
+    } else {
+        "🔊 已开启语音回复，但机器人未启用语音合成，回复仍以文字发送".to_string()
+    }
 }
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use serde::Deserialize;
 
@@ -8,8 +8,33 @@ pub struct AppConfig {
     pub cmd_suffix: String,
     pub database: DatabaseConfig,
     pub cache: CacheConfig,
+    // 初始超级管理员，数据库中的授权见 permission 模块；与 kovi.conf.toml 中 kovi 自身的 admins 无关
     pub admins: Vec<i64>,
     pub llm: LlmConfig,
+    #[serde(default)]
+    pub notify: NotifyConfig,
+    #[serde(default)]
+    pub stt: SttConfig,
+    #[serde(default)]
+    pub tts: TtsConfig,
+    #[serde(default)]
+    pub memory: MemoryConfig,
+    #[serde(default)]
+    pub knowledge: KnowledgeConfig,
+    #[serde(default)]
+    pub faq: FaqConfig,
+    #[serde(default)]
+    pub rules: RulesConfig,
+    #[serde(default)]
+    pub content_filter: ContentFilterConfig,
+    #[serde(default)]
+    pub join_request: JoinRequestConfig,
+    #[serde(default)]
+    pub onboarding: OnboardingConfig,
+    #[serde(default)]
+    pub commands: CommandsConfig,
+    #[serde(default)]
+    pub chart: ChartConfig,
 }
 
 #[derive(Debug, Deserialize)]
@@ -23,6 +48,20 @@ pub struct CacheConfig {
     pub conversation_capacity: Option<u64>,
     pub max_conversation_history: Option<usize>,
     pub conversation_timeout_minutes: Option<i64>,
+    // 多轮命令等待用户输入的超时时间
+    #[serde(default = "default_cmd_session_timeout", with = "humantime_serde")]
+    pub cmd_session_timeout: Duration,
+    // 需要确认的命令等待 /confirm 的时间
+    #[serde(default = "default_cmd_confirm_timeout", with = "humantime_serde")]
+    pub cmd_confirm_timeout: Duration,
+}
+
+fn default_cmd_session_timeout() -> Duration {
+    Duration::from_secs(300)
+}
+
+fn default_cmd_confirm_timeout() -> Duration {
+    Duration::from_secs(60)
 }
 
 #[derive(Debug, Deserialize)]
@@ -55,8 +94,655 @@ pub struct LlmConfig {
     // 群聊自动捕获消息的配置
     #[serde(default = "default_auto_capture_group")]
     pub auto_capture_group_messages: bool,
+    // 历史对话可占用的 token 预算（按模型估算），超出部分不发送
+    #[serde(default = "default_context_tokens")]
+    pub context_tokens: usize,
+    // 是否将移出历史的旧消息压缩为摘要并放入系统提示词
+    #[serde(default = "default_summary_enabled")]
+    pub summary_enabled: bool,
+    #[serde(default = "default_summary_max_chars")]
+    pub summary_max_chars: usize,
+    // 图片识别相关配置
+    #[serde(default)]
+    pub vision: VisionConfig,
+}
+
+fn default_context_tokens() -> usize {
+    4000
+}
+
+fn default_summary_enabled() -> bool {
+    true
+}
+
+fn default_summary_max_chars() -> usize {
+    300
 }
 
 fn default_auto_capture_group() -> bool {
     false
 }
+
+#[derive(Debug, Deserialize)]
+pub struct VisionConfig {
+    // 单条消息最多发送给模型的图片数
+    #[serde(default = "default_max_images")]
+    pub max_images: usize,
+    // 超过该大小（字节）或边长的图片会被缩小后重新编码为JPEG
+    #[serde(default = "default_max_image_bytes")]
+    pub max_image_bytes: usize,
+    #[serde(default = "default_max_dimension")]
+    pub max_dimension: u32,
+    // 下载图片的大小上限（字节），超过时放弃识别该图片
+    #[serde(default = "default_max_download_bytes")]
+    pub max_download_bytes: usize,
+    // 图片缓存容量（字节）与过期时间，按 OneBot 的 file/key 缓存，重复的表情包不会重复下载
+    #[serde(default = "default_image_cache_capacity")]
+    pub cache_capacity: u64,
+    #[serde(default = "default_image_cache_ttl", with = "humantime_serde")]
+    pub cache_ttl: Duration,
+}
+
+impl Default for VisionConfig {
+    fn default() -> Self {
+        Self {
+            max_images: default_max_images(),
+            max_image_bytes: default_max_image_bytes(),
+            max_dimension: default_max_dimension(),
+            max_download_bytes: default_max_download_bytes(),
+            cache_capacity: default_image_cache_capacity(),
+            cache_ttl: default_image_cache_ttl(),
+        }
+    }
+}
+
+fn default_max_images() -> usize {
+    4
+}
+
+fn default_max_image_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_max_dimension() -> u32 {
+    1568
+}
+
+fn default_max_download_bytes() -> usize {
+    20 * 1024 * 1024
+}
+
+fn default_image_cache_capacity() -> u64 {
+    64 * 1024 * 1024
+}
+
+fn default_image_cache_ttl() -> Duration {
+    Duration::from_secs(30 * 60)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotifyConfig {
+    // 成绩发布后是否私聊通知已绑定QQ的学生（默认关闭）
+    #[serde(default)]
+    pub grade_enabled: bool,
+    // 每批最多发送的通知数量
+    #[serde(default = "default_notify_batch_size")]
+    pub batch_size: u64,
+    // 两批之间的间隔
+    #[serde(with = "humantime_serde", default = "default_notify_batch_interval")]
+    pub batch_interval: Duration,
+    // 同一批内相邻两条消息的间隔，避免触发风控
+    #[serde(with = "humantime_serde", default = "default_notify_send_delay")]
+    pub send_delay: Duration,
+    // 单条通知的最大尝试次数，超过后标记为失败
+    #[serde(default = "default_notify_max_attempts")]
+    pub max_attempts: i32,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            grade_enabled: false,
+            batch_size: default_notify_batch_size(),
+            batch_interval: default_notify_batch_interval(),
+            send_delay: default_notify_send_delay(),
+            max_attempts: default_notify_max_attempts(),
+        }
+    }
+}
+
+fn default_notify_batch_size() -> u64 {
+    20
+}
+
+fn default_notify_batch_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_notify_send_delay() -> Duration {
+    Duration::from_millis(1500)
+}
+
+fn default_notify_max_attempts() -> i32 {
+    3
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SttBackend {
+    // OpenAI 兼容的 /audio/transcriptions 接口
+    #[default]
+    OpenAi,
+    // 本地命令（如 whisper.cpp），{input} 替换为音频文件路径，标准输出为识别结果
+    Command,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SttConfig {
+    // 是否将语音消息转写为文字（默认关闭）
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub backend: SttBackend,
+    // 未配置时使用 llm 的 base_url 与 api_key
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+    #[serde(default = "default_stt_model")]
+    pub model: String,
+    pub command: Option<String>,
+    // silk/amr 等接口不支持的格式先用该命令解码为 wav，{input}/{output} 为文件路径
+    #[serde(default = "default_decoder_command")]
+    pub decoder_command: String,
+    // ffmpeg 无法解码 QQ 的 silk 语音，需配置 SILK SDK 的 decoder（如 kn007/silk-v3-decoder），
+    // 输出 24000Hz 的 PCM，程序会去掉文件开头的 0x02 并封装为 wav
+    pub silk_decoder_command: Option<String>,
+    #[serde(default = "default_max_audio_bytes")]
+    pub max_audio_bytes: usize,
+    #[serde(default = "default_stt_timeout", with = "humantime_serde")]
+    pub timeout: Duration,
+}
+
+impl Default for SttConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: SttBackend::default(),
+            base_url: None,
+            api_key: None,
+            model: default_stt_model(),
+            command: None,
+            decoder_command: default_decoder_command(),
+            silk_decoder_command: None,
+            max_audio_bytes: default_max_audio_bytes(),
+            timeout: default_stt_timeout(),
+        }
+    }
+}
+
+fn default_stt_model() -> String {
+    "FunAudioLLM/SenseVoiceSmall".to_string()
+}
+
+fn default_decoder_command() -> String {
+    "ffmpeg -y -loglevel error -i {input} -ar 16000 -ac 1 {output}".to_string()
+}
+
+fn default_max_audio_bytes() -> usize {
+    5 * 1024 * 1024
+}
+
+fn default_stt_timeout() -> Duration {
+    Duration::from_secs(60)
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TtsBackend {
+    // OpenAI 兼容的 /audio/speech 接口
+    #[default]
+    OpenAi,
+    // 本地命令，{text} 替换为回复文字，{output} 为输出音频路径
+    Command,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TtsConfig {
+    // 是否允许语音回复（默认关闭），开启后由用户或群管理员通过 /strategy voice on 启用
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub backend: TtsBackend,
+    // 未配置时使用 llm 的 base_url 与 api_key
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+    #[serde(default = "default_tts_model")]
+    pub model: String,
+    #[serde(default = "default_tts_voice")]
+    pub voice: String,
+    pub command: Option<String>,
+    // 超过该字数的回复仍以文字发送
+    #[serde(default = "default_tts_max_chars")]
+    pub max_chars: usize,
+    #[serde(default = "default_tts_timeout", with = "humantime_serde")]
+    pub timeout: Duration,
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: TtsBackend::default(),
+            base_url: None,
+            api_key: None,
+            model: default_tts_model(),
+            voice: default_tts_voice(),
+            command: None,
+            max_chars: default_tts_max_chars(),
+            timeout: default_tts_timeout(),
+        }
+    }
+}
+
+fn default_tts_model() -> String {
+    "FunAudioLLM/CosyVoice2-0.5B".to_string()
+}
+
+fn default_tts_voice() -> String {
+    "FunAudioLLM/CosyVoice2-0.5B:anna".to_string()
+}
+
+fn default_tts_max_chars() -> usize {
+    200
+}
+
+fn default_tts_timeout() -> Duration {
+    Duration::from_secs(60)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MemoryConfig {
+    // 是否在回复时注入用户的长期记忆
+    #[serde(default = "default_memory_enabled")]
+    pub enabled: bool,
+    // 是否在对话后由模型自动提取值得记住的信息（默认关闭）
+    #[serde(default)]
+    pub auto_extract: bool,
+    // 每个用户最多保存的记忆条数，超出时淘汰最早的自动记忆
+    #[serde(default = "default_memory_max_per_user")]
+    pub max_per_user: usize,
+    // 每次回复最多注入的记忆条数
+    #[serde(default = "default_memory_inject_limit")]
+    pub inject_limit: usize,
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_memory_enabled(),
+            auto_extract: false,
+            max_per_user: default_memory_max_per_user(),
+            inject_limit: default_memory_inject_limit(),
+        }
+    }
+}
+
+fn default_memory_enabled() -> bool {
+    true
+}
+
+fn default_memory_max_per_user() -> usize {
+    50
+}
+
+fn default_memory_inject_limit() -> usize {
+    5
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingBackend {
+    // OpenAI 兼容的 /embeddings 接口
+    #[default]
+    OpenAi,
+    // 本地字符哈希向量，无需网络，适合测试或没有向量接口时使用
+    Hash,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KnowledgeConfig {
+    // 是否在大模型回复时检索课程知识库（默认关闭）
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub backend: EmbeddingBackend,
+    // 未配置时使用 llm 的 base_url 和 api_key
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+    #[serde(default = "default_embedding_model")]
+    pub model: String,
+    // hash 后端的向量维度
+    #[serde(default = "default_hash_dimensions")]
+    pub hash_dimensions: usize,
+    // 每个片段的最大字数及相邻片段的重叠字数
+    #[serde(default = "default_chunk_chars")]
+    pub chunk_chars: usize,
+    #[serde(default = "default_chunk_overlap")]
+    pub chunk_overlap: usize,
+    // 每次回复最多引用的片段数
+    #[serde(default = "default_knowledge_top_k")]
+    pub top_k: usize,
+    // 相似度低于该值的片段不会被引用
+    #[serde(default = "default_knowledge_min_score")]
+    pub min_score: f32,
+    // 上传文档的大小限制（字节）
+    #[serde(default = "default_max_document_bytes")]
+    pub max_document_bytes: usize,
+    // 片段向量在内存中的缓存时间，管理后台上传或删除文档后最迟在此时间后生效
+    #[serde(default = "default_knowledge_cache_ttl", with = "humantime_serde")]
+    pub cache_ttl: Duration,
+}
+
+impl Default for KnowledgeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: EmbeddingBackend::default(),
+            base_url: None,
+            api_key: None,
+            model: default_embedding_model(),
+            hash_dimensions: default_hash_dimensions(),
+            chunk_chars: default_chunk_chars(),
+            chunk_overlap: default_chunk_overlap(),
+            top_k: default_knowledge_top_k(),
+            min_score: default_knowledge_min_score(),
+            max_document_bytes: default_max_document_bytes(),
+            cache_ttl: default_knowledge_cache_ttl(),
+        }
+    }
+}
+
+fn default_embedding_model() -> String {
+    "BAAI/bge-m3".to_string()
+}
+
+fn default_hash_dimensions() -> usize {
+    512
+}
+
+fn default_chunk_chars() -> usize {
+    500
+}
+
+fn default_chunk_overlap() -> usize {
+    50
+}
+
+fn default_knowledge_top_k() -> usize {
+    3
+}
+
+fn default_knowledge_min_score() -> f32 {
+    0.3
+}
+
+fn default_max_document_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+fn default_knowledge_cache_ttl() -> Duration {
+    Duration::from_secs(300)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FaqConfig {
+    // 是否在调用大模型前先匹配常见问题
+    #[serde(default = "default_faq_enabled")]
+    pub enabled: bool,
+    // 模糊匹配的相似度阈值（0~1）
+    #[serde(default = "default_faq_fuzzy_threshold")]
+    pub fuzzy_threshold: f64,
+    // 规则在内存中的缓存时间，管理后台修改后最迟在此时间后生效
+    #[serde(default = "default_faq_cache_ttl", with = "humantime_serde")]
+    pub cache_ttl: Duration,
+}
+
+impl Default for FaqConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_faq_enabled(),
+            fuzzy_threshold: default_faq_fuzzy_threshold(),
+            cache_ttl: default_faq_cache_ttl(),
+        }
+    }
+}
+
+fn default_faq_enabled() -> bool {
+    true
+}
+
+fn default_faq_fuzzy_threshold() -> f64 {
+    0.8
+}
+
+fn default_faq_cache_ttl() -> Duration {
+    Duration::from_secs(60)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RulesConfig {
+    // 是否在处理群消息前执行群规则
+    #[serde(default = "default_rules_enabled")]
+    pub enabled: bool,
+    // 规则在内存中的缓存时间，管理后台修改后最迟在此时间后生效
+    #[serde(default = "default_rules_cache_ttl", with = "humantime_serde")]
+    pub cache_ttl: Duration,
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_rules_enabled(),
+            cache_ttl: default_rules_cache_ttl(),
+        }
+    }
+}
+
+fn default_rules_enabled() -> bool {
+    true
+}
+
+fn default_rules_cache_ttl() -> Duration {
+    Duration::from_secs(60)
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationModel {
+    // 只使用屏蔽词和正则
+    #[default]
+    None,
+    // OpenAI 兼容的 /moderations 接口
+    OpenAi,
+    // 让对话模型判断内容是否违规
+    Llm,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContentFilterConfig {
+    // 是否审核大模型回复涉及的用户消息和回复内容
+    #[serde(default = "default_content_filter_enabled")]
+    pub enabled: bool,
+    // 屏蔽词，忽略大小写、空格和标点匹配
+    #[serde(default)]
+    pub blocklist: Vec<String>,
+    // 正则表达式，匹配原始文字
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    // 命中后代替原回复发送的内容
+    #[serde(default = "default_refusal")]
+    pub refusal: String,
+    #[serde(default)]
+    pub model: ModerationModel,
+    // 未配置时使用 llm 的 base_url 和 api_key
+    pub base_url: Option<String>,
+    pub api_key: Option<String>,
+    // openai 审核接口使用的模型
+    #[serde(default = "default_moderation_model_name")]
+    pub model_name: String,
+}
+
+impl Default for ContentFilterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_content_filter_enabled(),
+            blocklist: Vec::new(),
+            patterns: Vec::new(),
+            refusal: default_refusal(),
+            model: ModerationModel::default(),
+            base_url: None,
+            api_key: None,
+            model_name: default_moderation_model_name(),
+        }
+    }
+}
+
+fn default_content_filter_enabled() -> bool {
+    true
+}
+
+fn default_refusal() -> String {
+    "抱歉，这个问题我无法回答，换个话题吧。".to_string()
+}
+
+fn default_moderation_model_name() -> String {
+    "omni-moderation-latest".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JoinRequestConfig {
+    // 好友和加群申请的验证信息中需包含已录入且未绑定其他QQ的学号才自动同意，否则交给管理员审批
+    #[serde(default = "default_require_student_id")]
+    pub require_student_id: bool,
+    // 邀请机器人加入这些群时自动同意，机器人管理员的邀请也会自动同意
+    #[serde(default)]
+    pub group_allowlist: Vec<i64>,
+    // 需要审批时私聊通知 admins
+    #[serde(default = "default_notify_admins")]
+    pub notify_admins: bool,
+    // 检查已审批申请的间隔，管理后台的审批最迟在此时间后执行
+    #[serde(default = "default_join_request_poll_interval", with = "humantime_serde")]
+    pub poll_interval: Duration,
+}
+
+impl Default for JoinRequestConfig {
+    fn default() -> Self {
+        Self {
+            require_student_id: default_require_student_id(),
+            group_allowlist: Vec::new(),
+            notify_admins: default_notify_admins(),
+            poll_interval: default_join_request_poll_interval(),
+        }
+    }
+}
+
+fn default_require_student_id() -> bool {
+    true
+}
+
+fn default_notify_admins() -> bool {
+    true
+}
+
+fn default_join_request_poll_interval() -> Duration {
+    Duration::from_secs(10)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OnboardingConfig {
+    // 同意好友申请后私聊引导新用户绑定学号、了解常用命令
+    #[serde(default = "default_onboarding_enabled")]
+    pub enabled: bool,
+    // 学号输入错误达到此次数后结束引导，用户可之后再用 /bind 绑定
+    #[serde(default = "default_onboarding_max_attempts")]
+    pub max_attempts: i32,
+}
+
+impl Default for OnboardingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_onboarding_enabled(),
+            max_attempts: default_onboarding_max_attempts(),
+        }
+    }
+}
+
+fn default_onboarding_enabled() -> bool {
+    true
+}
+
+fn default_onboarding_max_attempts() -> i32 {
+    3
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommandsConfig {
+    // 全局命令别名：别名 -> 命令，如 "成绩" = "query grade"。
+    // 带前缀（/成绩）或整条消息只有别名（查成绩）时触发，群内可用 /alias 添加自己的别名
+    #[serde(default = "default_command_aliases")]
+    pub aliases: HashMap<String, String>,
+}
+
+impl Default for CommandsConfig {
+    fn default() -> Self {
+        Self {
+            aliases: default_command_aliases(),
+        }
+    }
+}
+
+fn default_command_aliases() -> HashMap<String, String> {
+    [
+        ("成绩", "query grade"),
+        ("查成绩", "query grade"),
+        ("绑定", "bind"),
+        ("解绑", "bind --clear"),
+        ("取消", "cancel"),
+        ("确认", "confirm"),
+    ]
+    .into_iter()
+    .map(|(alias, command)| (alias.to_string(), command.to_string()))
+    .collect()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChartConfig {
+    // 图表文字使用的字体（ttf/otf/ttc），需要包含中文字形
+    #[serde(default = "default_chart_font_path")]
+    pub font_path: String,
+    #[serde(default = "default_chart_width")]
+    pub width: u32,
+    #[serde(default = "default_chart_height")]
+    pub height: u32,
+}
+
+impl Default for ChartConfig {
+    fn default() -> Self {
+        Self {
+            font_path: default_chart_font_path(),
+            width: default_chart_width(),
+            height: default_chart_height(),
+        }
+    }
+}
+
+fn default_chart_font_path() -> String {
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc".to_string()
+}
+
+fn default_chart_width() -> u32 {
+    800
+}
+
+fn default_chart_height() -> u32 {
+    480
+}
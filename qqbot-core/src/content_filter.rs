@@ -0,0 +1,116 @@
+//! 内容审核：屏蔽词与正则过滤，用于大模型回复前后检查用户消息和回复内容
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::config::APPCONFIG;
+
+pub const SOURCE_BLOCKLIST: &str = "blocklist";
+pub const SOURCE_REGEX: &str = "regex";
+pub const SOURCE_MODEL: &str = "model";
+
+/// 审核的环节
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    /// 用户发来的消息
+    Input,
+    /// 大模型生成的回复
+    Output,
+}
+
+impl Stage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Stage::Input => "input",
+            Stage::Output => "output",
+        }
+    }
+}
+
+/// 命中的审核规则
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub source: &'static str,
+    /// 命中的屏蔽词、正则或模型给出的类别
+    pub matched: String,
+}
+
+pub struct ContentFilter {
+    /// 原屏蔽词及其归一化形式
+    keywords: Vec<(String, String)>,
+    patterns: Vec<Regex>,
+}
+
+/// 按配置构建的过滤器，无效的正则会被跳过
+pub static FILTER: Lazy<ContentFilter> = Lazy::new(|| {
+    let config = &APPCONFIG.content_filter;
+    let patterns = config
+        .patterns
+        .iter()
+        .filter_map(|pattern| {
+            Regex::new(pattern)
+                .map_err(|e| log::warn!("内容审核正则 {} 无效: {}", pattern, e))
+                .ok()
+        })
+        .collect();
+    ContentFilter::new(&config.blocklist, patterns)
+});
+
+impl ContentFilter {
+    pub fn new(blocklist: &[String], patterns: Vec<Regex>) -> Self {
+        let keywords = blocklist
+            .iter()
+            .map(|word| (word.clone(), normalize(word)))
+            .filter(|(_, normalized)| !normalized.is_empty())
+            .collect();
+        Self { keywords, patterns }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keywords.is_empty() && self.patterns.is_empty()
+    }
+
+    /// 返回第一条命中的规则
+    pub fn check(&self, text: &str) -> Option<Violation> {
+        let normalized = normalize(text);
+        if let Some((word, _)) = self.keywords.iter().find(|(_, n)| normalized.contains(n.as_str())) {
+            return Some(Violation {
+                source: SOURCE_BLOCKLIST,
+                matched: word.clone(),
+            });
+        }
+        self.patterns
+            .iter()
+            .find(|pattern| pattern.is_match(text))
+            .map(|pattern| Violation {
+                source: SOURCE_REGEX,
+                matched: pattern.as_str().to_string(),
+            })
+    }
+}
+
+/// 去掉空白和标点并转为小写，避免用空格、符号拆开屏蔽词绕过过滤
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let filter = ContentFilter::new(
+            &["代考".to_string(), "VPN".to_string()],
+            vec![Regex::new(r"\d{17}[\dXx]").unwrap()],
+        );
+        assert_eq!(filter.check("有人 代-考 吗").unwrap().matched, "代考");
+        assert_eq!(filter.check("推荐个 v p n").unwrap().source, SOURCE_BLOCKLIST);
+        assert_eq!(filter.check("我的身份证号是11010519491231002X").unwrap().source, SOURCE_REGEX);
+        assert!(filter.check("期中考试什么时候").is_none());
+        assert!(ContentFilter::new(&[" ".to_string()], Vec::new()).is_empty());
+    }
+}
@@ -35,6 +35,17 @@ impl ConversationManager {
         content: String,
         user_id: crate::UserId,
         username: Option<String>,
+    ) {
+        Self::add_user_message_with_id(session_id, content, user_id, username, None).await;
+    }
+
+    /// 添加用户消息并记录原消息 id，之后被引用时可以从会话中找回内容
+    pub async fn add_user_message_with_id(
+        session_id: SessionId,
+        content: String,
+        user_id: crate::UserId,
+        username: Option<String>,
+        message_id: Option<i64>,
     ) {
         let mut session = Self::get_or_create_session(session_id.clone()).await;
         
@@ -44,16 +55,10 @@ impl ConversationManager {
             timestamp: Utc::now(),
             user_id: Some(user_id),
             username,
+            message_id: message_id.map(|id| id.to_string()),
         };
 
-        session.messages.push_back(message);
-        session.last_activity = Utc::now();
-
-        // 保持历史记录数量在限制内
-        while session.messages.len() > session.max_history {
-            session.messages.pop_front();
-        }
-
+        session.push_message(message);
         CONVERSATION_CACHE.insert(session_id, session).await;
     }
 
@@ -64,6 +69,24 @@ impl ConversationManager {
         CONVERSATION_CACHE.insert(session_id, session).await;
     }
 
+    /// 回复发出后补记它的 message_id，之后用户引用机器人的回复时可以从会话中找回内容。
+    /// 按内容匹配最近一条还没有 id 的助手回复，避免并发回复时记错
+    pub async fn set_reply_message_id(session_id: SessionId, content: &str, message_id: i64) {
+        let Some(mut session) = CONVERSATION_CACHE.get(&session_id).await else {
+            return;
+        };
+        let content = content.trim();
+        if let Some(msg) = session
+            .messages
+            .iter_mut()
+            .rev()
+            .find(|msg| msg.role == "assistant" && msg.message_id.is_none() && msg.content.trim() == content)
+        {
+            msg.message_id = Some(message_id.to_string());
+            CONVERSATION_CACHE.insert(session_id, session).await;
+        }
+    }
+
     /// 获取最近的对话历史
     pub async fn get_conversation_history(
         session_id: SessionId,
@@ -106,6 +129,51 @@ impl ConversationManager {
         }
     }
 
+    /// 取出已移出历史、等待并入摘要的消息，同时返回当前摘要
+    pub async fn take_evicted(session_id: SessionId) -> (Option<String>, Vec<ConversationMessage>) {
+        match CONVERSATION_CACHE.get(&session_id).await {
+            Some(mut session) if !session.evicted.is_empty() => {
+                let evicted = std::mem::take(&mut session.evicted);
+                let summary = session.summary.clone();
+                CONVERSATION_CACHE.insert(session_id, session).await;
+                (summary, evicted)
+            }
+            Some(session) => (session.summary, Vec::new()),
+            None => (None, Vec::new()),
+        }
+    }
+
+    /// 更新会话摘要，会话已过期时忽略
+    pub async fn set_summary(session_id: SessionId, summary: String) {
+        if let Some(mut session) = CONVERSATION_CACHE.get(&session_id).await {
+            session.summary = Some(summary);
+            CONVERSATION_CACHE.insert(session_id, session).await;
+        }
+    }
+
+    /// 获取会话摘要
+    pub async fn get_summary(session_id: SessionId) -> Option<String> {
+        CONVERSATION_CACHE
+            .get(&session_id)
+            .await
+            .and_then(|session| session.summary)
+    }
+
+    /// 按原消息 id 查找会话中的消息
+    pub async fn find_message(
+        session_id: SessionId,
+        message_id: &str,
+    ) -> Option<ConversationMessage> {
+        CONVERSATION_CACHE.get(&session_id).await.and_then(|session| {
+            session
+                .messages
+                .iter()
+                .rev()
+                .find(|msg| msg.message_id.as_deref() == Some(message_id))
+                .cloned()
+        })
+    }
+
     /// 清除用户的对话历史
     pub async fn clear_conversation(session_id: SessionId) {
         CONVERSATION_CACHE.remove(&session_id).await;
@@ -158,4 +226,20 @@ mod tests {
         assert_eq!(history[1].role, "assistant");
         assert_eq!(history[1].content, "你好！有什么可以帮助你的吗？");
     }
+
+    #[tokio::test]
+    async fn test_find_bot_reply_by_message_id() {
+        let session_id = SessionId::Group(654321);
+        ConversationManager::add_assistant_message(session_id.clone(), "第一条回复\n".to_string()).await;
+        ConversationManager::add_assistant_message(session_id.clone(), "第二条回复".to_string()).await;
+
+        // 发送时的内容经过 trim，仍能对应到记录的回复
+        ConversationManager::set_reply_message_id(session_id.clone(), "第一条回复", 1001).await;
+        ConversationManager::set_reply_message_id(session_id.clone(), "不存在的回复", 1002).await;
+
+        let found = ConversationManager::find_message(session_id.clone(), "1001").await.unwrap();
+        assert_eq!(found.role, "assistant");
+        assert_eq!(found.content, "第一条回复\n");
+        assert!(ConversationManager::find_message(session_id, "1002").await.is_none());
+    }
 }
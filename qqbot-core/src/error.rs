@@ -63,6 +63,18 @@ pub enum AppError {
     /// 验证错误
     #[error("验证失败: {message}")]
     Validation { message: String },
+
+    /// 消息模板错误
+    #[error("模板错误: {message}")]
+    Template { message: String },
+
+    /// 语音处理错误
+    #[error("语音处理错误: {message}")]
+    Speech { message: String },
+
+    /// 知识库错误
+    #[error("知识库错误: {message}")]
+    Knowledge { message: String },
 }
 
 impl AppError {
@@ -128,6 +140,27 @@ impl AppError {
             message: message.into(),
         }
     }
+
+    /// 创建模板错误
+    pub fn template(message: impl Into<String>) -> Self {
+        Self::Template {
+            message: message.into(),
+        }
+    }
+
+    /// 创建语音处理错误
+    pub fn speech(message: impl Into<String>) -> Self {
+        Self::Speech {
+            message: message.into(),
+        }
+    }
+
+    /// 创建知识库错误
+    pub fn knowledge(message: impl Into<String>) -> Self {
+        Self::Knowledge {
+            message: message.into(),
+        }
+    }
 }
 
 /// 应用结果类型别名
@@ -0,0 +1,150 @@
+//! 常见问题匹配：关键词、正则和模糊匹配
+
+use regex::Regex;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::faq::{MATCH_FUZZY, MATCH_KEYWORD, MATCH_REGEX, Model},
+};
+
+/// 编译后的匹配规则
+#[derive(Debug, Clone)]
+pub enum Matcher {
+    Keywords(Vec<String>),
+    Regex(Regex),
+    Fuzzy(String),
+}
+
+impl Matcher {
+    pub fn compile(match_type: &str, pattern: &str) -> AppResult<Self> {
+        match match_type {
+            MATCH_KEYWORD => {
+                let keywords: Vec<String> = pattern
+                    .split([',', '，'])
+                    .map(normalize)
+                    .filter(|keyword| !keyword.is_empty())
+                    .collect();
+                if keywords.is_empty() {
+                    return Err(AppError::validation("关键词不能为空"));
+                }
+                Ok(Matcher::Keywords(keywords))
+            }
+            MATCH_REGEX => Regex::new(pattern)
+                .map(Matcher::Regex)
+                .map_err(|e| AppError::validation(format!("正则表达式无效: {}", e))),
+            MATCH_FUZZY => {
+                let question = normalize(pattern);
+                if question.is_empty() {
+                    return Err(AppError::validation("标准问法不能为空"));
+                }
+                Ok(Matcher::Fuzzy(question))
+            }
+            other => Err(AppError::validation(format!("未知的匹配方式: {}", other))),
+        }
+    }
+
+    /// 返回匹配得分，未命中时为 None。关键词和正则命中即为 1
+    pub fn score(&self, text: &str, fuzzy_threshold: f64) -> Option<f64> {
+        match self {
+            Matcher::Keywords(keywords) => {
+                let text = normalize(text);
+                keywords.iter().all(|keyword| text.contains(keyword.as_str())).then_some(1.0)
+            }
+            Matcher::Regex(regex) => regex.is_match(text).then_some(1.0),
+            Matcher::Fuzzy(question) => {
+                let text = normalize(text);
+                if text.contains(question.as_str()) {
+                    return Some(1.0);
+                }
+                let similarity = strsim::normalized_levenshtein(&text, question);
+                (similarity >= fuzzy_threshold).then_some(similarity)
+            }
+        }
+    }
+}
+
+/// 去掉标点和空白并转为小写，使“期中考试是什么时候？”与“期中考试是什么时候”一致
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// 带编译好匹配器的 FAQ
+#[derive(Debug, Clone)]
+pub struct CompiledFaq {
+    pub faq: Model,
+    pub matcher: Matcher,
+}
+
+impl CompiledFaq {
+    pub fn compile(faq: Model) -> AppResult<Self> {
+        let matcher = Matcher::compile(&faq.match_type, &faq.pattern)?;
+        Ok(Self { faq, matcher })
+    }
+}
+
+/// 在生效范围内找出最佳匹配：优先级高者优先，同优先级取得分高者
+pub fn best_match<'a>(
+    rules: &'a [CompiledFaq],
+    text: &str,
+    group_id: Option<i64>,
+    fuzzy_threshold: f64,
+) -> Option<&'a Model> {
+    rules
+        .iter()
+        .filter(|rule| rule.faq.group_id.is_none() || rule.faq.group_id == group_id)
+        .filter_map(|rule| {
+            rule.matcher
+                .score(text, fuzzy_threshold)
+                .map(|score| (rule.faq.priority, score, &rule.faq))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)))
+        .map(|(_, _, faq)| faq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn faq(id: i64, match_type: &str, pattern: &str, group_id: Option<i64>, priority: i32) -> CompiledFaq {
+        CompiledFaq::compile(Model {
+            id,
+            question: pattern.to_string(),
+            answer: format!("answer {}", id),
+            match_type: match_type.to_string(),
+            pattern: pattern.to_string(),
+            group_id,
+            priority,
+            enabled: true,
+            hit_count: 0,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_best_match() {
+        let rules = vec![
+            faq(1, MATCH_FUZZY, "期中考试是什么时候", None, 0),
+            faq(2, MATCH_KEYWORD, "作业,截止", None, 0),
+            faq(3, MATCH_REGEX, r"^(?i)ddl\b", Some(100), 0),
+            faq(4, MATCH_KEYWORD, "作业，截止，实验", None, 1),
+        ];
+        let id = |text: &str, group| best_match(&rules, text, group, 0.8).map(|faq| faq.id);
+
+        assert_eq!(id("请问期中考试是什么时候？", None), Some(1));
+        assert_eq!(id("期中考试什么时候", None), Some(1));
+        assert_eq!(id("作业 什么时候截止", None), Some(2));
+        assert_eq!(id("实验作业什么时候截止", None), Some(4));
+        assert_eq!(id("DDL 是哪天", Some(100)), Some(3));
+        assert_eq!(id("DDL 是哪天", Some(200)), None);
+        assert_eq!(id("今天天气不错", None), None);
+
+        assert!(Matcher::compile(MATCH_REGEX, "(").is_err());
+        assert!(Matcher::compile(MATCH_KEYWORD, " , ").is_err());
+    }
+}
@@ -0,0 +1,52 @@
+//! 单元测试共用的数据构造函数
+
+use crate::models::{
+    grade::Model as GradeModel,
+    grade_notification::{Model as NotificationModel, STATUS_PENDING},
+    student::Model as StudentModel,
+};
+
+/// 某个学生在某门课某次考试中的成绩，考试类型与考试名称相同
+pub fn grade(student_id: i64, course_id: i32, exam_name: &str, score: i8) -> GradeModel {
+    GradeModel {
+        id: 0,
+        student_name: "张三".to_string(),
+        exam_name: exam_name.to_string(),
+        course_id,
+        course_seq: 1,
+        student_id,
+        score,
+        category: exam_name.to_string(),
+    }
+}
+
+/// 学号 1 的学生，qq_number 为 0 表示未绑定
+pub fn student(qq_number: i64, group_id: i64) -> StudentModel {
+    StudentModel {
+        id: 1,
+        student_id: 1,
+        name: "张三".to_string(),
+        qq_number,
+        group_id,
+        created_at: Default::default(),
+        updated_at: Default::default(),
+    }
+}
+
+/// 已尝试 attempts 次、仍在等待发送的通知
+pub fn notification(attempts: i32) -> NotificationModel {
+    NotificationModel {
+        id: 1,
+        grade_id: 1,
+        student_id: 1,
+        qq_number: 10001,
+        student_name: "张三".to_string(),
+        exam_name: "Mid".to_string(),
+        score: 80,
+        status: STATUS_PENDING.to_string(),
+        attempts,
+        last_error: None,
+        created_at: Default::default(),
+        sent_at: None,
+    }
+}
@@ -0,0 +1,150 @@
+use crate::error::{AppError, AppResult};
+
+/// 支持上传的文档格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentFormat {
+    Markdown,
+    Pdf,
+    Text,
+}
+
+impl DocumentFormat {
+    /// 根据文件扩展名判断格式
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        let extension = filename.rsplit_once('.')?.1.to_lowercase();
+        match extension.as_str() {
+            "md" | "markdown" => Some(DocumentFormat::Markdown),
+            "pdf" => Some(DocumentFormat::Pdf),
+            "txt" | "text" => Some(DocumentFormat::Text),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocumentFormat::Markdown => "markdown",
+            DocumentFormat::Pdf => "pdf",
+            DocumentFormat::Text => "text",
+        }
+    }
+}
+
+/// 提取文档中的纯文本
+pub fn extract_text(format: DocumentFormat, bytes: &[u8]) -> AppResult<String> {
+    let text = match format {
+        DocumentFormat::Pdf => pdf_extract::extract_text_from_mem(bytes)
+            .map_err(|e| AppError::knowledge(format!("PDF 解析失败: {}", e)))?,
+        DocumentFormat::Markdown | DocumentFormat::Text => String::from_utf8(bytes.to_vec())
+            .map_err(|_| AppError::knowledge("文档不是有效的 UTF-8 文本"))?,
+    };
+    if text.trim().is_empty() {
+        return Err(AppError::knowledge("文档中没有可提取的文字"));
+    }
+    Ok(text)
+}
+
+/// 将文本切分为不超过 chunk_chars 字的片段。
+/// 按空行分段后合并，过长的段落按字数切开并与前一片段重叠 overlap 字；
+/// Markdown 遇到标题时另起片段，并在片段开头带上所属标题，便于检索和引用
+pub fn split_chunks(format: DocumentFormat, text: &str, chunk_chars: usize, overlap: usize) -> Vec<String> {
+    let chunk_chars = chunk_chars.max(1);
+    let overlap = overlap.min(chunk_chars / 2);
+    let mut chunks = Vec::new();
+    let mut heading = String::new();
+    let mut current = String::new();
+
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        if format == DocumentFormat::Markdown && paragraph.starts_with('#') {
+            flush(&mut chunks, &mut current);
+            let (title, rest) = paragraph.split_once('\n').unwrap_or((paragraph, ""));
+            heading = title.trim_start_matches('#').trim().to_string();
+            if rest.trim().is_empty() {
+                continue;
+            }
+            push_paragraph(&mut chunks, &mut current, &heading, rest.trim(), chunk_chars, overlap);
+        } else {
+            push_paragraph(&mut chunks, &mut current, &heading, paragraph, chunk_chars, overlap);
+        }
+    }
+    flush(&mut chunks, &mut current);
+    chunks
+}
+
+fn push_paragraph(
+    chunks: &mut Vec<String>,
+    current: &mut String,
+    heading: &str,
+    paragraph: &str,
+    chunk_chars: usize,
+    overlap: usize,
+) {
+    let paragraph_len = paragraph.chars().count();
+    if !current.is_empty() && current.chars().count() + paragraph_len + 1 > chunk_chars {
+        flush(chunks, current);
+    }
+    if current.is_empty() && !heading.is_empty() {
+        current.push_str(heading);
+        current.push('\n');
+    }
+    if current.chars().count() + paragraph_len <= chunk_chars {
+        if !current.is_empty() && !current.ends_with('\n') {
+            current.push('\n');
+        }
+        current.push_str(paragraph);
+        return;
+    }
+
+    // 段落本身过长，按字数切开
+    let chars: Vec<char> = paragraph.chars().collect();
+    let mut start = 0;
+    while start < chars.len() {
+        let room = chunk_chars.saturating_sub(current.chars().count()).max(1);
+        let end = (start + room).min(chars.len());
+        current.extend(&chars[start..end]);
+        flush(chunks, current);
+        if end == chars.len() {
+            break;
+        }
+        if !heading.is_empty() {
+            current.push_str(heading);
+            current.push('\n');
+        }
+        start = end.saturating_sub(overlap).max(start + 1);
+    }
+}
+
+fn flush(chunks: &mut Vec<String>, current: &mut String) {
+    let chunk = current.trim();
+    if !chunk.is_empty() {
+        chunks.push(chunk.to_string());
+    }
+    current.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_markdown_chunks() {
+        assert_eq!(DocumentFormat::from_filename("大纲.MD"), Some(DocumentFormat::Markdown));
+        assert_eq!(DocumentFormat::from_filename("README"), None);
+
+        let text = "# 考核方式\n\n平时成绩占40%。\n\n期末考试占60%。\n\n## 截止日期\n作业一：10月20日";
+        let chunks = split_chunks(DocumentFormat::Markdown, text, 100, 10);
+        assert_eq!(
+            chunks,
+            vec![
+                "考核方式\n平时成绩占40%。\n期末考试占60%。".to_string(),
+                "截止日期\n作业一：10月20日".to_string(),
+            ]
+        );
+
+        // 过长的段落按字数切开并保留重叠
+        let long = "一二三四五六七八九十".repeat(3);
+        let chunks = split_chunks(DocumentFormat::Text, &long, 12, 2);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 12));
+        assert!(chunks[1].starts_with("一二"));
+        assert_eq!(chunks.concat().chars().count(), 30 + 2 * (chunks.len() - 1));
+    }
+}
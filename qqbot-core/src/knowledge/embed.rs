@@ -0,0 +1,182 @@
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    config::{APPCONFIG, app_config::EmbeddingBackend},
+    error::{AppError, AppResult},
+};
+
+/// 单次请求最多向量化的文本数
+const EMBED_BATCH_SIZE: usize = 32;
+
+/// 文本向量化后端
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    async fn embed(&self, texts: &[String]) -> AppResult<Vec<Vec<f32>>>;
+}
+
+/// OpenAI 兼容的 `/embeddings` 接口
+pub struct OpenAiEmbedder {
+    client: Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl OpenAiEmbedder {
+    pub fn new() -> Self {
+        let knowledge = &APPCONFIG.knowledge;
+        Self {
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(APPCONFIG.llm.timeout_seconds))
+                .build()
+                .expect("Failed to create HTTP client"),
+            base_url: knowledge.base_url.clone().unwrap_or_else(|| APPCONFIG.llm.base_url.clone()),
+            api_key: knowledge.api_key.clone().unwrap_or_else(|| APPCONFIG.llm.api_key.clone()),
+            model: knowledge.model.clone(),
+        }
+    }
+}
+
+impl Default for OpenAiEmbedder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Embedder for OpenAiEmbedder {
+    async fn embed(&self, texts: &[String]) -> AppResult<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(EMBED_BATCH_SIZE) {
+            let response = self
+                .client
+                .post(format!("{}/embeddings", self.base_url))
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .json(&json!({ "model": self.model, "input": batch }))
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let error_text = response.text().await.unwrap_or_default();
+                return Err(AppError::knowledge(format!("向量接口返回错误 {}: {}", status, error_text)));
+            }
+            let mut data = response.json::<EmbeddingResponse>().await?.data;
+            if data.len() != batch.len() {
+                return Err(AppError::knowledge("向量接口返回的数量与请求不一致"));
+            }
+            data.sort_by_key(|item| item.index);
+            vectors.extend(data.into_iter().map(|item| normalize(item.embedding)));
+        }
+        Ok(vectors)
+    }
+}
+
+/// 字符一元组和二元组的特征哈希向量，不依赖外部接口，结果稳定可复现
+pub struct HashEmbedder {
+    dimensions: usize,
+}
+
+impl HashEmbedder {
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions: dimensions.max(1),
+        }
+    }
+
+    pub fn embed_one(&self, text: &str) -> Vec<f32> {
+        let chars: Vec<char> = text
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(|c| c.to_lowercase())
+            .collect();
+        let mut vector = vec![0.0; self.dimensions];
+        for c in &chars {
+            vector[fnv1a(&c.to_string()) as usize % self.dimensions] += 1.0;
+        }
+        for pair in chars.windows(2) {
+            let bigram: String = pair.iter().collect();
+            vector[fnv1a(&bigram) as usize % self.dimensions] += 2.0;
+        }
+        normalize(vector)
+    }
+}
+
+#[async_trait]
+impl Embedder for HashEmbedder {
+    async fn embed(&self, texts: &[String]) -> AppResult<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|text| self.embed_one(text)).collect())
+    }
+}
+
+/// FNV-1a 哈希，向量会持久化，不能使用跨版本不稳定的 DefaultHasher
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// 根据配置创建的向量化后端
+pub static EMBEDDER: Lazy<Box<dyn Embedder>> = Lazy::new(|| match APPCONFIG.knowledge.backend {
+    EmbeddingBackend::OpenAi => Box::new(OpenAiEmbedder::new()),
+    EmbeddingBackend::Hash => Box::new(HashEmbedder::new(APPCONFIG.knowledge.hash_dimensions)),
+});
+
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+/// 余弦相似度，向量已归一化，维度不一致（如更换了向量模型）时返回 0
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// 将向量编码为小端 f32 字节序列以便存入数据库
+pub fn encode(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+pub fn decode(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_embedding_similarity() {
+        let embedder = HashEmbedder::new(256);
+        let deadline = embedder.embed_one("作业一的截止日期是10月20日");
+        let grading = embedder.embed_one("期末考试占总评的60%");
+        let query = embedder.embed_one("作业什么时候截止？");
+        assert!(cosine(&query, &deadline) > cosine(&query, &grading));
+        assert!((cosine(&deadline, &deadline) - 1.0).abs() < 1e-5);
+
+        assert_eq!(decode(&encode(&deadline)), deadline);
+    }
+}
@@ -0,0 +1,4 @@
+//! 课程知识库：文档解析与切分、向量化
+
+pub mod chunk;
+pub mod embed;
@@ -5,17 +5,27 @@ use chrono::{DateTime, Utc};
 use config::APPCONFIG;
 use moka::future::Cache;
 use once_cell::sync::Lazy;
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 
 use serde::{Deserialize, Serialize};
 
+pub mod action;
+pub mod chart;
 pub mod cmd;
 pub mod config;
+pub mod content_filter;
 pub mod conversation;
 pub mod error;
+pub mod faq;
+#[cfg(test)]
+mod fixtures;
+pub mod knowledge;
 pub mod models;
 pub mod reply_strategy;
+pub mod rules;
 pub mod repo; // 添加错误处理模块
+pub mod speech;
+pub mod template;
 
 // 重新导出常用类型
 pub use error::{AppError, AppResult};
@@ -31,6 +41,8 @@ pub struct ConversationMessage {
     pub timestamp: DateTime<Utc>,
     pub user_id: Option<UserId>,  // 在群聊中记录发言者ID，私聊中为None
     pub username: Option<String>, // 在群聊中记录发言者昵称，便于上下文理解
+    #[serde(default)]
+    pub message_id: Option<String>, // 原消息 id，用于解析引用回复
 }
 
 // 对话会话结构
@@ -39,6 +51,10 @@ pub struct ConversationSession {
     pub messages: VecDeque<ConversationMessage>,
     pub last_activity: DateTime<Utc>,
     pub max_history: usize, // 最大保留消息数
+    #[serde(default)]
+    pub summary: Option<String>, // 更早对话的滚动摘要
+    #[serde(default)]
+    pub evicted: Vec<ConversationMessage>, // 超出 max_history 被移出、尚未并入摘要的消息
 }
 
 impl ConversationSession {
@@ -47,6 +63,8 @@ impl ConversationSession {
             messages: VecDeque::new(),
             last_activity: Utc::now(),
             max_history,
+            summary: None,
+            evicted: Vec::new(),
         }
     }
 
@@ -73,14 +91,26 @@ impl ConversationSession {
             timestamp: Utc::now(),
             user_id: None, // 这里可以根据需要设置
             username: None,
+            message_id: None,
         };
+        self.push_message(message);
+    }
 
+    /// 追加消息，超出 max_history 的旧消息移入 evicted 等待摘要
+    pub fn push_message(&mut self, message: ConversationMessage) {
         self.messages.push_back(message);
         self.last_activity = Utc::now();
 
         // 保持历史记录数量在限制内
         while self.messages.len() > self.max_history {
-            self.messages.pop_front();
+            if let Some(old) = self.messages.pop_front() {
+                self.evicted.push(old);
+            }
+        }
+        // 摘要长期失败时避免无限堆积
+        if self.evicted.len() > self.max_history {
+            let overflow = self.evicted.len() - self.max_history;
+            self.evicted.drain(..overflow);
         }
     }
 }
@@ -116,18 +146,14 @@ impl SessionId {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum StrategeType {
     CmdStrategy,
+    #[default]
     LlmStrategy,
 }
-impl std::default::Default for StrategeType {
-    fn default() -> Self {
-        StrategeType::LlmStrategy
-    }
-}
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct UserData {
     #[serde(default)]
     pub stratege: StrategeType,
@@ -135,32 +161,32 @@ pub struct UserData {
     pub model: String,
     #[serde(default)]
     pub custom_prompt: Option<String>,
-}
-impl std::default::Default for UserData {
-    fn default() -> Self {
-        Self {
-            stratege: Default::default(),
-            model: String::from(""),
-            custom_prompt: None,
-        }
-    }
+    #[serde(default)]
+    pub voice_reply: bool, // 开启后较短的回复以语音发送
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct GroupData {
     pub stratege: StrategeType,
     #[serde(default)]
     pub model: String,
     #[serde(default)]
     pub custom_prompt: Option<String>,
+    #[serde(default)]
+    pub voice_reply: bool, // 开启后较短的回复以语音发送
+    #[serde(default)]
+    pub cmd_prefix: Option<String>, // 群内的命令前缀，覆盖全局的 cmd_suffix
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>, // 群内的命令别名，优先于全局别名
 }
 
-impl std::default::Default for GroupData {
-    fn default() -> Self {
-        Self {
-            stratege: Default::default(),
-            model: String::from(""),
-            custom_prompt: None,
+impl GroupData {
+    /// 别名保存为 JSON，没有别名时为空
+    pub fn aliases_json(&self) -> Option<String> {
+        if self.aliases.is_empty() {
+            None
+        } else {
+            serde_json::to_string(&self.aliases).ok()
         }
     }
 }
@@ -0,0 +1,25 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "content_incident")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub user_id: i64,          // 发送消息的用户
+    pub group_id: Option<i64>, // 私聊时为空
+    pub stage: String,         // 审核环节 (input, output)
+    pub source: String,        // 命中来源 (blocklist, regex, model)
+    pub matched: String,       // 命中的屏蔽词、正则或类别
+    #[sea_orm(column_type = "Text")]
+    pub content: String, // 被拦截的内容
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeWithTimeZone, // 拦截时间
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
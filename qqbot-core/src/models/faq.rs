@@ -0,0 +1,39 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+
+/// 关键词匹配：pattern 为逗号分隔的关键词，全部出现时命中
+pub const MATCH_KEYWORD: &str = "keyword";
+/// 正则匹配：pattern 为正则表达式
+pub const MATCH_REGEX: &str = "regex";
+/// 模糊匹配：pattern 为标准问法，与消息的相似度达到阈值时命中
+pub const MATCH_FUZZY: &str = "fuzzy";
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "faq")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub question: String, // 问题描述，便于管理
+    #[sea_orm(column_type = "Text")]
+    pub answer: String, // 回复内容
+    pub match_type: String, // 匹配方式 (keyword, regex, fuzzy)
+    pub pattern: String,    // 匹配规则
+    pub group_id: Option<i64>, // 生效的群，为空表示全部
+    pub priority: i32,      // 多条命中时优先级高的生效
+    pub enabled: bool,
+    pub hit_count: i64, // 命中次数
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeWithTimeZone, // 创建时间
+    #[sea_orm(
+        default_expr = "Expr::current_timestamp()",
+        on_update = "Expr::current_timestamp()"
+    )]
+    pub updated_at: DateTimeWithTimeZone, // 更新时间
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
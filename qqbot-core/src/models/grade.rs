@@ -1,6 +1,7 @@
 use sea_orm::entity::prelude::*;
 use sea_query::Iden;
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+use serde::Serialize;
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
 #[sea_orm(table_name = "grade")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = true)]
@@ -0,0 +1,35 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+
+/// 等待发送
+pub const STATUS_PENDING: &str = "pending";
+/// 已成功投递
+pub const STATUS_SENT: &str = "sent";
+/// 超过最大尝试次数，放弃发送
+pub const STATUS_FAILED: &str = "failed";
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "grade_notification")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub grade_id: i64,     // 关联成绩ID
+    pub student_id: i64,   // 学号
+    pub qq_number: i64,    // 接收通知的QQ号
+    pub student_name: String,
+    pub exam_name: String,
+    pub score: i8,
+    pub status: String,    // 投递状态 (pending, sent, failed)
+    pub attempts: i32,     // 已尝试次数
+    pub last_error: Option<String>, // 最近一次失败原因
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeWithTimeZone, // 创建时间
+    pub sent_at: Option<DateTimeWithTimeZone>, // 投递成功时间
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+
+/// 大模型聊天不是命令，用这个名字在策略中开关
+pub const COMMAND_LLM: &str = "llm";
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "group_command_policy")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub group_id: i64,            // QQ群ID
+    pub command: String,          // 命令名，不带前缀；llm 表示大模型聊天
+    pub enabled: bool,            // 是否在本群启用
+    pub min_role: Option<String>, // 使用所需的最低角色 (member, admin, owner, bot_admin)，为空时不限制
+    pub updated_by: Option<i64>,  // 最后修改的管理员
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeWithTimeZone, // 创建时间
+    #[sea_orm(
+        default_expr = "Expr::current_timestamp()",
+        on_update = "Expr::current_timestamp()"
+    )]
+    pub updated_at: DateTimeWithTimeZone, // 更新时间
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
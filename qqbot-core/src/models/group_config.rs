@@ -13,6 +13,11 @@ pub struct Model {
     pub strategy: String, // 策略类型 (cmd_strategy, llm_strategy)
     pub model: Option<String>, // LLM模型名称
     pub custom_prompt: Option<String>, // 群组自定义提示词
+    #[sea_orm(default_value = false)]
+    pub voice_reply: bool, // 是否以语音回复
+    pub cmd_prefix: Option<String>, // 群内的命令前缀，为空时使用全局的 cmd_suffix
+    #[sea_orm(column_type = "Text", nullable)]
+    pub cmd_aliases: Option<String>, // 群内的命令别名 (JSON: 别名 -> 命令)
     #[sea_orm(default_expr = "Expr::current_timestamp()")]
     pub created_at: DateTimeWithTimeZone, // 创建时间
     #[sea_orm(
@@ -40,6 +45,13 @@ impl Model {
             stratege: strategy,
             model: self.model.clone().unwrap_or_else(|| default_model.to_string()),
             custom_prompt: self.custom_prompt.clone(),
+            voice_reply: self.voice_reply,
+            cmd_prefix: self.cmd_prefix.clone(),
+            aliases: self
+                .cmd_aliases
+                .as_deref()
+                .and_then(|json| serde_json::from_str(json).ok())
+                .unwrap_or_default(),
         }
     }
 
@@ -59,6 +71,9 @@ impl Model {
                 Some(group_data.model.clone()) 
             }),
             custom_prompt: Set(group_data.custom_prompt.clone()),
+            voice_reply: Set(group_data.voice_reply),
+            cmd_prefix: Set(group_data.cmd_prefix.clone()),
+            cmd_aliases: Set(group_data.aliases_json()),
             created_at: NotSet,
             updated_at: NotSet,
         }
@@ -0,0 +1,51 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+
+/// 触发条件：消息包含逗号分隔的任一关键词
+pub const TRIGGER_KEYWORD: &str = "keyword";
+/// 触发条件：消息文字（含卡片内容）匹配正则
+pub const TRIGGER_REGEX: &str = "regex";
+/// 触发条件：消息包含指定类型的消息段，如 image、file、json
+pub const TRIGGER_SEGMENT: &str = "segment";
+/// 触发条件：@ 了指定 QQ，值为空表示 @ 任何人，all 表示 @全体成员
+pub const TRIGGER_AT: &str = "at";
+
+/// 动作：回复，内容支持模板变量
+pub const ACTION_REPLY: &str = "reply";
+/// 动作：撤回触发的消息
+pub const ACTION_RECALL: &str = "recall";
+/// 动作：禁言发送者，值为时长，如 10m
+pub const ACTION_MUTE: &str = "mute";
+/// 动作：私聊通知机器人管理员，内容支持模板变量
+pub const ACTION_NOTIFY_ADMIN: &str = "notify_admin";
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "group_rule")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub group_id: i64,         // 生效的群
+    pub name: String,          // 规则名称
+    pub trigger_type: String,  // 触发类型 (keyword, regex, segment, at)
+    pub trigger_value: String, // 触发条件
+    pub action_type: String,   // 动作类型 (reply, recall, mute, notify_admin)
+    #[sea_orm(column_type = "Text")]
+    pub action_value: String, // 动作参数
+    pub priority: i32,        // 优先级高的规则先执行
+    pub stop_processing: bool, // 命中后不再执行后续规则，也不交给命令或大模型处理
+    pub enabled: bool,
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeWithTimeZone, // 创建时间
+    #[sea_orm(
+        default_expr = "Expr::current_timestamp()",
+        on_update = "Expr::current_timestamp()"
+    )]
+    pub updated_at: DateTimeWithTimeZone, // 更新时间
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,53 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+
+/// 加好友申请
+pub const TYPE_FRIEND: &str = "friend";
+/// 加群申请或邀请机器人入群，由 sub_type 区分
+pub const TYPE_GROUP: &str = "group";
+
+/// 邀请机器人入群
+pub const SUB_TYPE_INVITE: &str = "invite";
+
+/// 等待管理员审批
+pub const STATUS_PENDING: &str = "pending";
+/// 已决定同意，等待机器人执行
+pub const STATUS_APPROVING: &str = "approving";
+/// 已决定拒绝，等待机器人执行
+pub const STATUS_REJECTING: &str = "rejecting";
+pub const STATUS_APPROVED: &str = "approved";
+pub const STATUS_REJECTED: &str = "rejected";
+/// 执行失败，如申请已过期
+pub const STATUS_FAILED: &str = "failed";
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "join_request")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub request_type: String,     // 申请类型 (friend, group)
+    pub sub_type: String,         // 加群申请的子类型 (add, invite)，好友申请为空
+    pub flag: String,             // 处理申请时需要回传的 flag
+    pub user_id: i64,             // 申请人或邀请人
+    pub group_id: Option<i64>,    // 加群申请的群号
+    #[sea_orm(column_type = "Text")]
+    pub comment: String, // 验证信息
+    pub status: String,           // 状态 (pending, approving, rejecting, approved, rejected, failed)
+    pub reason: String,           // 自动审批的依据或管理员填写的拒绝理由
+    pub handled_by: Option<i64>,  // 审批的管理员，自动审批或管理后台审批时为空
+    pub error: Option<String>,    // 执行失败的原因
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeWithTimeZone, // 收到申请的时间
+    #[sea_orm(
+        default_expr = "Expr::current_timestamp()",
+        on_update = "Expr::current_timestamp()"
+    )]
+    pub updated_at: DateTimeWithTimeZone, // 更新时间
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,33 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+#[sea_orm(table_name = "knowledge_chunk")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub document_id: i64, // 所属文档ID
+    pub chunk_index: i32, // 在文档中的序号
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    #[serde(skip)]
+    pub embedding: Vec<u8>, // 向量，小端 f32 序列
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::knowledge_document::Entity",
+        from = "Column::DocumentId",
+        to = "super::knowledge_document::Column::Id"
+    )]
+    Document,
+}
+
+impl Related<super::knowledge_document::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Document.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "knowledge_document")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub title: String,    // 文档标题，回答时作为引用来源
+    pub filename: String, // 上传的文件名
+    pub format: String,   // 文档格式 (markdown, pdf, text)
+    pub chunk_count: i32, // 切分出的片段数
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeWithTimeZone, // 上传时间
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::knowledge_chunk::Entity")]
+    Chunk,
+}
+
+impl Related<super::knowledge_chunk::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Chunk.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
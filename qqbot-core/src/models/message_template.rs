@@ -0,0 +1,25 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "message_template")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub template_key: String, // 模板标识，如 query.grade_summary
+    #[sea_orm(column_type = "Text")]
+    pub content: String, // 模板内容
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeWithTimeZone, // 创建时间
+    #[sea_orm(
+        default_expr = "Expr::current_timestamp()",
+        on_update = "Expr::current_timestamp()"
+    )]
+    pub updated_at: DateTimeWithTimeZone, // 更新时间
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,5 +1,18 @@
+pub mod content_incident;
+pub mod faq;
 pub mod grade;
+pub mod grade_notification;
 pub mod group;
+pub mod group_command_policy;
 pub mod group_config;
+pub mod group_rule;
+pub mod join_request;
+pub mod knowledge_chunk;
+pub mod knowledge_document;
+pub mod message_template;
+pub mod moderation_log;
+pub mod onboarding;
+pub mod permission_grant;
 pub mod student;
 pub mod user_config;
+pub mod user_memory;
@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+
+pub const ACTION_MUTE: &str = "mute";
+pub const ACTION_UNMUTE: &str = "unmute";
+pub const ACTION_KICK: &str = "kick";
+pub const ACTION_RECALL: &str = "recall";
+pub const ACTION_WHOLE_MUTE: &str = "whole_mute";
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "moderation_log")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub group_id: i64,          // 操作的群
+    pub operator_id: i64,       // 执行命令的管理员
+    pub action: String,         // 操作类型 (mute, unmute, kick, recall, whole_mute)
+    pub target_id: Option<i64>, // 被操作的成员，撤回和全员禁言时为空
+    pub detail: String,         // 禁言时长、被撤回的消息 ID 等
+    pub success: bool,          // 是否执行成功
+    pub error: Option<String>,  // 失败原因
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeWithTimeZone, // 操作时间
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
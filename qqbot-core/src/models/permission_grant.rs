@@ -0,0 +1,29 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+
+/// 机器人超级管理员，拥有全部权限
+pub const ROLE_SUPER_ADMIN: &str = "super_admin";
+/// 教师，可查看所授课程的成绩
+pub const ROLE_TEACHER: &str = "teacher";
+/// 助教，可查看所管理群内学生的成绩
+pub const ROLE_TA: &str = "ta";
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "permission_grant")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub user_id: i64,            // 被授权的QQ号
+    pub role: String,            // 角色 (super_admin, teacher, ta)
+    pub scope_id: i64,           // 教师为课程号，助教为群号，超级管理员为 0
+    pub granted_by: Option<i64>, // 授权的管理员
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeWithTimeZone, // 授权时间
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -13,6 +13,8 @@ pub struct Model {
     pub strategy: String, // 策略类型 (cmd_strategy, llm_strategy)
     pub model: Option<String>, // LLM模型名称
     pub custom_prompt: Option<String>, // 自定义提示词
+    #[sea_orm(default_value = false)]
+    pub voice_reply: bool, // 是否以语音回复
     #[sea_orm(default_expr = "Expr::current_timestamp()")]
     pub created_at: DateTimeWithTimeZone, // 创建时间
     #[sea_orm(
@@ -40,6 +42,7 @@ impl Model {
             stratege: strategy,
             model: self.model.clone().unwrap_or_else(|| default_model.to_string()),
             custom_prompt: self.custom_prompt.clone(),
+            voice_reply: self.voice_reply,
         }
     }
 
@@ -59,6 +62,7 @@ impl Model {
                 Some(user_data.model.clone()) 
             }),
             custom_prompt: Set(user_data.custom_prompt.clone()),
+            voice_reply: Set(user_data.voice_reply),
             created_at: NotSet,
             updated_at: NotSet,
         }
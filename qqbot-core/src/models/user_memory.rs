@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::prelude::DateTimeWithTimeZone;
+use sea_orm::sea_query::Expr;
+use serde::{Deserialize, Serialize};
+
+/// 用户通过 /memory add 手动添加
+pub const SOURCE_MANUAL: &str = "manual";
+/// 从对话中自动提取
+pub const SOURCE_AUTO: &str = "auto";
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "user_memory")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub user_id: i64,    // QQ用户ID
+    pub content: String, // 记忆内容，如 "在3班"、"喜欢用英文交流"
+    pub source: String,  // 来源 (manual, auto)
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeWithTimeZone, // 创建时间
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,5 +1,225 @@
-use crate::config::APPCONFIG;
+use std::sync::Arc;
 
-pub fn check_permission(user_id: i64) -> bool {
-    APPCONFIG.admins.contains(&user_id)
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+
+use crate::{
+    UserId,
+    config::{APPCONFIG, get_db},
+    models::permission_grant::{Model, ROLE_SUPER_ADMIN, ROLE_TA, ROLE_TEACHER},
+    repo::permission_grant::{PermissionGrantRepo, PermissionGrantRepository},
+};
+
+/// 用户拥有的权限：配置中的 admins 是初始超级管理员，其余授权保存在 permission_grant 表中
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Grants {
+    pub super_admin: bool,
+    // 作为教师的课程号
+    pub courses: Vec<i64>,
+    // 作为助教的群号
+    pub groups: Vec<i64>,
+}
+
+impl Grants {
+    fn from_models(super_admin: bool, models: &[Model]) -> Self {
+        let scopes = |role: &str| {
+            models
+                .iter()
+                .filter(|m| m.role == role)
+                .map(|m| m.scope_id)
+                .collect::<Vec<_>>()
+        };
+        Self {
+            super_admin: super_admin || models.iter().any(|m| m.role == ROLE_SUPER_ADMIN),
+            courses: scopes(ROLE_TEACHER),
+            groups: scopes(ROLE_TA),
+        }
+    }
+
+    /// 没有任何授权的普通用户
+    pub fn is_empty(&self) -> bool {
+        !self.super_admin && self.courses.is_empty() && self.groups.is_empty()
+    }
+
+    /// 能否查看某个群内学生某门课的成绩：教师按课程，助教按学生所在的群
+    pub fn can_view_grade(&self, student_group: i64, course_id: i64) -> bool {
+        self.super_admin || self.groups.contains(&student_group) || self.courses.contains(&course_id)
+    }
+}
+
+// 每条命令都要检查权限，授权按用户缓存，修改后失效
+static GRANTS: Lazy<Cache<UserId, Arc<Grants>>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(APPCONFIG.cache.cache_capacity / 10)
+        .time_to_live(APPCONFIG.cache.cache_lifetime)
+        .build()
+});
+
+// 群规则每次匹配都要用到超级管理员列表，单独缓存
+static SUPER_ADMINS: Lazy<Cache<(), Arc<Vec<UserId>>>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(1)
+        .time_to_live(APPCONFIG.cache.cache_lifetime)
+        .build()
+});
+
+/// 用户的权限，读取数据库失败时只按配置判断
+pub async fn grants(user_id: UserId) -> Arc<Grants> {
+    if let Some(grants) = GRANTS.get(&user_id).await {
+        return grants;
+    }
+    let configured = APPCONFIG.admins.contains(&user_id);
+    match PermissionGrantRepo::new(get_db().await).list_by_user(user_id).await {
+        Ok(models) => {
+            let grants = Arc::new(Grants::from_models(configured, &models));
+            GRANTS.insert(user_id, grants.clone()).await;
+            grants
+        }
+        Err(err) => {
+            log::warn!("读取用户 {} 的权限失败: {}", user_id, err);
+            Arc::new(Grants {
+                super_admin: configured,
+                ..Default::default()
+            })
+        }
+    }
+}
+
+/// 授权变更后清除缓存
+pub async fn invalidate(user_id: UserId) {
+    GRANTS.invalidate(&user_id).await;
+    SUPER_ADMINS.invalidate(&()).await;
+}
+
+/// 是否为机器人超级管理员
+pub async fn check_permission(user_id: UserId) -> bool {
+    grants(user_id).await.super_admin
+}
+
+/// 全部超级管理员，用于私聊通知
+pub async fn super_admins() -> Arc<Vec<UserId>> {
+    if let Some(admins) = SUPER_ADMINS.get(&()).await {
+        return admins;
+    }
+    let mut admins = APPCONFIG.admins.clone();
+    match PermissionGrantRepo::new(get_db().await).list_by_role(ROLE_SUPER_ADMIN).await {
+        Ok(models) => admins.extend(models.iter().map(|m| m.user_id)),
+        Err(err) => {
+            log::warn!("读取超级管理员失败: {}", err);
+            return Arc::new(admins);
+        }
+    }
+    admins.sort_unstable();
+    admins.dedup();
+    let admins = Arc::new(admins);
+    SUPER_ADMINS.insert((), admins.clone()).await;
+    admins
+}
+
+/// 群成员角色，按权限从低到高排序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MemberRole {
+    Member,
+    Admin,
+    Owner,
+}
+
+impl MemberRole {
+    /// 解析 OneBot 返回的 role 字段
+    pub fn parse(role: &str) -> Self {
+        match role {
+            "owner" => MemberRole::Owner,
+            "admin" => MemberRole::Admin,
+            _ => MemberRole::Member,
+        }
+    }
+
+    /// 群主或群管理员
+    pub fn is_manager(self) -> bool {
+        self >= MemberRole::Admin
+    }
+}
+
+/// 命令权限使用的角色，在群成员角色之上增加机器人管理员，按权限从低到高排序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    Member,
+    Admin,
+    Owner,
+    BotAdmin,
+}
+
+impl Role {
+    /// 用户在群内的角色，机器人管理员优先
+    pub async fn of(user_id: i64, member_role: MemberRole) -> Self {
+        if check_permission(user_id).await {
+            return Role::BotAdmin;
+        }
+        member_role.into()
+    }
+
+    pub fn parse(role: &str) -> Option<Self> {
+        match role {
+            "member" => Some(Role::Member),
+            "admin" => Some(Role::Admin),
+            "owner" => Some(Role::Owner),
+            "bot_admin" => Some(Role::BotAdmin),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Member => "member",
+            Role::Admin => "admin",
+            Role::Owner => "owner",
+            Role::BotAdmin => "bot_admin",
+        }
+    }
+
+    /// 群管理员及以上
+    pub fn is_manager(self) -> bool {
+        self >= Role::Admin
+    }
+}
+
+impl From<MemberRole> for Role {
+    fn from(role: MemberRole) -> Self {
+        match role {
+            MemberRole::Member => Role::Member,
+            MemberRole::Admin => Role::Admin,
+            MemberRole::Owner => Role::Owner,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_order() {
+        assert!(Role::BotAdmin > Role::Owner && Role::Owner > Role::Admin);
+        assert_eq!(Role::parse("bot_admin"), Some(Role::BotAdmin));
+        assert_eq!(Role::parse("teacher"), None);
+        assert!(!Role::Member.is_manager());
+    }
+
+    #[test]
+    fn test_grants_scope() {
+        let grant = |role: &str, scope_id| Model {
+            id: 0,
+            user_id: 1,
+            role: role.to_string(),
+            scope_id,
+            granted_by: None,
+            created_at: Default::default(),
+        };
+        let grants = Grants::from_models(false, &[grant(ROLE_TEACHER, 1001), grant(ROLE_TA, 123456)]);
+        assert!(!grants.super_admin && !grants.is_empty());
+        assert!(grants.can_view_grade(0, 1001));
+        assert!(grants.can_view_grade(123456, 2002));
+        assert!(!grants.can_view_grade(0, 2002));
+        assert!(Grants::from_models(true, &[]).can_view_grade(0, 2002));
+    }
 }
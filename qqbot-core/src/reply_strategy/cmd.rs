@@ -1,8 +1,13 @@
-use super::{MessageContent, MessageContext, RelyStrategy, ReplyError, Env};
-use crate::cmd::{CMD_REGISTRY, Execute};
-use crate::config::APPCONFIG;
+use super::{MessageContent, MessageContext, MessageSegment, RelyStrategy, ReplyError, Env};
+use crate::cmd::{
+    CMD_REGISTRY, CmdContext, CmdResult, Execute, alias,
+    session::{self, SessionKey},
+};
+use crate::GroupId;
+use crate::permission::Role;
+use crate::service::group_command_policy_service;
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct CommandReplyStrategy {}
 
 impl CommandReplyStrategy {
@@ -11,57 +16,115 @@ impl CommandReplyStrategy {
     }
 }
 
+/// 消息的文字部分是否为命令：以命令前缀开头，或整条消息是一个命令别名。
+/// 群聊中命令一般跟在 @机器人 之后，是混合消息，前缀和别名按群内设置
+pub async fn is_command(message: &MessageContent, group_id: Option<GroupId>) -> bool {
+    let text = message.get_text();
+    let mut words = text.split_whitespace();
+    match words.next() {
+        Some(first) => alias::resolve(first, words.next().is_none(), group_id).await.is_some(),
+        None => false,
+    }
+}
+
+/// 把命令消息拆成参数：文字按空白分割，@ 其他成员转为对应的 QQ 号，
+/// @ 机器人自己以及图片、引用等其他消息段忽略
+fn command_tokens(message: &MessageContent, self_id: i64) -> Vec<String> {
+    let split = |text: &str| text.split_whitespace().map(String::from).collect::<Vec<_>>();
+    match message {
+        MessageContent::Text(text) => split(text),
+        MessageContent::Mixed(segments) => segments
+            .iter()
+            .flat_map(|seg| match seg {
+                MessageSegment::Text { text } => split(text),
+                MessageSegment::At { qq } if *qq != self_id.to_string() => vec![qq.clone()],
+                _ => Vec::new(),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 impl RelyStrategy for CommandReplyStrategy {
     async fn reply(&self, ctx: &MessageContext) -> Result<MessageContent, ReplyError> {
-        match &ctx.message {
-            MessageContent::Text(cmd) => {
-                let args: Vec<&str> = cmd
-                    .split_whitespace()
-                    .filter(|arg| !arg.trim().is_empty())
-                    .collect();
-                let cmd = if args.len() > 0 {
-                    args[0].strip_prefix(&APPCONFIG.cmd_suffix).ok_or_else(|| {
-                        ReplyError(format!(
-                            "expected command suffix \"{}\"",
-                            APPCONFIG.cmd_suffix
-                        ))
-                    })
-                } else {
-                    return Err(ReplyError(format!(
-                        "the message isn't command, expected suffix {}",
-                        APPCONFIG.cmd_suffix
-                    )));
-                }?;
+        Ok(self.execute(ctx).await?.content)
+    }
+}
 
-                let mut args = args[1..].to_vec();
-                args.push("--sender");
-                let send_id = ctx.sender_id.to_string();
-                args.push(&send_id);
-                args.push("--myself");
-                let myself = ctx.self_id.to_string();
-                args.push(&myself);
-                args.push("--env");
-                let env = ctx.env.to_string();
-                args.push(&env);
-                
-                // 添加群组ID参数（如果是群聊环境）
-                let group_id_str;
-                if let Env::Group { group_id } = &ctx.env {
-                    args.push("--group-id");
-                    group_id_str = group_id.to_string();
-                    args.push(&group_id_str);
-                }
-                
-                if ctx.group_admin {
-                    args.push("--group-admin");
-                }
-                let cmd_result = CMD_REGISTRY
-                    .execute(cmd, &args)
-                    .await
-                    .map_err(|err| ReplyError(err.to_string()))?;
-                return Ok(MessageContent::Text(cmd_result.output));
-            }
-            _ => Err(ReplyError("only support text command message".into())),
+impl CommandReplyStrategy {
+    /// 执行命令，返回的结果带有发送方式，执行状态记录在日志中
+    pub async fn execute(&self, ctx: &MessageContext) -> Result<CmdResult, ReplyError> {
+        if !matches!(ctx.message, MessageContent::Text(_) | MessageContent::Mixed(_)) {
+            return Err(ReplyError("only support text command message".into()));
+        }
+        let group_id = match &ctx.env {
+            Env::Group { group_id } => Some(*group_id),
+            Env::Private => None,
+        };
+        let tokens = command_tokens(&ctx.message, ctx.self_id);
+        // 第一个词按群内的前缀和别名展开为命令名和参数
+        let Some(first) = tokens.first() else {
+            return Err(ReplyError("the message isn't command".into()));
+        };
+        let Some(expanded) = alias::resolve(first, tokens.len() == 1, group_id).await else {
+            return Err(ReplyError(format!(
+                "the message isn't command, expected prefix {}",
+                alias::prefix(group_id).await
+            )));
+        };
+        let cmd = expanded[0].as_str();
+        let role = Role::of(ctx.sender_id, ctx.sender_role).await;
+        // 群内停用或限制角色的命令不执行
+        if let Some(group_id) = group_id {
+            group_command_policy_service::check(group_id, cmd, role).await?;
         }
+
+        let args: Vec<&str> = expanded[1..]
+            .iter()
+            .chain(&tokens[1..])
+            .map(String::as_str)
+            .collect();
+        // 身份信息只来自消息本身，不混入用户输入的参数
+        let cmd_ctx = CmdContext {
+            sender: ctx.sender_id,
+            myself: ctx.self_id,
+            group_id: group_id.unwrap_or(0),
+            env: ctx.env.to_string(),
+            role,
+            // 引用回复时传入被引用消息的 id，如 /recall
+            reply_to: ctx.message.reply_id().and_then(|id| id.parse().ok()),
+        };
+        let result = CMD_REGISTRY.execute(&cmd_ctx, cmd, &args).await;
+        log::info!(
+            "command {} from {}: {}",
+            cmd,
+            ctx.sender_id,
+            result.as_ref().map_or("failed", |r| r.status.as_str())
+        );
+        let mut cmd_result = result.map_err(|err| ReplyError(err.to_string()))?;
+        // 需要继续输入的命令，把该用户的下一条消息交给它
+        session::track(SessionKey::new(ctx.sender_id, group_id), &mut cmd_result).await;
+        Ok(cmd_result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_command_tokens() {
+        let message = MessageContent::Mixed(vec![
+            MessageSegment::Reply { id: "7".to_string() },
+            MessageSegment::At { qq: "9999".to_string() },
+            MessageSegment::Text { text: " /mute ".to_string() },
+            MessageSegment::At { qq: "123456".to_string() },
+            MessageSegment::Text { text: " 10m".to_string() },
+        ]);
+        assert_eq!(command_tokens(&message, 9999), vec!["/mute", "123456", "10m"]);
+        assert!(is_command(&message, None).await);
+        // 不带前缀的别名只有单独发送时才是命令
+        assert!(is_command(&MessageContent::Text("成绩".to_string()), None).await);
+        assert!(!is_command(&MessageContent::Text("成绩 怎么算的".to_string()), None).await);
     }
 }
@@ -0,0 +1,193 @@
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use reqwest::Client;
+
+use super::llm::{ChatContent, ChatMessage, ChatRequest, ChatResponse};
+use crate::{ConversationMessage, SessionId, config::APPCONFIG, conversation::ConversationManager};
+
+/// 每条消息的格式开销（role、分隔符等）
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+static SUMMARY_CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .timeout(Duration::from_secs(APPCONFIG.llm.timeout_seconds))
+        .build()
+        .expect("Failed to create HTTP client")
+});
+
+/// 按模型分词器的大致特征估算 token 数：返回 (每个汉字的 token 数, 每个 token 对应的其他字符数)
+fn token_ratio(model: &str) -> (f32, f32) {
+    let model = model.to_lowercase();
+    if model.contains("gpt-4o") || model.contains("gpt-4.1") || model.contains("o1") || model.contains("o3") {
+        (0.8, 4.0)
+    } else if model.contains("gpt") {
+        (1.2, 4.0)
+    } else if ["qwen", "deepseek", "glm", "yi-"].iter().any(|name| model.contains(name)) {
+        (0.7, 3.5)
+    } else {
+        (1.0, 3.5)
+    }
+}
+
+/// 估算一段文字在指定模型下的 token 数
+pub fn estimate_tokens(model: &str, text: &str) -> usize {
+    let (cjk_ratio, chars_per_token) = token_ratio(model);
+    let cjk = text.chars().filter(|c| !c.is_ascii()).count();
+    let other = text.chars().count() - cjk;
+    (cjk as f32 * cjk_ratio + other as f32 / chars_per_token).ceil() as usize
+}
+
+/// 将摘要拼接到系统提示词中
+pub fn system_prompt_with_summary(system_prompt: &str, summary: Option<&str>) -> String {
+    match summary {
+        Some(summary) if !summary.trim().is_empty() => {
+            format!("{}\n\n以下是更早对话的摘要，供参考：\n{}", system_prompt, summary)
+        }
+        _ => system_prompt.to_string(),
+    }
+}
+
+/// 从最新的消息开始向前选取，直到用完 token 预算，返回按时间顺序排列的 (role, content)
+pub fn select_history(
+    model: &str,
+    history: &[ConversationMessage],
+    budget: usize,
+    format: impl Fn(&ConversationMessage) -> String,
+) -> Vec<(String, String)> {
+    let mut used = 0;
+    let mut selected = Vec::new();
+    for message in history.iter().rev() {
+        let content = format(message);
+        let tokens = estimate_tokens(model, &content) + MESSAGE_OVERHEAD_TOKENS;
+        if used + tokens > budget {
+            break;
+        }
+        used += tokens;
+        selected.push((message.role.clone(), content));
+    }
+    selected.reverse();
+    selected
+}
+
+/// 群聊中为用户消息加上发言者，便于模型区分不同成员
+pub fn format_message(message: &ConversationMessage, is_group: bool) -> String {
+    match (is_group, message.role.as_str(), message.user_id) {
+        (true, "user", Some(user_id)) => {
+            let default_username = format!("用户{}", user_id);
+            let username = message.username.as_deref().unwrap_or(&default_username);
+            format!("[{}]: {}", username, message.content)
+        }
+        _ => message.content.clone(),
+    }
+}
+
+/// 在后台把移出历史的消息并入会话摘要
+pub fn schedule_summary(session_id: SessionId) {
+    if !APPCONFIG.llm.summary_enabled {
+        return;
+    }
+    tokio::spawn(async move {
+        let (summary, evicted) = ConversationManager::take_evicted(session_id.clone()).await;
+        if evicted.is_empty() {
+            return;
+        }
+        let is_group = session_id.is_group();
+        let transcript = evicted
+            .iter()
+            .map(|message| {
+                let speaker = if message.role == "assistant" { "助手" } else { "用户" };
+                format!("{}: {}", speaker, format_message(message, is_group))
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        match summarize(summary.as_deref(), &transcript).await {
+            Ok(summary) => ConversationManager::set_summary(session_id, summary).await,
+            Err(err) => log::warn!("对话摘要生成失败: {}", err),
+        }
+    });
+}
+
+async fn summarize(previous: Option<&str>, transcript: &str) -> Result<String, String> {
+    let max_chars = APPCONFIG.llm.summary_max_chars;
+    let prompt = format!(
+        "请将以下对话压缩为不超过{}字的摘要，保留人物、问题、结论和待办事项，不要编造内容。\n\n已有摘要：\n{}\n\n新增对话：\n{}",
+        max_chars,
+        previous.unwrap_or("（无）"),
+        transcript
+    );
+    let text = complete(prompt, 0.3).await?;
+    Ok(text.chars().take(max_chars * 2).collect())
+}
+
+/// 不带历史的单轮补全，供摘要、记忆提取等后台任务使用
+pub(crate) async fn complete(prompt: String, temperature: f32) -> Result<String, String> {
+    let request = ChatRequest {
+        model: APPCONFIG.llm.model.clone(),
+        messages: vec![ChatMessage {
+            role: "user".to_string(),
+            content: ChatContent::Text(prompt),
+        }],
+        temperature,
+        max_tokens: Some(APPCONFIG.llm.max_tokens),
+        top_p: Some(APPCONFIG.llm.top_p),
+        stream: false,
+    };
+
+    let response = SUMMARY_CLIENT
+        .post(format!("{}/chat/completions", APPCONFIG.llm.base_url))
+        .header("Authorization", format!("Bearer {}", APPCONFIG.llm.api_key))
+        .json(&request)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("LLM API returned error {}", response.status()));
+    }
+    let chat_response: ChatResponse = response.json().await.map_err(|e| e.to_string())?;
+    match chat_response.choices.into_iter().next().map(|choice| choice.message.content) {
+        Some(ChatContent::Text(text)) => Ok(text),
+        _ => Err("No response from LLM".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn message(role: &str, content: &str) -> ConversationMessage {
+        ConversationMessage {
+            role: role.to_string(),
+            content: content.to_string(),
+            timestamp: Utc::now(),
+            user_id: Some(1),
+            username: Some("张三".to_string()),
+            message_id: None,
+        }
+    }
+
+    #[test]
+    fn test_select_history_within_budget() {
+        assert_eq!(estimate_tokens("Qwen/Qwen2-VL-72B-Instruct", "你好世界"), 3);
+        assert_eq!(estimate_tokens("gpt-3.5-turbo", "hello world!"), 3);
+
+        let history = vec![
+            message("user", "第一条很早的消息，内容比较长，会被预算挤掉"),
+            message("assistant", "好的"),
+            message("user", "最新的问题"),
+        ];
+        let selected = select_history("qwen", &history, 20, |m| format_message(m, true));
+        assert_eq!(
+            selected,
+            vec![
+                ("assistant".to_string(), "好的".to_string()),
+                ("user".to_string(), "[张三]: 最新的问题".to_string()),
+            ]
+        );
+        assert_eq!(
+            system_prompt_with_summary("提示词", Some("之前聊了成绩")),
+            "提示词\n\n以下是更早对话的摘要，供参考：\n之前聊了成绩"
+        );
+    }
+}
@@ -1,9 +1,12 @@
-use super::{Env, MessageContent, MessageContext, RelyStrategy, ReplyError, MessageSegment, ImageInfo};
-use crate::{GroupId, SessionId, UserId, config::APPCONFIG};
+use super::{Env, MessageContent, MessageContext, RelyStrategy, ReplyError, MessageSegment, ImageInfo, QuotedMessage, context};
+use crate::{GroupId, SessionId, UserId, config::APPCONFIG, content_filter::Stage, service::{content_filter_service, knowledge_service, memory_service}};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
+/// 引用内容放入提示词时的最大字符数，避免长消息挤占上下文
+const QUOTE_MAX_CHARS: usize = 300;
+
 /// LLM API 的图片数据结构，对应 OneBot11 协议
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ImageData {
@@ -88,6 +91,12 @@ pub struct SimpleLlmReplyStrategy {
     model: String,
 }
 
+impl Default for SimpleLlmReplyStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SimpleLlmReplyStrategy {
     pub fn new() -> Self {
         Self {
@@ -134,11 +143,8 @@ impl SimpleLlmReplyStrategy {
                             text_parts.push(format!("[图片: {}]", image_info.file));
                             images.push(self.convert_image_info_to_data(image_info));
                         },
-                        MessageSegment::At { qq } => {
-                            text_parts.push(format!("[@{}]", qq));
-                        },
-                        MessageSegment::Face { id } => {
-                            text_parts.push(format!("[表情{}]", id));
+                        other => {
+                            text_parts.push(other.describe());
                         },
                     }
                 }
@@ -150,55 +156,44 @@ impl SimpleLlmReplyStrategy {
     }
 
     /// 调用 LLM API（支持视觉模型）
-    async fn call_llm_api(&self, content: String, image: Option<ImageData>) -> Result<String, ReplyError> {
+    async fn call_llm_api(&self, history: Vec<ChatMessage>, content: String, images: Vec<ImageData>) -> Result<String, ReplyError> {
         // 构建消息内容
-        let user_content = if let Some(image_data) = image {
-            // 如果有图片，构建包含图片的消息
-            if let Some(image_url) = &image_data.url {
-                // 检查是否是HTTP URL，如果是则直接使用，否则尝试下载并转换为base64
-                let final_image_url = if image_url.starts_with("http://") || image_url.starts_with("https://") {
-                    image_url.clone()
-                } else {
-                    // 如果URL不是标准HTTP URL，尝试下载并转换为base64
-                    match self.download_and_encode_image(image_url).await {
-                        Ok(base64_url) => base64_url,
-                        Err(_) => {
-                            // 下载失败，回退到文本描述
-                            return Ok(format!("{} [图片: {}, 无法加载]", content, image_data.file));
-                        }
-                    }
-                };
-                
-                // 使用图片URL构建视觉消息
-                ChatContent::Array(vec![
-                    ContentPart::Text { text: content },
-                    ContentPart::ImageUrl { 
-                        image_url: ImageUrl { 
-                            url: final_image_url,
-                            detail: Some("high".to_string()) // 高清晰度识别
-                        }
-                    },
-                ])
-            } else {
-                // 如果没有URL，仅使用文本描述
-                let description = format!("{} [图片文件: {}]", content, image_data.file);
-                ChatContent::Text(description)
-            }
-        } else {
+        let user_content = if images.is_empty() {
             // 纯文本消息
             ChatContent::Text(content)
+        } else {
+            // 每张图片作为一个内容部分，加载失败的图片以文字说明代替
+            let mut parts = Vec::new();
+            let mut failed = Vec::new();
+            for image_data in images.iter().take(APPCONFIG.llm.vision.max_images) {
+                match super::vision::load_image(&self.client, image_data).await {
+                    Ok(data_url) => parts.push(ContentPart::ImageUrl {
+                        image_url: ImageUrl {
+                            url: data_url.to_string(),
+                            detail: Some("high".to_string()), // 高清晰度识别
+                        },
+                    }),
+                    Err(_) => failed.push(format!("[图片: {}, 无法加载]", image_data.file)),
+                }
+            }
+            let text = if failed.is_empty() {
+                content
+            } else {
+                format!("{} {}", content, failed.join(" "))
+            };
+            if parts.is_empty() {
+                ChatContent::Text(text)
+            } else {
+                parts.insert(0, ContentPart::Text { text });
+                ChatContent::Array(parts)
+            }
         };
         
-        let messages = vec![
-            ChatMessage {
-                role: "system".to_string(),
-                content: ChatContent::Text(APPCONFIG.llm.system_prompt.clone()),
-            },
-            ChatMessage {
-                role: "user".to_string(),
-                content: user_content,
-            },
-        ];
+        let mut messages = history;
+        messages.push(ChatMessage {
+            role: "user".to_string(),
+            content: user_content,
+        });
 
         let request = ChatRequest {
             model: self.model.clone(),
@@ -256,45 +251,62 @@ impl SimpleLlmReplyStrategy {
         }
     }
 
-    /// 下载图片并转换为Base64编码
-    async fn download_and_encode_image(&self, image_url: &str) -> Result<String, ReplyError> {
-        let response = self
-            .client
-            .get(image_url)
-            .send()
-            .await
-            .map_err(|e| ReplyError(format!("Failed to download image: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(ReplyError(format!(
-                "Failed to download image, status: {}",
-                response.status()
-            )));
+    /// 解析被引用的消息：优先使用插件通过 get_msg 取回的内容，否则在会话记录中按 id 查找
+    async fn resolve_quoted(&self, ctx: &MessageContext) -> Option<(String, Vec<ImageData>)> {
+        if let Some(QuotedMessage { sender_id, sender_name, content, .. }) = &ctx.quoted {
+            let (text, images) = self.extract_content_and_images(content);
+            let who = if *sender_id == Some(ctx.self_id) {
+                "你".to_string()
+            } else {
+                sender_name
+                    .clone()
+                    .or_else(|| sender_id.map(|id| format!("用户{}", id)))
+                    .unwrap_or_else(|| "某人".to_string())
+            };
+            return Some((format!("{}: {}", who, text), images));
         }
 
-        let image_bytes = response
-            .bytes()
-            .await
-            .map_err(|e| ReplyError(format!("Failed to read image bytes: {}", e)))?;
-
-        // 编码为Base64
-        use base64::{Engine as _, engine::general_purpose};
-        let base64_image = general_purpose::STANDARD.encode(&image_bytes);
-        
-        // 尝试检测图片格式
-        let content_type = if image_url.to_lowercase().ends_with(".png") {
-            "image/png"
-        } else if image_url.to_lowercase().ends_with(".jpg") || image_url.to_lowercase().ends_with(".jpeg") {
-            "image/jpeg"
-        } else if image_url.to_lowercase().ends_with(".gif") {
-            "image/gif"
-        } else if image_url.to_lowercase().ends_with(".webp") {
-            "image/webp"
-        } else {
-            "image/jpeg" // 默认
+        let reply_id = ctx.message.reply_id()?;
+        let session_id = match &ctx.env {
+            Env::Private => SessionId::Private(ctx.sender_id as UserId),
+            Env::Group { group_id } => SessionId::Group(*group_id as GroupId),
+        };
+        let message = crate::conversation::ConversationManager::find_message(session_id, reply_id).await?;
+        let who = match message.role.as_str() {
+            "assistant" => "你".to_string(),
+            _ => message.username.unwrap_or_else(|| "用户".to_string()),
         };
+        Some((format!("{}: {}", who, message.content), Vec::new()))
+    }
 
-        Ok(format!("data:{};base64,{}", content_type, base64_image))
+    /// 构建系统提示词（含摘要）和按 token 预算选取的历史对话，需在记录本条消息之前调用
+    async fn build_history(&self, ctx: &MessageContext, session_id: SessionId, content: &str) -> Vec<ChatMessage> {
+        let summary = crate::conversation::ConversationManager::get_summary(session_id.clone()).await;
+        // 长期记忆是个人信息，只在私聊中注入，避免在群里泄露
+        let memories = match ctx.env {
+            Env::Private => memory_service::relevant_memories(ctx.sender_id as UserId, content).await,
+            Env::Group { .. } => Vec::new(),
+        };
+        let references = knowledge_service::retrieve(content).await;
+        let system_prompt = context::system_prompt_with_summary(&APPCONFIG.llm.system_prompt, summary.as_deref());
+        let system_prompt = memory_service::system_prompt_with_memories(&system_prompt, &memories);
+        let mut messages = vec![ChatMessage {
+            role: "system".to_string(),
+            content: ChatContent::Text(knowledge_service::system_prompt_with_knowledge(&system_prompt, &references)),
+        }];
+
+        let history = crate::conversation::ConversationManager::get_conversation_history(session_id, usize::MAX).await;
+        let is_group = matches!(ctx.env, Env::Group { .. });
+        let budget = APPCONFIG.llm.context_tokens.saturating_sub(context::estimate_tokens(&self.model, content));
+        for (role, content) in context::select_history(&self.model, &history, budget, |msg| {
+            context::format_message(msg, is_group)
+        }) {
+            messages.push(ChatMessage {
+                role,
+                content: ChatContent::Text(content),
+            });
+        }
+        messages
     }
 
     /// 记录消息到对话历史
@@ -310,7 +322,7 @@ impl SimpleLlmReplyStrategy {
             Env::Private => format!("用户{}", ctx.sender_id),
         };
 
-        crate::conversation::ConversationManager::add_user_message_with_info(
+        crate::conversation::ConversationManager::add_user_message_with_id(
             session_id.clone(),
             content.to_string(),
             ctx.sender_id as UserId,
@@ -318,6 +330,7 @@ impl SimpleLlmReplyStrategy {
                 Env::Group { .. } => Some(username),
                 Env::Private => None,
             },
+            ctx.message_id,
         ).await;
     }
 
@@ -345,17 +358,45 @@ impl RelyStrategy for SimpleLlmReplyStrategy {
             return Err(ReplyError("空消息内容".to_string()));
         }
 
-        // 记录用户消息
+        let session_id = match &ctx.env {
+            Env::Private => SessionId::Private(ctx.sender_id as UserId),
+            Env::Group { group_id } => SessionId::Group(*group_id as GroupId),
+        };
+        let history = self.build_history(ctx, session_id.clone(), &content).await;
+
+        // 记录用户消息，并在后台提取值得长期记住的信息
         self.log_message(ctx, &content).await;
+        memory_service::schedule_extract(ctx.sender_id as UserId, content.clone());
+
+        // 引用回复时把被引用的内容作为上下文放在提问前
+        let mut quoted_images = Vec::new();
+        let prompt = match self.resolve_quoted(ctx).await {
+            Some((quoted, images)) => {
+                quoted_images = images;
+                let quoted: String = quoted.chars().take(QUOTE_MAX_CHARS).collect();
+                format!("（引用了 {}）\n{}", quoted, content)
+            }
+            None => content,
+        };
 
-        // 处理图片（如果有的话，使用第一张图片）
-        let image_data = images.first().cloned();
+        // 处理图片：本条消息没有图片时使用被引用消息中的图片
+        let images = if images.is_empty() { quoted_images } else { images };
         
         // 调用 LLM API
-        let response = self.call_llm_api(content, image_data).await?;
+        let response = self.call_llm_api(history, prompt, images).await?;
+
+        // 回复先经过内容审核，被拦截的回复不写入历史和摘要
+        let group_id = match &ctx.env {
+            Env::Group { group_id } => Some(*group_id),
+            Env::Private => None,
+        };
+        if content_filter_service::screen(Stage::Output, &response, ctx.sender_id, group_id).await.is_some() {
+            return Ok(MessageContent::Text(content_filter_service::refusal()));
+        }
 
-        // 记录回复
+        // 记录回复，并在后台把移出历史的消息并入摘要
         self.log_reply(ctx, &response).await;
+        context::schedule_summary(session_id);
 
         Ok(MessageContent::Text(response))
     }
@@ -1,5 +1,6 @@
-use super::{Env, MessageContent, MessageContext, RelyStrategy, ReplyError, FileAttachment};
-use crate::{GroupId, SessionId, UserId, config::APPCONFIG, service::user_config_service::UserConfigService, service::group_config_service::GroupConfigService};
+use super::{Env, MessageContent, MessageContext, RelyStrategy, ReplyError, FileAttachment, context};
+use crate::conversation::ConversationManager;
+use crate::{GroupId, SessionId, UserId, config::APPCONFIG, service::user_config_service::UserConfigService, service::group_config_service::GroupConfigService, service::{knowledge_service, memory_service}};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -69,6 +70,12 @@ pub struct LlmReplyStrategy {
     model: String,
 }
 
+impl Default for LlmReplyStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl LlmReplyStrategy {
     pub fn new() -> Self {
         Self {
@@ -89,40 +96,32 @@ impl LlmReplyStrategy {
         custom_prompt: Option<String>,
     ) -> Vec<ChatMessage> {
         let system_prompt = custom_prompt.unwrap_or_else(|| APPCONFIG.llm.system_prompt.clone());
+        // 更早的对话以摘要形式放入系统提示词
+        let summary = ConversationManager::get_summary(session_id.clone()).await;
+        let system_prompt = context::system_prompt_with_summary(&system_prompt, summary.as_deref());
+        // 注入与本条消息相关的用户长期记忆和课程资料，记忆只在私聊中注入，避免在群里泄露
+        let query = ctx.message.describe();
+        let memories = match ctx.env {
+            Env::Private => memory_service::relevant_memories(ctx.sender_id as UserId, &query).await,
+            Env::Group { .. } => Vec::new(),
+        };
+        let references = knowledge_service::retrieve(&query).await;
+        let system_prompt = memory_service::system_prompt_with_memories(&system_prompt, &memories);
         let mut messages = vec![ChatMessage {
             role: "system".to_string(),
-            content: system_prompt,
+            content: knowledge_service::system_prompt_with_knowledge(&system_prompt, &references),
             image_data: None,
         }];
 
-        // 获取历史对话 - 使用 ConversationManager 简化逻辑
-        let history = crate::conversation::ConversationManager::get_conversation_history(
-            session_id, 10
-        ).await;
-
-        // 将历史对话转换为ChatMessage格式，在群聊中包含用户信息
-        for conv_msg in history {
-            let content = match &ctx.env {
-                Env::Group { .. } => {
-                    // 在群聊中，为每条消息添加用户标识
-                    if conv_msg.role == "user" {
-                        if let Some(user_id) = conv_msg.user_id {
-                            let default_username = format!("用户{}", user_id);
-                            let username = conv_msg.username.as_deref()
-                                .unwrap_or(&default_username);
-                            format!("[{}]: {}", username, conv_msg.content)
-                        } else {
-                            conv_msg.content.clone()
-                        }
-                    } else {
-                        conv_msg.content.clone()
-                    }
-                }
-                Env::Private => conv_msg.content.clone(),
-            };
-
+        // 按 token 预算选取最近的历史对话，在群聊中包含用户信息
+        let history = ConversationManager::get_conversation_history(session_id, usize::MAX).await;
+        let is_group = matches!(ctx.env, Env::Group { .. });
+        let selected = context::select_history(&self.model, &history, APPCONFIG.llm.context_tokens, |msg| {
+            context::format_message(msg, is_group)
+        });
+        for (role, content) in selected {
             messages.push(ChatMessage {
-                role: conv_msg.role.clone(),
+                role,
                 content,
                 image_data: None,
             });
@@ -233,10 +232,8 @@ impl LlmReplyStrategy {
         let image_data = self.extract_image_data_from_attachment(attachment)?;
         
         // 获取自定义提示词（群组优先或用户配置）
-        let custom_prompt = match self.get_custom_prompt(ctx).await {
-            Ok(prompt) => prompt,
-            Err(_) => None, // 如果获取失败，使用默认提示词
-        };
+        // 如果获取失败，使用默认提示词
+        let custom_prompt = self.get_custom_prompt(ctx).await.unwrap_or_default();
 
         // 记录图片消息到对话历史
         let username = match &ctx.env {
@@ -262,13 +259,13 @@ impl LlmReplyStrategy {
         let mut messages = self.get_conversation_history(ctx, session_id.clone(), custom_prompt).await;
         
         // 为最后一条用户消息添加图片数据
-        if let Some(last_message) = messages.last_mut() {
-            if last_message.role == "user" {
-                last_message.image_data = Some(image_data.clone());
-                // 如果图片有描述信息，也包含在内容中
-                if let Some(summary) = &image_data.summary {
-                    last_message.content = format!("{} (图片描述: {})", last_message.content, summary);
-                }
+        if let Some(last_message) = messages.last_mut()
+            && last_message.role == "user"
+        {
+            last_message.image_data = Some(image_data.clone());
+            // 如果图片有描述信息，也包含在内容中
+            if let Some(summary) = &image_data.summary {
+                last_message.content = format!("{} (图片描述: {})", last_message.content, summary);
             }
         }
 
@@ -276,9 +273,10 @@ impl LlmReplyStrategy {
 
         // 记录助手回复到对话历史
         crate::conversation::ConversationManager::add_assistant_message(
-            session_id,
+            session_id.clone(),
             response.clone(),
         ).await;
+        context::schedule_summary(session_id);
 
         Ok(MessageContent::Text(response))
     }
@@ -371,10 +369,8 @@ impl LlmReplyStrategy {
         };
 
         // 获取自定义提示词（群组优先或用户配置）
-        let custom_prompt = match self.get_custom_prompt(ctx).await {
-            Ok(prompt) => prompt,
-            Err(_) => None, // 如果获取失败，使用默认提示词
-        };
+        // 如果获取失败，使用默认提示词
+        let custom_prompt = self.get_custom_prompt(ctx).await.unwrap_or_default();
 
         // 记录用户消息到对话历史
         let username = match &ctx.env {
@@ -397,13 +393,15 @@ impl LlmReplyStrategy {
 
         // 构建消息
         let messages = self.get_conversation_history(ctx, session_id.clone(), custom_prompt).await;
+        memory_service::schedule_extract(ctx.sender_id as UserId, text.to_string());
         let response = self.call_llm_api(messages).await?;
 
         // 记录助手回复到对话历史
         crate::conversation::ConversationManager::add_assistant_message(
-            session_id,
+            session_id.clone(),
             response.clone(),
         ).await;
+        context::schedule_summary(session_id);
 
         Ok(MessageContent::Text(response))
     }
@@ -418,10 +416,8 @@ impl LlmReplyStrategy {
         };
 
         // 获取自定义提示词（群组优先或用户配置）
-        let custom_prompt = match self.get_custom_prompt(ctx).await {
-            Ok(prompt) => prompt,
-            Err(_) => None, // 如果获取失败，使用默认提示词
-        };
+        // 如果获取失败，使用默认提示词
+        let custom_prompt = self.get_custom_prompt(ctx).await.unwrap_or_default();
 
         // 构建描述信息，包含文本和图片信息
         let mut content_parts = Vec::new();
@@ -452,11 +448,8 @@ impl LlmReplyStrategy {
                     };
                     image_data_list.push(image_data);
                 },
-                MessageSegment::At { qq } => {
-                    content_parts.push(format!("[@{}]", qq));
-                },
-                MessageSegment::Face { id } => {
-                    content_parts.push(format!("[表情{}]", id));
+                other => {
+                    content_parts.push(other.describe());
                 },
             }
         }
@@ -486,16 +479,16 @@ impl LlmReplyStrategy {
         let mut messages = self.get_conversation_history(ctx, session_id.clone(), custom_prompt).await;
         
         // 如果有图片，为最后一条用户消息添加第一张图片的数据
-        if has_image && !image_data_list.is_empty() {
-            if let Some(last_message) = messages.last_mut() {
-                if last_message.role == "user" {
-                    last_message.image_data = Some(image_data_list[0].clone());
-                    
-                    // 如果图片有描述信息，也包含在内容中
-                    if let Some(summary) = &image_data_list[0].summary {
-                        last_message.content = format!("{} (图片描述: {})", last_message.content, summary);
-                    }
-                }
+        if has_image
+            && !image_data_list.is_empty()
+            && let Some(last_message) = messages.last_mut()
+            && last_message.role == "user"
+        {
+            last_message.image_data = Some(image_data_list[0].clone());
+
+            // 如果图片有描述信息，也包含在内容中
+            if let Some(summary) = &image_data_list[0].summary {
+                last_message.content = format!("{} (图片描述: {})", last_message.content, summary);
             }
         }
 
@@ -503,9 +496,10 @@ impl LlmReplyStrategy {
 
         // 记录助手回复到对话历史
         crate::conversation::ConversationManager::add_assistant_message(
-            session_id,
+            session_id.clone(),
             response.clone(),
         ).await;
+        context::schedule_summary(session_id);
 
         Ok(MessageContent::Text(response))
     }
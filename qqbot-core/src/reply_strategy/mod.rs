@@ -1,10 +1,14 @@
 use crate::error::AppError;
+use crate::permission::MemberRole;
 use core::fmt;
 
 pub mod cmd;
+pub mod context;
 pub mod llm;
 pub mod llm_full;
+pub mod onebot;
 pub mod reply_manager;
+pub mod vision;
 #[derive(Debug, Clone)]
 pub enum Env {
     Group { group_id: i64 },
@@ -39,6 +43,25 @@ pub struct ImageInfo {
     pub emoji_package_id: Option<String>,
 }
 
+// 文件、语音、视频等媒体消息段的公共信息
+#[derive(Debug, Clone, Default)]
+pub struct MediaInfo {
+    pub file: String,
+    pub url: Option<String>,
+    pub name: Option<String>,
+    pub file_size: Option<u64>,
+}
+
+// 商城表情（mface）信息
+#[derive(Debug, Clone, Default)]
+pub struct MarketFaceInfo {
+    pub emoji_id: Option<String>,
+    pub emoji_package_id: Option<String>,
+    pub key: Option<String>,
+    pub summary: Option<String>,
+    pub url: Option<String>,
+}
+
 // 消息段类型，对应OneBot11协议的消息段
 #[derive(Debug, Clone)]
 pub enum MessageSegment {
@@ -46,9 +69,69 @@ pub enum MessageSegment {
     Image { image_info: ImageInfo },
     At { qq: String },
     Face { id: u32 },
+    Reply { id: String },                   // 引用回复，id 为被引用消息的 message_id
+    Forward { id: String },                 // 合并转发，id 可用于 get_forward_msg
+    File { media: MediaInfo },
+    Record { media: MediaInfo },            // 语音
+    Video { media: MediaInfo },
+    Json { data: String },                  // 小程序/分享卡片，data 为原始 JSON 字符串
+    Xml { data: String },
+    MFace { face: MarketFaceInfo },
     // 可以根据需要添加更多消息段类型
 }
 
+impl MessageSegment {
+    /// OneBot 消息段类型名
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MessageSegment::Text { .. } => "text",
+            MessageSegment::Image { .. } => "image",
+            MessageSegment::At { .. } => "at",
+            MessageSegment::Face { .. } => "face",
+            MessageSegment::Reply { .. } => "reply",
+            MessageSegment::Forward { .. } => "forward",
+            MessageSegment::File { .. } => "file",
+            MessageSegment::Record { .. } => "record",
+            MessageSegment::Video { .. } => "video",
+            MessageSegment::Json { .. } => "json",
+            MessageSegment::Xml { .. } => "xml",
+            MessageSegment::MFace { .. } => "mface",
+        }
+    }
+
+    /// 消息段的文字描述，用于对话历史和 LLM 上下文
+    pub fn describe(&self) -> String {
+        match self {
+            MessageSegment::Text { text } => text.clone(),
+            MessageSegment::Image { image_info } => format!("[图片: {}]", image_info.file),
+            MessageSegment::At { qq } => format!("[@{}]", qq),
+            MessageSegment::Face { id } => format!("[表情{}]", id),
+            MessageSegment::Reply { .. } => "[引用消息]".to_string(),
+            MessageSegment::Forward { .. } => "[合并转发消息]".to_string(),
+            MessageSegment::File { media } => {
+                format!("[文件: {}]", media.name.as_deref().unwrap_or(&media.file))
+            }
+            MessageSegment::Record { .. } => "[语音]".to_string(),
+            MessageSegment::Video { .. } => "[视频]".to_string(),
+            MessageSegment::Json { data } => {
+                // 卡片消息一般带有 prompt 字段，如 "[QQ小程序]课程签到"
+                let prompt = serde_json::from_str::<serde_json::Value>(data)
+                    .ok()
+                    .and_then(|v| v.get("prompt").and_then(|p| p.as_str()).map(String::from));
+                match prompt {
+                    Some(prompt) => format!("[卡片消息: {}]", prompt),
+                    None => "[卡片消息]".to_string(),
+                }
+            }
+            MessageSegment::Xml { .. } => "[卡片消息]".to_string(),
+            MessageSegment::MFace { face } => match &face.summary {
+                Some(summary) => format!("[表情: {}]", summary),
+                None => "[表情]".to_string(),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum MessageContent {
     Text(String),                           // 纯文本消息（向后兼容）
@@ -58,6 +141,21 @@ pub enum MessageContent {
 }
 
 impl MessageContent {
+    /// 在回复前加上对原消息的引用，没有 message_id 时原样返回
+    pub fn quote(self, message_id: Option<i64>) -> MessageContent {
+        let Some(id) = message_id else {
+            return self;
+        };
+        let mut segments = vec![MessageSegment::Reply { id: id.to_string() }];
+        match self {
+            MessageContent::Text(text) => segments.push(MessageSegment::Text { text }),
+            MessageContent::Mixed(rest) => segments.extend(rest),
+            // 图片和文件附件无法与引用合并为消息段，保持原样
+            other => return other,
+        }
+        MessageContent::Mixed(segments)
+    }
+
     pub fn has_text(&self) -> bool {
         match self {
             MessageContent::Text(text) => !text.trim().is_empty(),
@@ -93,7 +191,41 @@ impl MessageContent {
             _ => false,
         }
     }
+
+    /// 是否包含语音消息段
+    pub fn has_record(&self) -> bool {
+        match self {
+            MessageContent::Mixed(segments) => segments.iter().any(|seg| matches!(seg, MessageSegment::Record { .. })),
+            _ => false,
+        }
+    }
     
+    /// 被引用消息的 message_id
+    pub fn reply_id(&self) -> Option<&str> {
+        match self {
+            MessageContent::Mixed(segments) => segments.iter().find_map(|seg| match seg {
+                MessageSegment::Reply { id } => Some(id.as_str()),
+                _ => None,
+            }),
+            _ => None,
+        }
+    }
+
+    /// 整条消息的文字描述，非文本消息段以 [类型] 形式表示
+    pub fn describe(&self) -> String {
+        match self {
+            MessageContent::Text(text) => text.clone(),
+            MessageContent::Image(attachment) => format!("[图片: {}]", attachment.name),
+            MessageContent::File(attachment) => format!("[文件: {}]", attachment.name),
+            MessageContent::Mixed(segments) => segments
+                .iter()
+                .map(|seg| seg.describe())
+                .filter(|desc| !desc.trim().is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+
     pub fn get_images(&self) -> Vec<&ImageInfo> {
         match self {
             MessageContent::Mixed(segments) => {
@@ -108,15 +240,26 @@ impl MessageContent {
         }
     }
 }
+// 被引用的消息，由插件通过 get_msg 解析
+#[derive(Debug, Clone)]
+pub struct QuotedMessage {
+    pub message_id: String,
+    pub sender_id: Option<i64>,
+    pub sender_name: Option<String>,
+    pub content: MessageContent,
+}
+
 #[derive(Debug)]
 pub struct MessageContext {
     pub env: Env,
     pub sender_id: i64,
     pub self_id: i64,
-    pub group_admin: bool,
+    pub message_id: Option<i64>,            // 原消息 id，用于引用回复
+    pub sender_role: MemberRole,            // 发送者在群内的角色，私聊为普通成员
     pub message: MessageContent,
     pub history: Vec<MessageContent>,
     pub sender_name: Option<String>, // 发送者的昵称或用户名，在群聊中特别有用
+    pub quoted: Option<QuotedMessage>,      // 引用回复时被引用的消息
 }
 #[derive(Debug)]
 pub struct ReplyError(pub String);
@@ -149,21 +292,52 @@ pub trait RelyStrategy {
     ) -> impl std::future::Future<Output = Result<MessageContent, ReplyError>> + Send;
 }
 
+/// 回复的发送方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Delivery {
+    /// 在收到消息的私聊或群里回复
+    #[default]
+    InPlace,
+    /// 私聊发送者，如在群里查询成绩
+    Private,
+    /// 不回复
+    Silent,
+}
+
+/// 回复内容及其发送方式
+#[derive(Debug, Clone)]
+pub struct Reply {
+    pub content: MessageContent,
+    pub delivery: Delivery,
+}
+
+impl Reply {
+    pub fn in_place(content: MessageContent) -> Self {
+        Self {
+            content,
+            delivery: Delivery::InPlace,
+        }
+    }
+}
+
 #[tokio::test]
+#[ignore = "needs the MySQL database from config"]
 async fn reply_message_test() -> Result<(), Box<dyn std::error::Error>> {
     use crate::config::get_db;
     use cmd::CommandReplyStrategy;
 
     get_db().await;
-    let mc = MessageContent::Text(vec!["/query".into(), "grade"].join(" "));
+    let mc = MessageContent::Text(["/query", "grade"].join(" "));
     let message_context = MessageContext {
         env: Env::Private,
         sender_id: 87654321,
         self_id: 9999,
+        message_id: None,
         message: mc,
-        group_admin: false,
+        sender_role: MemberRole::Member,
         history: vec![],
         sender_name: None,
+        quoted: None,
     };
     let cmd_strategy = CommandReplyStrategy::new();
     let result = cmd_strategy.reply(&message_context).await;
@@ -0,0 +1,157 @@
+use base64::{Engine as _, engine::general_purpose};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::{FileAttachment, MessageContent, MessageSegment};
+
+/// OneBot11 消息段，序列化后即 `{"type": ..., "data": {...}}`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OneBotSegment {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub data: Value,
+}
+
+impl OneBotSegment {
+    fn new(type_: &str, data: Value) -> Self {
+        Self {
+            type_: type_.to_string(),
+            data,
+        }
+    }
+}
+
+/// 将回复内容转换为 OneBot11 消息段数组，空文本段会被丢弃
+pub fn to_onebot_segments(content: &MessageContent) -> Vec<OneBotSegment> {
+    match content {
+        MessageContent::Text(text) => text_segment(text).into_iter().collect(),
+        MessageContent::Image(attachment) => vec![OneBotSegment::new(
+            "image",
+            json!({ "file": base64_file(attachment) }),
+        )],
+        MessageContent::File(attachment) => vec![OneBotSegment::new(
+            "file",
+            json!({ "file": base64_file(attachment), "name": attachment.name }),
+        )],
+        MessageContent::Mixed(segments) => {
+            let mut result: Vec<OneBotSegment> = segments.iter().filter_map(to_onebot_segment).collect();
+            // 协议端要求引用回复段位于消息开头
+            if let Some(pos) = result.iter().position(|seg| seg.type_ == "reply") {
+                let reply = result.remove(pos);
+                result.insert(0, reply);
+            }
+            result
+        }
+    }
+}
+
+/// 转换为可直接放入 `send_msg` 的 JSON 消息数组
+pub fn to_onebot_message(content: &MessageContent) -> Value {
+    json!(to_onebot_segments(content))
+}
+
+fn to_onebot_segment(segment: &MessageSegment) -> Option<OneBotSegment> {
+    match segment {
+        MessageSegment::Text { text } => text_segment(text),
+        MessageSegment::Image { image_info } => {
+            // 优先使用可访问的 URL，否则原样传递 file（本地路径、base64:// 或协议端缓存名）
+            let file = image_info
+                .url
+                .as_ref()
+                .filter(|url| url.starts_with("http"))
+                .unwrap_or(&image_info.file);
+            let mut data = json!({ "file": file });
+            if let Some(summary) = &image_info.summary {
+                data["summary"] = json!(summary);
+            }
+            if let Some(sub_type) = image_info.sub_type {
+                data["sub_type"] = json!(sub_type);
+            }
+            Some(OneBotSegment::new("image", data))
+        }
+        MessageSegment::At { qq } => Some(OneBotSegment::new("at", json!({ "qq": qq }))),
+        MessageSegment::Face { id } => Some(OneBotSegment::new("face", json!({ "id": id.to_string() }))),
+        MessageSegment::Reply { id } => Some(OneBotSegment::new("reply", json!({ "id": id }))),
+        MessageSegment::Forward { id } => Some(OneBotSegment::new("forward", json!({ "id": id }))),
+        MessageSegment::File { media } => {
+            let mut data = json!({ "file": media.file });
+            if let Some(name) = &media.name {
+                data["name"] = json!(name);
+            }
+            Some(OneBotSegment::new("file", data))
+        }
+        MessageSegment::Record { media } => Some(OneBotSegment::new("record", json!({ "file": media.file }))),
+        MessageSegment::Video { media } => Some(OneBotSegment::new("video", json!({ "file": media.file }))),
+        MessageSegment::Json { data } => Some(OneBotSegment::new("json", json!({ "data": data }))),
+        MessageSegment::Xml { data } => Some(OneBotSegment::new("xml", json!({ "data": data }))),
+        MessageSegment::MFace { face } => Some(OneBotSegment::new(
+            "mface",
+            json!({
+                "emoji_id": face.emoji_id,
+                "emoji_package_id": face.emoji_package_id,
+                "key": face.key,
+                "summary": face.summary,
+            }),
+        )),
+    }
+}
+
+fn text_segment(text: &str) -> Option<OneBotSegment> {
+    if text.is_empty() {
+        None
+    } else {
+        Some(OneBotSegment::new("text", json!({ "text": text })))
+    }
+}
+
+fn base64_file(attachment: &FileAttachment) -> String {
+    format!("base64://{}", general_purpose::STANDARD.encode(&attachment.content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reply_strategy::ImageInfo;
+
+    #[test]
+    fn test_mixed_to_onebot() {
+        let content = MessageContent::Mixed(vec![
+            MessageSegment::At { qq: "10001".into() },
+            MessageSegment::Text { text: " 你好".into() },
+            MessageSegment::Reply { id: "42".into() },
+            MessageSegment::Image {
+                image_info: ImageInfo {
+                    file: "abc.png".into(),
+                    url: Some("https://example.com/abc.png".into()),
+                    summary: None,
+                    sub_type: None,
+                    file_size: None,
+                    key: None,
+                    emoji_id: None,
+                    emoji_package_id: None,
+                },
+            },
+            MessageSegment::Text { text: String::new() },
+        ]);
+        assert_eq!(
+            to_onebot_message(&content),
+            json!([
+                { "type": "reply", "data": { "id": "42" } },
+                { "type": "at", "data": { "qq": "10001" } },
+                { "type": "text", "data": { "text": " 你好" } },
+                { "type": "image", "data": { "file": "https://example.com/abc.png" } },
+            ])
+        );
+
+        let image = MessageContent::Image(FileAttachment {
+            name: "chart.png".into(),
+            content: vec![1, 2, 3],
+            mime_type: Some("image/png".into()),
+        });
+        assert_eq!(
+            to_onebot_message(&image),
+            json!([{ "type": "image", "data": { "file": "base64://AQID" } }])
+        );
+        assert!(to_onebot_segments(&MessageContent::Text(String::new())).is_empty());
+    }
+}
@@ -1,8 +1,12 @@
-use super::cmd::CommandReplyStrategy;
+use super::cmd::{CommandReplyStrategy, is_command};
 use super::llm::SimpleLlmReplyStrategy;
-use super::{MessageContent, MessageContext, RelyStrategy, ReplyError, Env};
+use super::{Delivery, MessageContent, MessageContext, RelyStrategy, Reply, ReplyError, Env};
 use crate::{BOT_CACHE, StrategeType};
-use crate::service::group_config_service::GROUP_CACHE;
+use crate::content_filter::Stage;
+use crate::permission::Role;
+use crate::service::{
+    content_filter_service, faq_service, group_command_policy_service, group_config_service::GROUP_CACHE,
+};
 
 #[derive(Clone)]
 pub struct ReplyManager {
@@ -18,7 +22,8 @@ impl ReplyManager {
         }
     }
 
-    pub async fn reply(&self, ctx: &MessageContext) -> Result<MessageContent, ReplyError> {
+    /// 命令的回复可能改为私聊发送者或不回复，其余回复都在原处发送
+    pub async fn reply(&self, ctx: &MessageContext) -> Result<Reply, ReplyError> {
         // 根据环境获取有效配置（群组优先或用户配置）
         let strategy = match &ctx.env {
             Env::Group { group_id } => {
@@ -37,26 +42,53 @@ impl ReplyManager {
         };
 
         match strategy {
-            StrategeType::CmdStrategy => self.cmd_strategy.reply(ctx).await,
+            StrategeType::CmdStrategy => Ok(self.cmd_strategy.execute(ctx).await?.into()),
             StrategeType::LlmStrategy => {
                 // 对于LLM策略，如果消息不是以命令前缀开头，则使用LLM回复
-                if let MessageContent::Text(text) = &ctx.message {
-                    if text.starts_with(&crate::config::APPCONFIG.cmd_suffix) {
-                        // 仍然是命令，使用命令策略处理
-                        self.cmd_strategy.reply(ctx).await
-                    } else {
-                        // 普通聊天消息，使用LLM策略
-                        self.llm_strategy.reply(ctx).await
-                    }
+                let group_id = match &ctx.env {
+                    Env::Group { group_id } => Some(*group_id),
+                    Env::Private => None,
+                };
+                if is_command(&ctx.message, group_id).await {
+                    // 仍然是命令，使用命令策略处理
+                    Ok(self.cmd_strategy.execute(ctx).await?.into())
+                } else if let Some(group_id) = group_id
+                    && !group_command_policy_service::llm_allowed(group_id, Role::of(ctx.sender_id, ctx.sender_role).await).await
+                {
+                    // 群内停用了大模型聊天，不回复
+                    Ok(Reply {
+                        content: MessageContent::Text(String::new()),
+                        delivery: Delivery::Silent,
+                    })
                 } else {
-                    // 非文本消息，尝试LLM策略
-                    self.llm_strategy.reply(ctx).await
+                    // 普通聊天消息或非文本消息，先匹配常见问题，未命中再使用LLM策略
+                    Ok(Reply::in_place(self.faq_or_llm(ctx).await?))
                 }
             }
         }
     }
 }
 
+impl ReplyManager {
+    /// 常见问题直接回复，不调用大模型。用户消息先经过内容审核，大模型回复在记录前审核
+    async fn faq_or_llm(&self, ctx: &MessageContext) -> Result<MessageContent, ReplyError> {
+        let group_id = match &ctx.env {
+            Env::Group { group_id } => Some(*group_id),
+            Env::Private => None,
+        };
+        let text = ctx.message.get_text();
+        if content_filter_service::screen(Stage::Input, &text, ctx.sender_id, group_id).await.is_some() {
+            return Ok(MessageContent::Text(content_filter_service::refusal()));
+        }
+        if ctx.message.has_text()
+            && let Some(answer) = faq_service::auto_answer(&text, group_id).await
+        {
+            return Ok(MessageContent::Text(answer));
+        }
+        self.llm_strategy.reply(ctx).await
+    }
+}
+
 impl Default for ReplyManager {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,171 @@
+use std::{io::Cursor, sync::Arc};
+
+use base64::{Engine as _, engine::general_purpose};
+use image::codecs::jpeg::JpegEncoder;
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+
+use super::ReplyError;
+use super::llm::ImageData;
+use crate::config::APPCONFIG;
+
+/// 重新编码时使用的 JPEG 质量
+const JPEG_QUALITY: u8 = 85;
+
+/// 图片缓存，键为 OneBot 的 key 或 file（QQ 的 file 本身就是内容哈希），值为 data URL
+pub static IMAGE_CACHE: Lazy<Cache<String, Arc<String>>> = Lazy::new(|| {
+    let vision = &APPCONFIG.llm.vision;
+    Cache::builder()
+        .weigher(|_key: &String, value: &Arc<String>| value.len().try_into().unwrap_or(u32::MAX))
+        .max_capacity(vision.cache_capacity)
+        .time_to_live(vision.cache_ttl)
+        .build()
+});
+
+/// 根据文件头识别图片格式，不依赖 URL 后缀
+pub fn sniff_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if bytes.starts_with(b"BM") {
+        Some("image/bmp")
+    } else {
+        None
+    }
+}
+
+/// 图片超过大小或边长限制时缩小并重新编码为 JPEG，返回 (数据, MIME)
+pub fn fit_image(bytes: Vec<u8>, max_bytes: usize, max_dimension: u32) -> Result<(Vec<u8>, &'static str), ReplyError> {
+    let mime = sniff_mime(&bytes).ok_or_else(|| ReplyError("无法识别的图片格式".to_string()))?;
+    let img = image::load_from_memory(&bytes)
+        .map_err(|e| ReplyError(format!("Failed to decode image: {}", e)))?;
+
+    // GIF 需要转成静态图片，模型接口一般不接受动图
+    if bytes.len() <= max_bytes
+        && img.width() <= max_dimension
+        && img.height() <= max_dimension
+        && mime != "image/gif"
+    {
+        return Ok((bytes, mime));
+    }
+
+    // 每次缩小到 3/4，直到满足大小限制
+    let mut dimension = max_dimension.min(img.width().max(img.height()));
+    loop {
+        let rgb = img.thumbnail(dimension, dimension).to_rgb8();
+        let mut buf = Vec::new();
+        JpegEncoder::new_with_quality(&mut Cursor::new(&mut buf), JPEG_QUALITY)
+            .encode_image(&rgb)
+            .map_err(|e| ReplyError(format!("Failed to encode image: {}", e)))?;
+        if buf.len() <= max_bytes || dimension <= 256 {
+            return Ok((buf, "image/jpeg"));
+        }
+        dimension = dimension * 3 / 4;
+    }
+}
+
+/// 加载图片并转换为 data URL，命中缓存时不再下载
+pub async fn load_image(client: &Client, image: &ImageData) -> Result<Arc<String>, ReplyError> {
+    let cache_key = image.key.clone().unwrap_or_else(|| image.file.clone());
+    if let Some(cached) = IMAGE_CACHE.get(&cache_key).await {
+        return Ok(cached);
+    }
+
+    let url = image
+        .url
+        .as_ref()
+        .ok_or_else(|| ReplyError(format!("图片 {} 没有可下载的地址", image.file)))?;
+    let vision = &APPCONFIG.llm.vision;
+    let bytes = download(client, url, vision.max_download_bytes).await?;
+
+    let (bytes, mime) = tokio::task::spawn_blocking({
+        let (max_bytes, max_dimension) = (vision.max_image_bytes, vision.max_dimension);
+        move || fit_image(bytes, max_bytes, max_dimension)
+    })
+    .await
+    .map_err(|e| ReplyError(format!("Image task failed: {}", e)))??;
+
+    let data_url = Arc::new(format!(
+        "data:{};base64,{}",
+        mime,
+        general_purpose::STANDARD.encode(&bytes)
+    ));
+    IMAGE_CACHE.insert(cache_key, data_url.clone()).await;
+    Ok(data_url)
+}
+
+/// 下载图片，超过 max_bytes 时中止，避免把超大文件读进内存
+async fn download(client: &Client, url: &str, max_bytes: usize) -> Result<Vec<u8>, ReplyError> {
+    let mut response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| ReplyError(format!("Failed to download image: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(ReplyError(format!(
+            "Failed to download image, status: {}",
+            response.status()
+        )));
+    }
+
+    let too_large = || ReplyError(format!("图片超过下载上限 {} 字节", max_bytes));
+    if response.content_length().is_some_and(|len| len > max_bytes as u64) {
+        return Err(too_large());
+    }
+
+    // Content-Length 可能缺失或不准确，边读边检查
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| ReplyError(format!("Failed to read image bytes: {}", e)))?
+    {
+        if bytes.len() + chunk.len() > max_bytes {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbImage};
+
+    #[test]
+    fn test_sniff_and_fit() {
+        let mut png = Vec::new();
+        RgbImage::new(800, 400)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        assert_eq!(sniff_mime(&png), Some("image/png"));
+        assert_eq!(sniff_mime(b"GIF89a...."), Some("image/gif"));
+        assert_eq!(sniff_mime(b"hello"), None);
+
+        // BMP 能识别也能解码
+        let mut bmp = Vec::new();
+        RgbImage::new(16, 16)
+            .write_to(&mut Cursor::new(&mut bmp), ImageFormat::Bmp)
+            .unwrap();
+        assert_eq!(sniff_mime(&bmp), Some("image/bmp"));
+        assert!(fit_image(bmp, usize::MAX, 1024).is_ok());
+
+        // 满足限制时原样返回
+        let (same, mime) = fit_image(png.clone(), usize::MAX, 1024).unwrap();
+        assert_eq!((same.len(), mime), (png.len(), "image/png"));
+
+        // 超过边长时缩小并转为 JPEG
+        let (small, mime) = fit_image(png, usize::MAX, 200).unwrap();
+        assert_eq!(mime, "image/jpeg");
+        let img = image::load_from_memory(&small).unwrap();
+        assert_eq!((img.width(), img.height()), (200, 100));
+    }
+}
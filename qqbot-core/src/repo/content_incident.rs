@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::content_incident::{ActiveModel, Column, Entity, Model};
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryOrder, Set,
+};
+
+/// 一次内容拦截记录
+#[derive(Debug, Clone)]
+pub struct NewContentIncident {
+    pub user_id: i64,
+    pub group_id: Option<i64>,
+    pub stage: &'static str,
+    pub source: &'static str,
+    pub matched: String,
+    pub content: String,
+}
+
+#[async_trait]
+pub trait ContentIncidentRepository {
+    async fn create(&self, incident: NewContentIncident) -> Result<Model, DbErr>;
+    async fn list(&self, page: u64, limit: u64) -> Result<(Vec<Model>, u64), DbErr>;
+}
+
+pub struct ContentIncidentRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl ContentIncidentRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ContentIncidentRepository for ContentIncidentRepo {
+    async fn create(&self, incident: NewContentIncident) -> Result<Model, DbErr> {
+        let active = ActiveModel {
+            user_id: Set(incident.user_id),
+            group_id: Set(incident.group_id),
+            stage: Set(incident.stage.to_string()),
+            source: Set(incident.source.to_string()),
+            matched: Set(incident.matched),
+            content: Set(incident.content),
+            ..Default::default()
+        };
+        active.insert(self.db.as_ref()).await
+    }
+
+    async fn list(&self, page: u64, limit: u64) -> Result<(Vec<Model>, u64), DbErr> {
+        let paginator = Entity::find()
+            .order_by_desc(Column::Id)
+            .paginate(self.db.as_ref(), limit);
+        let total = paginator.num_items().await?;
+        let items = paginator.fetch_page(page.saturating_sub(1)).await?;
+        Ok((items, total))
+    }
+}
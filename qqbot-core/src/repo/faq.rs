@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::faq::{ActiveModel, Column, Entity, Model};
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+    sea_query::Expr,
+};
+
+/// 新建或修改 FAQ 时的字段
+#[derive(Debug, Clone)]
+pub struct FaqFields {
+    pub question: String,
+    pub answer: String,
+    pub match_type: String,
+    pub pattern: String,
+    pub group_id: Option<i64>,
+    pub priority: i32,
+    pub enabled: bool,
+}
+
+#[async_trait]
+pub trait FaqRepository {
+    async fn list(&self) -> Result<Vec<Model>, DbErr>;
+    async fn list_enabled(&self) -> Result<Vec<Model>, DbErr>;
+    async fn create(&self, fields: FaqFields) -> Result<Model, DbErr>;
+    async fn update(&self, id: i64, fields: FaqFields) -> Result<Model, DbErr>;
+    async fn delete(&self, id: i64) -> Result<bool, DbErr>;
+    async fn increment_hit(&self, id: i64) -> Result<(), DbErr>;
+}
+
+pub struct FaqRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl FaqRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+fn apply(active: &mut ActiveModel, fields: FaqFields) {
+    active.question = Set(fields.question);
+    active.answer = Set(fields.answer);
+    active.match_type = Set(fields.match_type);
+    active.pattern = Set(fields.pattern);
+    active.group_id = Set(fields.group_id);
+    active.priority = Set(fields.priority);
+    active.enabled = Set(fields.enabled);
+}
+
+#[async_trait]
+impl FaqRepository for FaqRepo {
+    async fn list(&self) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .order_by_desc(Column::Priority)
+            .order_by_asc(Column::Id)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn list_enabled(&self) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::Enabled.eq(true))
+            .order_by_desc(Column::Priority)
+            .order_by_asc(Column::Id)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn create(&self, fields: FaqFields) -> Result<Model, DbErr> {
+        let mut active = <ActiveModel as Default>::default();
+        apply(&mut active, fields);
+        active.hit_count = Set(0);
+        active.insert(self.db.as_ref()).await
+    }
+
+    async fn update(&self, id: i64, fields: FaqFields) -> Result<Model, DbErr> {
+        let existing = Entity::find_by_id(id)
+            .one(self.db.as_ref())
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("faq {}", id)))?;
+        let mut active: ActiveModel = existing.into();
+        apply(&mut active, fields);
+        active.update(self.db.as_ref()).await
+    }
+
+    async fn delete(&self, id: i64) -> Result<bool, DbErr> {
+        let result = Entity::delete_by_id(id).exec(self.db.as_ref()).await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn increment_hit(&self, id: i64) -> Result<(), DbErr> {
+        Entity::update_many()
+            .col_expr(Column::HitCount, Expr::col(Column::HitCount).add(1))
+            .filter(Column::Id.eq(id))
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+}
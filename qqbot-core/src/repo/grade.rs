@@ -1,9 +1,12 @@
 use std::sync::Arc;
 
 use super::{DbErr, GradeRepository};
-use crate::models::grade::{ActiveModel, Column, Entity as GradeEntity, Model as GradeModel};
+use crate::models::{
+    grade::{ActiveModel, Column, Entity as GradeEntity, Model as GradeModel},
+    student::{self, Entity as StudentEntity, Model as StudentModel},
+};
 use async_trait::async_trait;
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set};
 
 pub struct GradeRepo {
     db: Arc<DatabaseConnection>,
@@ -24,6 +27,30 @@ impl GradeRepository for GradeRepo {
             .await
     }
 
+    async fn query_exam_grades(
+        &self,
+        exam_name: &str,
+        course_id: Option<i32>,
+    ) -> Result<Vec<(GradeModel, Option<StudentModel>)>, DbErr> {
+        let mut query = GradeEntity::find().filter(Column::ExamName.eq(exam_name));
+        if let Some(course_id) = course_id {
+            query = query.filter(Column::CourseId.eq(course_id));
+        }
+        query
+            .order_by_asc(Column::CourseId)
+            .find_also_related(StudentEntity)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn query_group_grades(&self, group_id: i64) -> Result<Vec<GradeModel>, DbErr> {
+        GradeEntity::find()
+            .inner_join(StudentEntity)
+            .filter(student::Column::GroupId.eq(group_id))
+            .all(self.db.as_ref())
+            .await
+    }
+
     async fn add_grade(
         &self,
         course_id: i32,
@@ -48,6 +75,7 @@ impl GradeRepository for GradeRepo {
 }
 
 #[tokio::test]
+#[ignore = "needs a local MySQL database"]
 async fn query_grade_test() -> Result<(), Box<dyn std::error::Error>> {
     let db: DatabaseConnection =
         sea_orm::Database::connect("mysql://root:@localhost/diesel_demo").await?;
@@ -0,0 +1,166 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::grade::Model as GradeModel;
+use crate::models::grade_notification::{
+    ActiveModel, Column, Entity, Model, STATUS_FAILED, STATUS_PENDING, STATUS_SENT,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+
+#[async_trait]
+pub trait GradeNotificationRepository {
+    async fn enqueue(&self, grade: &GradeModel, qq: i64) -> Result<Model, DbErr>;
+    async fn find_pending(&self, limit: u64) -> Result<Vec<Model>, DbErr>;
+    async fn mark_sent(&self, id: i64) -> Result<(), DbErr>;
+    async fn mark_failed(&self, id: i64, error: String, max_attempts: i32) -> Result<(), DbErr>;
+    async fn reset(&self, id: i64) -> Result<Model, DbErr>;
+    async fn list(
+        &self,
+        status: Option<String>,
+        page: u64,
+        limit: u64,
+    ) -> Result<(Vec<Model>, u64), DbErr>;
+}
+
+pub struct GradeNotificationRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl GradeNotificationRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+
+    async fn find(&self, id: i64) -> Result<Model, DbErr> {
+        Entity::find_by_id(id)
+            .one(self.db.as_ref())
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("grade_notification {}", id)))
+    }
+}
+
+#[async_trait]
+impl GradeNotificationRepository for GradeNotificationRepo {
+    async fn enqueue(&self, grade: &GradeModel, qq: i64) -> Result<Model, DbErr> {
+        let notification = ActiveModel {
+            grade_id: Set(grade.id),
+            student_id: Set(grade.student_id),
+            qq_number: Set(qq),
+            student_name: Set(grade.student_name.clone()),
+            exam_name: Set(grade.exam_name.clone()),
+            score: Set(grade.score),
+            status: Set(STATUS_PENDING.to_string()),
+            attempts: Set(0),
+            ..Default::default()
+        };
+
+        notification.insert(self.db.as_ref()).await
+    }
+
+    async fn find_pending(&self, limit: u64) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::Status.eq(STATUS_PENDING))
+            .order_by_asc(Column::Id)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn mark_sent(&self, id: i64) -> Result<(), DbErr> {
+        sent(self.find(id).await?).update(self.db.as_ref()).await?;
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: i64, error: String, max_attempts: i32) -> Result<(), DbErr> {
+        failed(self.find(id).await?, error, max_attempts)
+            .update(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn reset(&self, id: i64) -> Result<Model, DbErr> {
+        let mut active: ActiveModel = self.find(id).await?.into();
+        active.status = Set(STATUS_PENDING.to_string());
+        active.attempts = Set(0);
+        active.last_error = Set(None);
+        active.update(self.db.as_ref()).await
+    }
+
+    async fn list(
+        &self,
+        status: Option<String>,
+        page: u64,
+        limit: u64,
+    ) -> Result<(Vec<Model>, u64), DbErr> {
+        let mut query = Entity::find().order_by_desc(Column::Id);
+        if let Some(status) = status {
+            query = query.filter(Column::Status.eq(status));
+        }
+
+        let paginator = query.paginate(self.db.as_ref(), limit);
+        let total = paginator.num_items().await?;
+        let items = paginator.fetch_page(page.saturating_sub(1)).await?;
+        Ok((items, total))
+    }
+}
+
+
+/// 投递成功后的状态
+fn sent(model: Model) -> ActiveModel {
+    let mut active: ActiveModel = model.into();
+    let attempts = active.attempts.take().unwrap_or_default();
+    active.attempts = Set(attempts + 1);
+    active.status = Set(STATUS_SENT.to_string());
+    active.last_error = Set(None);
+    active.sent_at = Set(Some(Utc::now().into()));
+    active
+}
+
+/// 投递失败后的状态：未达到最大尝试次数时保持 pending，等待下一批重试
+fn failed(model: Model, error: String, max_attempts: i32) -> ActiveModel {
+    let mut active: ActiveModel = model.into();
+    let attempts = active.attempts.take().unwrap_or_default() + 1;
+    active.attempts = Set(attempts);
+    if attempts >= max_attempts {
+        active.status = Set(STATUS_FAILED.to_string());
+    }
+    active.last_error = Set(Some(error));
+    active
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use sea_orm::ActiveValue;
+
+    #[test]
+    fn test_failed_retries_until_max_attempts() {
+        // 未达到上限时保持 pending，等待下一批重试
+        let active = failed(fixtures::notification(0), "timeout".to_string(), 3);
+        assert_eq!(active.attempts, Set(1));
+        assert_eq!(active.status, ActiveValue::Unchanged(STATUS_PENDING.to_string()));
+        assert_eq!(active.last_error, Set(Some("timeout".to_string())));
+
+        // 第 max_attempts 次失败后放弃，不再被 find_pending 取出
+        let active = failed(fixtures::notification(2), "timeout".to_string(), 3);
+        assert_eq!(active.attempts, Set(3));
+        assert_eq!(active.status, Set(STATUS_FAILED.to_string()));
+    }
+
+    #[test]
+    fn test_sent_clears_error() {
+        let mut notification = fixtures::notification(1);
+        notification.last_error = Some("timeout".to_string());
+        let active = sent(notification);
+        assert_eq!(active.attempts, Set(2));
+        assert_eq!(active.status, Set(STATUS_SENT.to_string()));
+        assert_eq!(active.last_error, Set(None));
+        assert!(matches!(active.sent_at, ActiveValue::Set(Some(_))));
+    }
+}
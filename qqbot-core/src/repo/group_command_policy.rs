@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::group_command_policy::{ActiveModel, Column, Entity, Model};
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    QueryOrder, Set,
+};
+
+#[async_trait]
+pub trait GroupCommandPolicyRepository {
+    async fn list_by_group(&self, group_id: i64) -> Result<Vec<Model>, DbErr>;
+    /// 保存群内某个命令的策略，不存在时新建
+    async fn save(
+        &self,
+        group_id: i64,
+        command: &str,
+        enabled: bool,
+        min_role: Option<String>,
+        updated_by: i64,
+    ) -> Result<Model, DbErr>;
+    /// 删除策略，恢复默认，返回是否删除成功
+    async fn delete(&self, group_id: i64, command: &str) -> Result<bool, DbErr>;
+}
+
+pub struct GroupCommandPolicyRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl GroupCommandPolicyRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl GroupCommandPolicyRepository for GroupCommandPolicyRepo {
+    async fn list_by_group(&self, group_id: i64) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::GroupId.eq(group_id))
+            .order_by_asc(Column::Command)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn save(
+        &self,
+        group_id: i64,
+        command: &str,
+        enabled: bool,
+        min_role: Option<String>,
+        updated_by: i64,
+    ) -> Result<Model, DbErr> {
+        let existing = Entity::find()
+            .filter(Column::GroupId.eq(group_id))
+            .filter(Column::Command.eq(command))
+            .one(self.db.as_ref())
+            .await?;
+        match existing {
+            Some(existing) => {
+                let mut active = existing.into_active_model();
+                active.enabled = Set(enabled);
+                active.min_role = Set(min_role);
+                active.updated_by = Set(Some(updated_by));
+                active.update(self.db.as_ref()).await
+            }
+            None => {
+                let active = ActiveModel {
+                    group_id: Set(group_id),
+                    command: Set(command.to_string()),
+                    enabled: Set(enabled),
+                    min_role: Set(min_role),
+                    updated_by: Set(Some(updated_by)),
+                    ..Default::default()
+                };
+                active.insert(self.db.as_ref()).await
+            }
+        }
+    }
+
+    async fn delete(&self, group_id: i64, command: &str) -> Result<bool, DbErr> {
+        let result = Entity::delete_many()
+            .filter(Column::GroupId.eq(group_id))
+            .filter(Column::Command.eq(command))
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -39,8 +39,10 @@ impl GroupConfigRepository for GroupConfigRepo {
         match existing {
             Some(_) => {
                 // 记录存在，执行更新
-                let mut active_model: crate::models::group_config::ActiveModel = Default::default();
-                active_model.group_id = Set(group_id);
+                let mut active_model = crate::models::group_config::ActiveModel {
+                    group_id: Set(group_id),
+                    ..Default::default()
+                };
                 
                 let strategy_str = match group_data.stratege {
                     crate::StrategeType::CmdStrategy => "cmd_strategy",
@@ -54,6 +56,9 @@ impl GroupConfigRepository for GroupConfigRepo {
                     Some(group_data.model.clone()) 
                 });
                 active_model.custom_prompt = Set(group_data.custom_prompt.clone());
+                active_model.voice_reply = Set(group_data.voice_reply);
+                active_model.cmd_prefix = Set(group_data.cmd_prefix.clone());
+                active_model.cmd_aliases = Set(group_data.aliases_json());
                 
                 active_model.update(&self.db).await?;
             }
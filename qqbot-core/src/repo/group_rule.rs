@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::group_rule::{ActiveModel, Column, Entity, Model};
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+
+/// 新建或修改群规则时的字段
+#[derive(Debug, Clone)]
+pub struct GroupRuleFields {
+    pub group_id: i64,
+    pub name: String,
+    pub trigger_type: String,
+    pub trigger_value: String,
+    pub action_type: String,
+    pub action_value: String,
+    pub priority: i32,
+    pub stop_processing: bool,
+    pub enabled: bool,
+}
+
+#[async_trait]
+pub trait GroupRuleRepository {
+    /// group_id 为 None 时返回所有群的规则
+    async fn list(&self, group_id: Option<i64>) -> Result<Vec<Model>, DbErr>;
+    async fn list_enabled(&self, group_id: i64) -> Result<Vec<Model>, DbErr>;
+    async fn create(&self, fields: GroupRuleFields) -> Result<Model, DbErr>;
+    async fn update(&self, id: i64, fields: GroupRuleFields) -> Result<Model, DbErr>;
+    /// 删除规则，返回被删除规则所属的群
+    async fn delete(&self, id: i64) -> Result<Option<i64>, DbErr>;
+}
+
+pub struct GroupRuleRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl GroupRuleRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+fn apply(active: &mut ActiveModel, fields: GroupRuleFields) {
+    active.group_id = Set(fields.group_id);
+    active.name = Set(fields.name);
+    active.trigger_type = Set(fields.trigger_type);
+    active.trigger_value = Set(fields.trigger_value);
+    active.action_type = Set(fields.action_type);
+    active.action_value = Set(fields.action_value);
+    active.priority = Set(fields.priority);
+    active.stop_processing = Set(fields.stop_processing);
+    active.enabled = Set(fields.enabled);
+}
+
+#[async_trait]
+impl GroupRuleRepository for GroupRuleRepo {
+    async fn list(&self, group_id: Option<i64>) -> Result<Vec<Model>, DbErr> {
+        let mut query = Entity::find();
+        if let Some(group_id) = group_id {
+            query = query.filter(Column::GroupId.eq(group_id));
+        }
+        query
+            .order_by_asc(Column::GroupId)
+            .order_by_desc(Column::Priority)
+            .order_by_asc(Column::Id)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn list_enabled(&self, group_id: i64) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::GroupId.eq(group_id))
+            .filter(Column::Enabled.eq(true))
+            .order_by_desc(Column::Priority)
+            .order_by_asc(Column::Id)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn create(&self, fields: GroupRuleFields) -> Result<Model, DbErr> {
+        let mut active = <ActiveModel as Default>::default();
+        apply(&mut active, fields);
+        active.insert(self.db.as_ref()).await
+    }
+
+    async fn update(&self, id: i64, fields: GroupRuleFields) -> Result<Model, DbErr> {
+        let existing = Entity::find_by_id(id)
+            .one(self.db.as_ref())
+            .await?
+            .ok_or_else(|| DbErr::RecordNotFound(format!("group_rule {}", id)))?;
+        let mut active: ActiveModel = existing.into();
+        apply(&mut active, fields);
+        active.update(self.db.as_ref()).await
+    }
+
+    async fn delete(&self, id: i64) -> Result<Option<i64>, DbErr> {
+        let Some(existing) = Entity::find_by_id(id).one(self.db.as_ref()).await? else {
+            return Ok(None);
+        };
+        Entity::delete_by_id(id).exec(self.db.as_ref()).await?;
+        Ok(Some(existing.group_id))
+    }
+}
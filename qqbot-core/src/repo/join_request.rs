@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::join_request::{
+    ActiveModel, Column, Entity, Model, STATUS_APPROVING, STATUS_PENDING, STATUS_REJECTING,
+};
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, sea_query::Expr,
+};
+
+/// 收到的好友或加群申请
+#[derive(Debug, Clone)]
+pub struct NewJoinRequest {
+    pub request_type: String,
+    pub sub_type: String,
+    pub flag: String,
+    pub user_id: i64,
+    pub group_id: Option<i64>,
+    pub comment: String,
+    pub status: &'static str,
+    pub reason: String,
+}
+
+#[async_trait]
+pub trait JoinRequestRepository {
+    async fn create(&self, request: NewJoinRequest) -> Result<Model, DbErr>;
+    async fn find(&self, id: i64) -> Result<Option<Model>, DbErr>;
+    /// 审批待处理的申请，申请已被处理时返回 false
+    async fn decide(
+        &self,
+        id: i64,
+        status: &'static str,
+        reason: String,
+        handled_by: Option<i64>,
+    ) -> Result<bool, DbErr>;
+    /// 已审批、等待机器人执行的申请
+    async fn find_decided(&self, limit: u64) -> Result<Vec<Model>, DbErr>;
+    async fn finish(&self, id: i64, status: &'static str, error: Option<String>) -> Result<(), DbErr>;
+    async fn list(
+        &self,
+        status: Option<String>,
+        page: u64,
+        limit: u64,
+    ) -> Result<(Vec<Model>, u64), DbErr>;
+}
+
+pub struct JoinRequestRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl JoinRequestRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl JoinRequestRepository for JoinRequestRepo {
+    async fn create(&self, request: NewJoinRequest) -> Result<Model, DbErr> {
+        let active = ActiveModel {
+            request_type: Set(request.request_type),
+            sub_type: Set(request.sub_type),
+            flag: Set(request.flag),
+            user_id: Set(request.user_id),
+            group_id: Set(request.group_id),
+            comment: Set(request.comment),
+            status: Set(request.status.to_string()),
+            reason: Set(request.reason),
+            ..Default::default()
+        };
+        active.insert(self.db.as_ref()).await
+    }
+
+    async fn find(&self, id: i64) -> Result<Option<Model>, DbErr> {
+        Entity::find_by_id(id).one(self.db.as_ref()).await
+    }
+
+    async fn decide(
+        &self,
+        id: i64,
+        status: &'static str,
+        reason: String,
+        handled_by: Option<i64>,
+    ) -> Result<bool, DbErr> {
+        // 只更新仍在等待审批的申请，避免管理员同时审批时重复处理
+        let result = Entity::update_many()
+            .col_expr(Column::Status, Expr::value(status))
+            .col_expr(Column::Reason, Expr::value(reason))
+            .col_expr(Column::HandledBy, Expr::value(handled_by))
+            .filter(Column::Id.eq(id))
+            .filter(Column::Status.eq(STATUS_PENDING))
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn find_decided(&self, limit: u64) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::Status.is_in([STATUS_APPROVING, STATUS_REJECTING]))
+            .order_by_asc(Column::Id)
+            .limit(limit)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn finish(&self, id: i64, status: &'static str, error: Option<String>) -> Result<(), DbErr> {
+        Entity::update_many()
+            .col_expr(Column::Status, Expr::value(status))
+            .col_expr(Column::Error, Expr::value(error))
+            .filter(Column::Id.eq(id))
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        status: Option<String>,
+        page: u64,
+        limit: u64,
+    ) -> Result<(Vec<Model>, u64), DbErr> {
+        let mut query = Entity::find().order_by_desc(Column::Id);
+        if let Some(status) = status {
+            query = query.filter(Column::Status.eq(status));
+        }
+
+        let paginator = query.paginate(self.db.as_ref(), limit);
+        let total = paginator.num_items().await?;
+        let items = paginator.fetch_page(page.saturating_sub(1)).await?;
+        Ok((items, total))
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::{
+    knowledge_chunk::{
+        ActiveModel as ChunkActiveModel, Column as ChunkColumn, Entity as ChunkEntity,
+        Model as ChunkModel,
+    },
+    knowledge_document::{
+        ActiveModel as DocumentActiveModel, Column as DocumentColumn, Entity as DocumentEntity,
+        Model as DocumentModel,
+    },
+};
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+    TransactionTrait,
+};
+
+#[async_trait]
+pub trait KnowledgeRepository {
+    /// 保存文档及其片段，chunks 为 (内容, 向量) 列表
+    async fn create_document(
+        &self,
+        title: String,
+        filename: String,
+        format: &str,
+        chunks: Vec<(String, Vec<u8>)>,
+    ) -> Result<DocumentModel, DbErr>;
+    async fn list_documents(&self) -> Result<Vec<DocumentModel>, DbErr>;
+    async fn delete_document(&self, id: i64) -> Result<bool, DbErr>;
+    async fn all_chunks(&self) -> Result<Vec<ChunkModel>, DbErr>;
+}
+
+pub struct KnowledgeRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl KnowledgeRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl KnowledgeRepository for KnowledgeRepo {
+    async fn create_document(
+        &self,
+        title: String,
+        filename: String,
+        format: &str,
+        chunks: Vec<(String, Vec<u8>)>,
+    ) -> Result<DocumentModel, DbErr> {
+        let txn = self.db.begin().await?;
+        let document = DocumentActiveModel {
+            title: Set(title),
+            filename: Set(filename),
+            format: Set(format.to_string()),
+            chunk_count: Set(chunks.len() as i32),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        let chunks = chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, (content, embedding))| ChunkActiveModel {
+                document_id: Set(document.id),
+                chunk_index: Set(index as i32),
+                content: Set(content),
+                embedding: Set(embedding),
+                ..Default::default()
+            });
+        ChunkEntity::insert_many(chunks)
+            .on_empty_do_nothing()
+            .exec(&txn)
+            .await?;
+        txn.commit().await?;
+        Ok(document)
+    }
+
+    async fn list_documents(&self) -> Result<Vec<DocumentModel>, DbErr> {
+        DocumentEntity::find()
+            .order_by_desc(DocumentColumn::Id)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn delete_document(&self, id: i64) -> Result<bool, DbErr> {
+        let txn = self.db.begin().await?;
+        ChunkEntity::delete_many()
+            .filter(ChunkColumn::DocumentId.eq(id))
+            .exec(&txn)
+            .await?;
+        let result = DocumentEntity::delete_by_id(id).exec(&txn).await?;
+        txn.commit().await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn all_chunks(&self) -> Result<Vec<ChunkModel>, DbErr> {
+        ChunkEntity::find()
+            .order_by_asc(ChunkColumn::Id)
+            .all(self.db.as_ref())
+            .await
+    }
+}
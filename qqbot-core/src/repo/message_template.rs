@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::message_template::{ActiveModel, Entity, Model};
+use async_trait::async_trait;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+
+#[async_trait]
+pub trait MessageTemplateRepository {
+    async fn find_by_key(&self, key: &str) -> Result<Option<Model>, DbErr>;
+    async fn list(&self) -> Result<Vec<Model>, DbErr>;
+    async fn upsert(&self, key: &str, content: &str) -> Result<(), DbErr>;
+    async fn delete(&self, key: &str) -> Result<(), DbErr>;
+}
+
+pub struct MessageTemplateRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl MessageTemplateRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl MessageTemplateRepository for MessageTemplateRepo {
+    async fn find_by_key(&self, key: &str) -> Result<Option<Model>, DbErr> {
+        Entity::find_by_id(key.to_string())
+            .one(self.db.as_ref())
+            .await
+    }
+
+    async fn list(&self) -> Result<Vec<Model>, DbErr> {
+        Entity::find().all(self.db.as_ref()).await
+    }
+
+    async fn upsert(&self, key: &str, content: &str) -> Result<(), DbErr> {
+        match self.find_by_key(key).await? {
+            Some(existing) => {
+                let mut active: ActiveModel = existing.into();
+                active.content = Set(content.to_string());
+                active.update(self.db.as_ref()).await?;
+            }
+            None => {
+                let active = ActiveModel {
+                    template_key: Set(key.to_string()),
+                    content: Set(content.to_string()),
+                    ..Default::default()
+                };
+                active.insert(self.db.as_ref()).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), DbErr> {
+        Entity::delete_by_id(key.to_string())
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(())
+    }
+}
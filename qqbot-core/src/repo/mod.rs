@@ -11,6 +11,14 @@ pub trait StudentRepository {
 #[async_trait]
 pub trait GradeRepository {
     async fn query_grades(&self, student_id: i64) -> Result<Vec<GradeModel>, DbErr>;
+    /// 某次考试的全部成绩及对应的学生，可按课程号筛选
+    async fn query_exam_grades(
+        &self,
+        exam_name: &str,
+        course_id: Option<i32>,
+    ) -> Result<Vec<(GradeModel, Option<StudentModel>)>, DbErr>;
+    /// 某个群内所有学生的成绩，用于计算班级平均分
+    async fn query_group_grades(&self, group_id: i64) -> Result<Vec<GradeModel>, DbErr>;
     async fn add_grade(
         &self,
         course_id: i32,
@@ -21,7 +29,19 @@ pub trait GradeRepository {
     ) -> Result<(), DbErr>;
 }
 
+pub mod content_incident;
+pub mod faq;
 pub mod grade;
+pub mod grade_notification;
+pub mod group_command_policy;
 pub mod group_config;
+pub mod group_rule;
+pub mod join_request;
+pub mod knowledge;
+pub mod message_template;
+pub mod moderation_log;
+pub mod onboarding;
+pub mod permission_grant;
 pub mod student;
 pub mod user_config;
+pub mod user_memory;
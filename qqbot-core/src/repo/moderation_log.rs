@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::moderation_log::{ActiveModel, Column, Entity, Model};
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, Set,
+};
+
+/// 一条群管理操作记录
+#[derive(Debug, Clone)]
+pub struct NewModerationLog {
+    pub group_id: i64,
+    pub operator_id: i64,
+    pub action: &'static str,
+    pub target_id: Option<i64>,
+    pub detail: String,
+    pub error: Option<String>,
+}
+
+#[async_trait]
+pub trait ModerationLogRepository {
+    async fn create(&self, log: NewModerationLog) -> Result<Model, DbErr>;
+    async fn list(
+        &self,
+        group_id: Option<i64>,
+        page: u64,
+        limit: u64,
+    ) -> Result<(Vec<Model>, u64), DbErr>;
+}
+
+pub struct ModerationLogRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl ModerationLogRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ModerationLogRepository for ModerationLogRepo {
+    async fn create(&self, log: NewModerationLog) -> Result<Model, DbErr> {
+        let active = ActiveModel {
+            group_id: Set(log.group_id),
+            operator_id: Set(log.operator_id),
+            action: Set(log.action.to_string()),
+            target_id: Set(log.target_id),
+            detail: Set(log.detail),
+            success: Set(log.error.is_none()),
+            error: Set(log.error),
+            ..Default::default()
+        };
+        active.insert(self.db.as_ref()).await
+    }
+
+    async fn list(
+        &self,
+        group_id: Option<i64>,
+        page: u64,
+        limit: u64,
+    ) -> Result<(Vec<Model>, u64), DbErr> {
+        let mut query = Entity::find().order_by_desc(Column::Id);
+        if let Some(group_id) = group_id {
+            query = query.filter(Column::GroupId.eq(group_id));
+        }
+
+        let paginator = query.paginate(self.db.as_ref(), limit);
+        let total = paginator.num_items().await?;
+        let items = paginator.fetch_page(page.saturating_sub(1)).await?;
+        Ok((items, total))
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::onboarding::{ActiveModel, Entity, Model};
+use async_trait::async_trait;
+use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, IntoActiveModel, Set};
+
+#[async_trait]
+pub trait OnboardingRepository {
+    async fn find(&self, user_id: i64) -> Result<Option<Model>, DbErr>;
+    /// 保存用户的引导进度，不存在时新建
+    async fn save(&self, user_id: i64, step: &'static str, attempts: i32) -> Result<Model, DbErr>;
+}
+
+pub struct OnboardingRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl OnboardingRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl OnboardingRepository for OnboardingRepo {
+    async fn find(&self, user_id: i64) -> Result<Option<Model>, DbErr> {
+        Entity::find_by_id(user_id).one(self.db.as_ref()).await
+    }
+
+    async fn save(&self, user_id: i64, step: &'static str, attempts: i32) -> Result<Model, DbErr> {
+        match self.find(user_id).await? {
+            Some(existing) => {
+                let mut active = existing.into_active_model();
+                active.step = Set(step.to_string());
+                active.attempts = Set(attempts);
+                active.update(self.db.as_ref()).await
+            }
+            None => {
+                let active = ActiveModel {
+                    user_id: Set(user_id),
+                    step: Set(step.to_string()),
+                    attempts: Set(attempts),
+                    ..Default::default()
+                };
+                active.insert(self.db.as_ref()).await
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::permission_grant::{ActiveModel, Column, Entity, Model};
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+
+#[async_trait]
+pub trait PermissionGrantRepository {
+    async fn list(&self) -> Result<Vec<Model>, DbErr>;
+    async fn list_by_user(&self, user_id: i64) -> Result<Vec<Model>, DbErr>;
+    async fn list_by_role(&self, role: &str) -> Result<Vec<Model>, DbErr>;
+    /// 授权，已存在相同授权时返回 None
+    async fn grant(
+        &self,
+        user_id: i64,
+        role: &str,
+        scope_id: i64,
+        granted_by: i64,
+    ) -> Result<Option<Model>, DbErr>;
+    /// 撤销授权，返回是否存在该授权
+    async fn revoke(&self, user_id: i64, role: &str, scope_id: i64) -> Result<bool, DbErr>;
+}
+
+pub struct PermissionGrantRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl PermissionGrantRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl PermissionGrantRepository for PermissionGrantRepo {
+    async fn list(&self) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .order_by_asc(Column::UserId)
+            .order_by_asc(Column::Role)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn list_by_user(&self, user_id: i64) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn list_by_role(&self, role: &str) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::Role.eq(role))
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn grant(
+        &self,
+        user_id: i64,
+        role: &str,
+        scope_id: i64,
+        granted_by: i64,
+    ) -> Result<Option<Model>, DbErr> {
+        let existing = Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Role.eq(role))
+            .filter(Column::ScopeId.eq(scope_id))
+            .one(self.db.as_ref())
+            .await?;
+        if existing.is_some() {
+            return Ok(None);
+        }
+        let active = ActiveModel {
+            user_id: Set(user_id),
+            role: Set(role.to_string()),
+            scope_id: Set(scope_id),
+            granted_by: Set(Some(granted_by)),
+            ..Default::default()
+        };
+        active.insert(self.db.as_ref()).await.map(Some)
+    }
+
+    async fn revoke(&self, user_id: i64, role: &str, scope_id: i64) -> Result<bool, DbErr> {
+        let result = Entity::delete_many()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Role.eq(role))
+            .filter(Column::ScopeId.eq(scope_id))
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -39,8 +39,10 @@ impl UserConfigRepository for UserConfigRepo {
         match existing {
             Some(_) => {
                 // 记录存在，执行更新
-                let mut active_model: crate::models::user_config::ActiveModel = Default::default();
-                active_model.user_id = Set(user_id);
+                let mut active_model = crate::models::user_config::ActiveModel {
+                    user_id: Set(user_id),
+                    ..Default::default()
+                };
                 
                 let strategy_str = match user_data.stratege {
                     crate::StrategeType::CmdStrategy => "cmd_strategy",
@@ -54,6 +56,7 @@ impl UserConfigRepository for UserConfigRepo {
                     Some(user_data.model.clone()) 
                 });
                 active_model.custom_prompt = Set(user_data.custom_prompt.clone());
+                active_model.voice_reply = Set(user_data.voice_reply);
                 
                 active_model.update(&self.db).await?;
             }
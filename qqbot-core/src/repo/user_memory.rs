@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use super::DbErr;
+use crate::models::user_memory::{ActiveModel, Column, Entity, Model};
+use async_trait::async_trait;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+
+#[async_trait]
+pub trait UserMemoryRepository {
+    async fn list_by_user(&self, user_id: i64) -> Result<Vec<Model>, DbErr>;
+    async fn add(&self, user_id: i64, content: String, source: &str) -> Result<Model, DbErr>;
+    /// 删除用户的一条记忆，返回是否删除成功
+    async fn delete(&self, user_id: i64, id: i64) -> Result<bool, DbErr>;
+    async fn delete_all(&self, user_id: i64) -> Result<u64, DbErr>;
+}
+
+pub struct UserMemoryRepo {
+    db: Arc<DatabaseConnection>,
+}
+
+impl UserMemoryRepo {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl UserMemoryRepository for UserMemoryRepo {
+    async fn list_by_user(&self, user_id: i64) -> Result<Vec<Model>, DbErr> {
+        Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::Id)
+            .all(self.db.as_ref())
+            .await
+    }
+
+    async fn add(&self, user_id: i64, content: String, source: &str) -> Result<Model, DbErr> {
+        let memory = ActiveModel {
+            user_id: Set(user_id),
+            content: Set(content),
+            source: Set(source.to_string()),
+            ..Default::default()
+        };
+        memory.insert(self.db.as_ref()).await
+    }
+
+    async fn delete(&self, user_id: i64, id: i64) -> Result<bool, DbErr> {
+        // 带上 user_id 条件，避免删除他人的记忆
+        let result = Entity::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::UserId.eq(user_id))
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn delete_all(&self, user_id: i64) -> Result<u64, DbErr> {
+        let result = Entity::delete_many()
+            .filter(Column::UserId.eq(user_id))
+            .exec(self.db.as_ref())
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,306 @@
+//! 群规则引擎：按触发条件匹配群消息，生成回复、撤回、禁言、通知管理员等动作
+
+use std::time::Duration;
+
+use regex::Regex;
+use serde_json::json;
+
+use crate::{
+    action::{self, BotAction},
+    error::{AppError, AppResult},
+    models::group_rule::{
+        ACTION_MUTE, ACTION_NOTIFY_ADMIN, ACTION_RECALL, ACTION_REPLY, Model, TRIGGER_AT,
+        TRIGGER_KEYWORD, TRIGGER_REGEX, TRIGGER_SEGMENT,
+    },
+    reply_strategy::{MessageContent, MessageSegment},
+    template,
+};
+
+#[derive(Debug, Clone)]
+pub enum Trigger {
+    Keywords(Vec<String>),
+    Regex(Regex),
+    Segments(Vec<String>),
+    /// None 表示 @ 任何人
+    At(Option<String>),
+}
+
+#[derive(Debug, Clone)]
+pub enum RuleAction {
+    Reply(String),
+    Recall,
+    Mute(Duration),
+    NotifyAdmin(String),
+}
+
+/// 编译好的规则
+#[derive(Debug, Clone)]
+pub struct CompiledRule {
+    pub rule: Model,
+    pub trigger: Trigger,
+    pub action: RuleAction,
+}
+
+impl CompiledRule {
+    pub fn compile(rule: Model) -> AppResult<Self> {
+        let trigger = compile_trigger(&rule.trigger_type, &rule.trigger_value)?;
+        let action = compile_action(&rule.action_type, &rule.action_value)?;
+        Ok(Self { rule, trigger, action })
+    }
+}
+
+/// 检查触发条件和动作参数是否有效
+pub fn validate(trigger_type: &str, trigger_value: &str, action_type: &str, action_value: &str) -> AppResult<()> {
+    compile_trigger(trigger_type, trigger_value)?;
+    compile_action(action_type, action_value)?;
+    Ok(())
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split([',', '，'])
+        .map(|item| item.trim().to_lowercase())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn compile_trigger(trigger_type: &str, value: &str) -> AppResult<Trigger> {
+    match trigger_type {
+        TRIGGER_KEYWORD => {
+            let keywords = split_list(value);
+            if keywords.is_empty() {
+                return Err(AppError::validation("关键词不能为空"));
+            }
+            Ok(Trigger::Keywords(keywords))
+        }
+        TRIGGER_REGEX => Regex::new(value)
+            .map(Trigger::Regex)
+            .map_err(|e| AppError::validation(format!("正则表达式无效: {}", e))),
+        TRIGGER_SEGMENT => {
+            let kinds = split_list(value);
+            if kinds.is_empty() {
+                return Err(AppError::validation("消息段类型不能为空"));
+            }
+            Ok(Trigger::Segments(kinds))
+        }
+        TRIGGER_AT => {
+            let target = value.trim();
+            Ok(Trigger::At((!target.is_empty()).then(|| target.to_string())))
+        }
+        other => Err(AppError::validation(format!("未知的触发类型: {}", other))),
+    }
+}
+
+fn compile_action(action_type: &str, value: &str) -> AppResult<RuleAction> {
+    match action_type {
+        ACTION_REPLY | ACTION_NOTIFY_ADMIN => {
+            if value.trim().is_empty() {
+                return Err(AppError::validation("消息内容不能为空"));
+            }
+            template::validate(value)?;
+            if action_type == ACTION_REPLY {
+                Ok(RuleAction::Reply(value.to_string()))
+            } else {
+                Ok(RuleAction::NotifyAdmin(value.to_string()))
+            }
+        }
+        ACTION_RECALL => Ok(RuleAction::Recall),
+        ACTION_MUTE => Ok(RuleAction::Mute(action::parse_mute_duration(value)?)),
+        other => Err(AppError::validation(format!("未知的动作类型: {}", other))),
+    }
+}
+
+/// 待匹配的群消息
+pub struct RuleEvent<'a> {
+    pub group_id: i64,
+    pub sender_id: i64,
+    pub sender_name: &'a str,
+    pub message_id: Option<i64>,
+    pub content: &'a MessageContent,
+    /// 群管理员和群主不会被撤回或禁言
+    pub sender_is_admin: bool,
+}
+
+/// 规则匹配结果
+#[derive(Debug, Default)]
+pub struct RuleOutcome {
+    pub actions: Vec<BotAction>,
+    /// 命中的规则 ID
+    pub matched: Vec<i64>,
+    /// 是否停止后续处理（不再交给命令或大模型）
+    pub stop: bool,
+}
+
+impl Trigger {
+    pub fn matches(&self, content: &MessageContent) -> bool {
+        match self {
+            Trigger::Keywords(keywords) => {
+                let text = searchable_text(content).to_lowercase();
+                keywords.iter().any(|keyword| text.contains(keyword.as_str()))
+            }
+            Trigger::Regex(regex) => regex.is_match(&searchable_text(content)),
+            Trigger::Segments(kinds) => segments(content).iter().any(|seg| kinds.iter().any(|k| k == seg.kind())),
+            Trigger::At(target) => segments(content).iter().any(|seg| match seg {
+                MessageSegment::At { qq } => target.as_ref().is_none_or(|target| target == qq),
+                _ => false,
+            }),
+        }
+    }
+}
+
+fn segments(content: &MessageContent) -> &[MessageSegment] {
+    match content {
+        MessageContent::Mixed(segments) => segments,
+        _ => &[],
+    }
+}
+
+/// 文字及卡片消息的原始内容，使分享卡片中的链接也能被匹配
+fn searchable_text(content: &MessageContent) -> String {
+    match content {
+        MessageContent::Text(text) => text.clone(),
+        MessageContent::Mixed(segments) => segments
+            .iter()
+            .filter_map(|seg| match seg {
+                MessageSegment::Text { text } => Some(text.as_str()),
+                MessageSegment::Json { data } | MessageSegment::Xml { data } => Some(data.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn render(source: &str, rule: &Model, event: &RuleEvent) -> String {
+    let ctx = json!({
+        "rule": rule.name,
+        "group_id": event.group_id,
+        "sender": { "id": event.sender_id, "name": event.sender_name },
+        "message": event.content.describe(),
+    });
+    template::render_str(source, ctx).unwrap_or_else(|err| {
+        log::warn!("群规则 {} 的消息模板渲染失败: {}", rule.id, err);
+        source.to_string()
+    })
+}
+
+/// 按优先级依次匹配规则，遇到 stop_processing 的规则后停止。rules 需已按优先级排序
+pub fn evaluate(rules: &[CompiledRule], event: &RuleEvent, admins: &[i64]) -> RuleOutcome {
+    let mut outcome = RuleOutcome::default();
+    let mut recalled = false;
+    for compiled in rules {
+        let rule = &compiled.rule;
+        if rule.group_id != event.group_id || !compiled.trigger.matches(event.content) {
+            continue;
+        }
+        outcome.matched.push(rule.id);
+
+        match &compiled.action {
+            RuleAction::Reply(source) => outcome.actions.push(BotAction::SendGroup {
+                group_id: event.group_id,
+                // 消息已被撤回时不再引用
+                content: MessageContent::Text(render(source, rule, event))
+                    .quote(if recalled { None } else { event.message_id }),
+            }),
+            RuleAction::Recall => {
+                if let (Some(message_id), false, false) = (event.message_id, event.sender_is_admin, recalled) {
+                    recalled = true;
+                    outcome.actions.push(BotAction::Recall { message_id });
+                }
+            }
+            RuleAction::Mute(duration) => {
+                if !event.sender_is_admin {
+                    outcome.actions.push(BotAction::Mute {
+                        group_id: event.group_id,
+                        user_id: event.sender_id,
+                        duration: *duration,
+                    });
+                }
+            }
+            RuleAction::NotifyAdmin(source) => {
+                let text = render(source, rule, event);
+                outcome.actions.extend(admins.iter().map(|admin| BotAction::SendPrivate {
+                    user_id: *admin,
+                    content: MessageContent::Text(text.clone()),
+                }));
+            }
+        }
+
+        if rule.stop_processing {
+            outcome.stop = true;
+            break;
+        }
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn rule(id: i64, trigger: (&str, &str), action: (&str, &str), stop: bool) -> CompiledRule {
+        CompiledRule::compile(Model {
+            id,
+            group_id: 100,
+            name: format!("rule {}", id),
+            trigger_type: trigger.0.to_string(),
+            trigger_value: trigger.1.to_string(),
+            action_type: action.0.to_string(),
+            action_value: action.1.to_string(),
+            priority: 0,
+            stop_processing: stop,
+            enabled: true,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_evaluate_rules() {
+        let rules = vec![
+            rule(1, (TRIGGER_REGEX, r"https?://([\w-]+\.)*spam\.com"), (ACTION_RECALL, ""), false),
+            rule(2, (TRIGGER_REGEX, r"spam\.com"), (ACTION_MUTE, "10m"), true),
+            rule(3, (TRIGGER_KEYWORD, "你好,hello"), (ACTION_REPLY, "{{ sender.name }} 你好"), false),
+            rule(4, (TRIGGER_SEGMENT, "file"), (ACTION_NOTIFY_ADMIN, "{{ sender.name }} 发了 {{ message }}"), false),
+        ];
+        fn event(content: &MessageContent, sender_is_admin: bool) -> RuleEvent<'_> {
+            RuleEvent {
+                group_id: 100,
+                sender_id: 1,
+                sender_name: "张三",
+                message_id: Some(42),
+                content,
+                sender_is_admin,
+            }
+        }
+
+        let link = MessageContent::Text("看看 https://www.spam.com/a 你好".to_string());
+        let outcome = evaluate(&rules, &event(&link, false), &[9]);
+        assert_eq!(outcome.matched, vec![1, 2]);
+        assert!(outcome.stop);
+        assert!(matches!(outcome.actions[0], BotAction::Recall { message_id: 42 }));
+        assert!(matches!(outcome.actions[1], BotAction::Mute { duration, .. } if duration.as_secs() == 600));
+
+        // 管理员不会被撤回或禁言
+        assert!(evaluate(&rules, &event(&link, true), &[9]).actions.is_empty());
+
+        let file = MessageContent::Mixed(vec![
+            MessageSegment::Text { text: "Hello".to_string() },
+            MessageSegment::File { media: Default::default() },
+        ]);
+        let outcome = evaluate(&rules, &event(&file, false), &[9]);
+        assert_eq!(outcome.matched, vec![3, 4]);
+        match &outcome.actions[1] {
+            BotAction::SendPrivate { user_id: 9, content: MessageContent::Text(text) } => {
+                assert!(text.starts_with("张三 发了 Hello"))
+            }
+            other => panic!("unexpected action {:?}", other),
+        }
+
+        let invalid = Model { action_value: "1y".to_string(), ..rules[1].rule.clone() };
+        assert!(CompiledRule::compile(invalid).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    GroupId, UserId,
+    config::{APPCONFIG, app_config::ModerationModel, get_db},
+    content_filter::{FILTER, SOURCE_MODEL, Stage, Violation},
+    error::{AppError, AppResult},
+    models::content_incident::Model,
+    repo::content_incident::{ContentIncidentRepo, ContentIncidentRepository, NewContentIncident},
+    reply_strategy::context,
+};
+
+/// 记录中命中内容的最大长度，与表结构一致
+const MAX_MATCHED_CHARS: usize = 255;
+
+static CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(APPCONFIG.llm.timeout_seconds))
+        .build()
+        .expect("Failed to create HTTP client")
+});
+
+#[derive(Deserialize)]
+struct ModerationResponse {
+    results: Vec<ModerationResult>,
+}
+
+#[derive(Deserialize)]
+struct ModerationResult {
+    flagged: bool,
+    #[serde(default)]
+    categories: BTreeMap<String, bool>,
+}
+
+/// 内容审核服务：拦截记录的保存与查询
+pub struct ContentFilterService {
+    repo: ContentIncidentRepo,
+}
+
+impl ContentFilterService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: ContentIncidentRepo::new(db),
+        }
+    }
+
+    pub async fn record(&self, incident: NewContentIncident) -> AppResult<Model> {
+        let incident = self.repo.create(incident).await?;
+        Ok(incident)
+    }
+
+    pub async fn list(&self, page: u64, limit: u64) -> AppResult<(Vec<Model>, u64)> {
+        let result = self.repo.list(page, limit).await?;
+        Ok(result)
+    }
+}
+
+/// 审核一段文字，命中时记录拦截并返回命中的规则。
+/// 未启用、内容为空或审核模型出错时放行，不影响正常回复
+pub async fn screen(stage: Stage, text: &str, user_id: UserId, group_id: Option<GroupId>) -> Option<Violation> {
+    if !APPCONFIG.content_filter.enabled || text.trim().is_empty() {
+        return None;
+    }
+    let violation = match FILTER.check(text) {
+        Some(violation) => violation,
+        None => match model_check(text).await {
+            Ok(Some(category)) => Violation {
+                source: SOURCE_MODEL,
+                matched: category,
+            },
+            Ok(None) => return None,
+            Err(err) => {
+                log::warn!("内容审核模型调用失败: {}", err);
+                return None;
+            }
+        },
+    };
+
+    log::warn!(
+        "拦截用户 {} 的{}，命中 {}: {}",
+        user_id,
+        if stage == Stage::Input { "消息" } else { "回复" },
+        violation.source,
+        violation.matched
+    );
+    let incident = NewContentIncident {
+        user_id,
+        group_id,
+        stage: stage.as_str(),
+        source: violation.source,
+        matched: violation.matched.chars().take(MAX_MATCHED_CHARS).collect(),
+        content: text.to_string(),
+    };
+    if let Err(err) = ContentFilterService::new(get_db().await).record(incident).await {
+        log::warn!("保存内容拦截记录失败: {}", err);
+    }
+    Some(violation)
+}
+
+/// 命中审核时代替原回复发送的内容
+pub fn refusal() -> String {
+    APPCONFIG.content_filter.refusal.clone()
+}
+
+/// 调用审核模型，返回违规类别
+async fn model_check(text: &str) -> AppResult<Option<String>> {
+    match APPCONFIG.content_filter.model {
+        ModerationModel::None => Ok(None),
+        ModerationModel::OpenAi => openai_check(text).await,
+        ModerationModel::Llm => llm_check(text).await,
+    }
+}
+
+async fn openai_check(text: &str) -> AppResult<Option<String>> {
+    let config = &APPCONFIG.content_filter;
+    let base_url = config.base_url.as_ref().unwrap_or(&APPCONFIG.llm.base_url);
+    let api_key = config.api_key.as_ref().unwrap_or(&APPCONFIG.llm.api_key);
+    let response = CLIENT
+        .post(format!("{}/moderations", base_url))
+        .header("Authorization", format!("Bearer {}", api_key))
+        .json(&json!({ "model": config.model_name, "input": text }))
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::internal(format!("审核接口返回错误 {}: {}", status, error_text)));
+    }
+    let result = response.json::<ModerationResponse>().await?;
+    Ok(result.results.into_iter().find(|r| r.flagged).map(|r| {
+        let categories: Vec<String> = r
+            .categories
+            .into_iter()
+            .filter(|(_, flagged)| *flagged)
+            .map(|(category, _)| category)
+            .collect();
+        if categories.is_empty() { "flagged".to_string() } else { categories.join(",") }
+    }))
+}
+
+async fn llm_check(text: &str) -> AppResult<Option<String>> {
+    let prompt = format!(
+        "你是班级群的内容审核员。判断下面的内容是否包含色情、暴力、违法犯罪、考试作弊、\
+         人身攻击或泄露他人隐私等不适合出现在班级群中的内容。\
+         只输出 SAFE，或输出 UNSAFE:类别。\n\n内容：\n{}",
+        text
+    );
+    let response = context::complete(prompt, 0.0).await.map_err(AppError::reply)?;
+    Ok(parse_verdict(&response))
+}
+
+/// 解析审核结果，不区分大小写，无法识别时视为通过
+fn parse_verdict(response: &str) -> Option<String> {
+    const UNSAFE: &str = "unsafe";
+    let verdict = response.trim().trim_matches(['`', '*']).trim();
+    if !verdict.get(..UNSAFE.len())?.eq_ignore_ascii_case(UNSAFE) {
+        return None;
+    }
+    let category = verdict[UNSAFE.len()..].trim_start().trim_start_matches([':', '：']).trim();
+    Some(if category.is_empty() { UNSAFE.to_string() } else { category.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(parse_verdict("UNSAFE:考试作弊"), Some("考试作弊".to_string()));
+        assert_eq!(parse_verdict(" unsafe ： 人身攻击\n"), Some("人身攻击".to_string()));
+        assert_eq!(parse_verdict("`Unsafe`"), Some("unsafe".to_string()));
+        assert_eq!(parse_verdict("SAFE"), None);
+        assert_eq!(parse_verdict("safe"), None);
+        assert_eq!(parse_verdict("内容安全"), None);
+        assert_eq!(parse_verdict(""), None);
+    }
+}
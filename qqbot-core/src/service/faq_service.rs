@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    config::{APPCONFIG, get_db},
+    error::{AppError, AppResult},
+    faq::{self, CompiledFaq, Matcher},
+    models::faq::Model,
+    repo::faq::{FaqFields, FaqRepo, FaqRepository},
+};
+
+/// 编译好的启用规则。管理后台与机器人是不同进程，修改后依赖 cache_ttl 过期重新加载
+static FAQ_RULES: Lazy<Cache<(), Arc<Vec<CompiledFaq>>>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(1)
+        .time_to_live(APPCONFIG.faq.cache_ttl)
+        .build()
+});
+
+/// 常见问题服务：规则的增删改查与消息匹配
+pub struct FaqService {
+    repo: FaqRepo,
+}
+
+impl FaqService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: FaqRepo::new(db),
+        }
+    }
+
+    pub async fn list(&self) -> AppResult<Vec<Model>> {
+        let faqs = self.repo.list().await?;
+        Ok(faqs)
+    }
+
+    pub async fn create(&self, fields: FaqFields) -> AppResult<Model> {
+        let fields = Self::validate(fields)?;
+        let faq = self.repo.create(fields).await?;
+        FAQ_RULES.invalidate_all();
+        Ok(faq)
+    }
+
+    pub async fn update(&self, id: i64, fields: FaqFields) -> AppResult<Model> {
+        let fields = Self::validate(fields)?;
+        let faq = self.repo.update(id, fields).await.map_err(|e| match e {
+            sea_orm::DbErr::RecordNotFound(_) => AppError::not_found(format!("FAQ {}", id)),
+            e => AppError::Database(e),
+        })?;
+        FAQ_RULES.invalidate_all();
+        Ok(faq)
+    }
+
+    pub async fn delete(&self, id: i64) -> AppResult<()> {
+        if !self.repo.delete(id).await? {
+            return Err(AppError::not_found(format!("FAQ {}", id)));
+        }
+        FAQ_RULES.invalidate_all();
+        Ok(())
+    }
+
+    /// 查找与消息匹配的 FAQ，group_id 为 None 表示私聊
+    pub async fn find_answer(&self, text: &str, group_id: Option<i64>) -> AppResult<Option<Model>> {
+        let rules = self.load_rules().await?;
+        Ok(faq::best_match(&rules, text, group_id, APPCONFIG.faq.fuzzy_threshold).cloned())
+    }
+
+    pub async fn record_hit(&self, id: i64) -> AppResult<()> {
+        self.repo.increment_hit(id).await?;
+        Ok(())
+    }
+
+    fn validate(mut fields: FaqFields) -> AppResult<FaqFields> {
+        fields.question = fields.question.trim().to_string();
+        fields.answer = fields.answer.trim().to_string();
+        if fields.question.is_empty() || fields.answer.is_empty() {
+            return Err(AppError::validation("问题和回复不能为空"));
+        }
+        Matcher::compile(&fields.match_type, &fields.pattern)?;
+        Ok(fields)
+    }
+
+    async fn load_rules(&self) -> AppResult<Arc<Vec<CompiledFaq>>> {
+        if let Some(rules) = FAQ_RULES.get(&()).await {
+            return Ok(rules);
+        }
+        let rules: Vec<CompiledFaq> = self
+            .repo
+            .list_enabled()
+            .await?
+            .into_iter()
+            .filter_map(|faq| {
+                let id = faq.id;
+                CompiledFaq::compile(faq)
+                    .map_err(|err| log::warn!("FAQ {} 的匹配规则无效: {}", id, err))
+                    .ok()
+            })
+            .collect();
+        let rules = Arc::new(rules);
+        FAQ_RULES.insert((), rules.clone()).await;
+        Ok(rules)
+    }
+}
+
+/// 匹配常见问题并返回回复内容，未启用、未命中或出错时返回 None
+pub async fn auto_answer(text: &str, group_id: Option<i64>) -> Option<String> {
+    if !APPCONFIG.faq.enabled || text.trim().is_empty() {
+        return None;
+    }
+    let service = FaqService::new(get_db().await);
+    match service.find_answer(text, group_id).await {
+        Ok(Some(faq)) => {
+            if let Err(err) = service.record_hit(faq.id).await {
+                log::warn!("记录 FAQ {} 命中次数失败: {}", faq.id, err);
+            }
+            Some(faq.answer)
+        }
+        Ok(None) => None,
+        Err(err) => {
+            log::warn!("FAQ 匹配失败: {}", err);
+            None
+        }
+    }
+}
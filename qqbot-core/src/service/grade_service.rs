@@ -1,21 +1,109 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use sea_orm::DatabaseConnection;
 
 use crate::{
+    chart::{self, GradePoint},
     error::{AppError, AppResult},
-    models::grade::Model,
-    permission::check_permission,
-    repo::{GradeRepository, grade::GradeRepo},
+    models::{grade::Model, student::Model as StudentModel},
+    permission::{self, Grants},
+    repo::{
+        GradeRepository,
+        grade::GradeRepo,
+        student::{StudentRepo, StudentRepository},
+    },
 };
 
 use super::{StuServiceImpl, UserService};
 
 pub trait GradeService {
+    /// 查询学生的成绩，不指定学号时查询 operator 本人绑定的学号
     fn find_grades(
         &self,
-        qq: i64,
+        operator: i64,
+        student_id: Option<i64>,
     ) -> impl std::future::Future<Output = AppResult<Vec<Model>>> + Send;
+
+    /// 统计某次考试各课程的成绩，只统计 operator 有权查看的部分
+    fn exam_stats(
+        &self,
+        operator: i64,
+        exam_name: &str,
+        course_id: Option<i32>,
+    ) -> impl std::future::Future<Output = AppResult<Vec<CourseStats>>> + Send;
+
+    /// 学生各次考试的成绩走势及班级平均分，用于画图
+    fn grade_trend(
+        &self,
+        operator: i64,
+        student_id: Option<i64>,
+    ) -> impl std::future::Future<Output = AppResult<(StudentModel, Vec<GradePoint>)>> + Send;
+}
+
+/// 及格线
+const PASS_SCORE: i8 = 60;
+
+/// 一门课在某次考试中的成绩统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct CourseStats {
+    pub course_id: i32,
+    pub count: usize,
+    pub average: f64,
+    pub max: i8,
+    pub min: i8,
+    pub passed: usize,
+}
+
+/// 按课程统计成绩，结果按课程号排序
+pub fn summarize(grades: &[Model]) -> Vec<CourseStats> {
+    let mut by_course: BTreeMap<i32, Vec<i8>> = BTreeMap::new();
+    for grade in grades {
+        by_course.entry(grade.course_id).or_default().push(grade.score);
+    }
+    by_course
+        .into_iter()
+        .map(|(course_id, scores)| CourseStats {
+            course_id,
+            count: scores.len(),
+            average: scores.iter().map(|&s| s as f64).sum::<f64>() / scores.len() as f64,
+            max: scores.iter().copied().max().unwrap_or_default(),
+            min: scores.iter().copied().min().unwrap_or_default(),
+            passed: scores.iter().filter(|&&s| s >= PASS_SCORE).count(),
+        })
+        .collect()
+}
+
+/// 按授权范围过滤他人的成绩。有成绩但全部不在范围内时返回无权限，而不是空结果
+pub fn visible_grades(grants: &Grants, stu: &StudentModel, grades: Vec<Model>) -> AppResult<Vec<Model>> {
+    let total = grades.len();
+    let visible: Vec<Model> = grades
+        .into_iter()
+        .filter(|grade| grants.can_view_grade(stu.group_id, grade.course_id as i64))
+        .collect();
+    if total > 0 && visible.is_empty() {
+        return Err(AppError::permission(format!("你无权查看 {} 的成绩", stu.name)));
+    }
+    Ok(visible)
+}
+
+/// 按授权范围过滤一次考试的成绩，没有可查看的成绩时返回未找到
+pub fn visible_exam_grades(
+    grants: &Grants,
+    exam_name: &str,
+    rows: Vec<(Model, Option<StudentModel>)>,
+) -> AppResult<Vec<Model>> {
+    let grades: Vec<Model> = rows
+        .into_iter()
+        .filter(|(grade, student)| {
+            let group_id = student.as_ref().map_or(0, |s| s.group_id);
+            grants.can_view_grade(group_id, grade.course_id as i64)
+        })
+        .map(|(grade, _)| grade)
+        .collect();
+    if grades.is_empty() {
+        return Err(AppError::not_found(format!("{} 中你有权查看的成绩", exam_name)));
+    }
+    Ok(grades)
 }
 
 pub struct GradeServiceImpl {
@@ -32,19 +120,69 @@ impl GradeServiceImpl {
     }
 }
 
+impl GradeServiceImpl {
+    /// 指定学号的学生，不指定时为 operator 本人绑定的学生
+    async fn find_student(&self, operator: i64, student_id: Option<i64>) -> AppResult<StudentModel> {
+        match student_id {
+            Some(student_id) => StudentRepo::new(self.db.clone())
+                .find_by_id(student_id)
+                .await?
+                .ok_or_else(|| AppError::not_found(format!("学号 {}", student_id))),
+            None => StuServiceImpl::new(self.db.clone()).find_by_qq(operator).await,
+        }
+    }
+}
+
 impl GradeService for GradeServiceImpl {
-    async fn find_grades(&self, qq: i64) -> AppResult<Vec<Model>> {
-        let ss = StuServiceImpl::new(self.db.clone());
+    async fn find_grades(&self, operator: i64, student_id: Option<i64>) -> AppResult<Vec<Model>> {
+        let stu = self.find_student(operator, student_id).await?;
 
-        let stu = ss.find_by_qq(qq).await?;
+        let grades = self.repo.query_grades(stu.student_id).await?;
+        if stu.qq_number == operator {
+            return Ok(grades);
+        }
 
-        // 检查权限
-        if stu.qq_number != qq && !check_permission(qq) {
+        // 查看他人成绩：超级管理员不限，教师只能看所授课程，助教只能看所管理群内的学生
+        let grants = permission::grants(operator).await;
+        if grants.is_empty() {
             return Err(AppError::permission("你无法查看他人的成绩"));
         }
+        visible_grades(&grants, &stu, grades)
+    }
 
-        let grades = self.repo.query_grades(stu.student_id).await?;
-        Ok(grades)
+    async fn exam_stats(
+        &self,
+        operator: i64,
+        exam_name: &str,
+        course_id: Option<i32>,
+    ) -> AppResult<Vec<CourseStats>> {
+        let grants = permission::grants(operator).await;
+        if grants.is_empty() {
+            return Err(AppError::permission("只有教师、助教或管理员可以查看考试统计"));
+        }
+        let rows = self.repo.query_exam_grades(exam_name, course_id).await?;
+        let grades = visible_exam_grades(&grants, exam_name, rows)?;
+        Ok(summarize(&grades))
+    }
+
+    async fn grade_trend(
+        &self,
+        operator: i64,
+        student_id: Option<i64>,
+    ) -> AppResult<(StudentModel, Vec<GradePoint>)> {
+        let stu = self.find_student(operator, student_id).await?;
+        let grades = self.find_grades(operator, Some(stu.student_id)).await?;
+        if grades.is_empty() {
+            return Err(AppError::not_found(format!("{} 的成绩", stu.name)));
+        }
+        // 没有加入班级群的学生不计算平均分
+        let class_grades = if stu.group_id == 0 {
+            Vec::new()
+        } else {
+            self.repo.query_group_grades(stu.group_id).await?
+        };
+        let points = chart::grade_points(&grades, &class_grades);
+        Ok((stu, points))
     }
 }
 
@@ -59,7 +197,7 @@ pub async fn create_grade(
     category: &str,
 ) -> AppResult<Model> {
     use crate::config::get_db;
-    use crate::models::grade::{ActiveModel, Entity as Grade};
+    use crate::models::grade::ActiveModel;
     use sea_orm::{ActiveModelTrait, Set};
 
     let db = get_db().await;
@@ -76,9 +214,14 @@ pub async fn create_grade(
     };
 
     let result = grade.insert(db.as_ref()).await.map_err(AppError::from)?;
+
+    // 成绩发布后按配置通知学生
+    super::notify_service::notify_grade_published(db.clone(), &result).await;
+
     Ok(result)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn update_grade_by_id(
     id: i64,
     student_name: Option<String>,
@@ -143,3 +286,70 @@ pub async fn delete_grade_by_id(id: i64) -> AppResult<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{grade, student};
+
+    const COURSE_A: i32 = 1001;
+    const COURSE_B: i32 = 2002;
+
+    #[test]
+    fn test_summarize() {
+        let stats = summarize(&[
+            grade(1, 2, "Mid", 90),
+            grade(1, 1, "Mid", 50),
+            grade(2, 1, "Mid", 70),
+        ]);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].course_id, 1);
+        assert_eq!(stats[0].average, 60.0);
+        assert_eq!((stats[0].max, stats[0].min, stats[0].passed), (70, 50, 1));
+        assert_eq!(stats[1].count, 1);
+    }
+
+    #[test]
+    fn test_find_grades_outside_scope() {
+        // 教师只教课程 A，学生只有课程 B 的成绩
+        let teacher = Grants { courses: vec![COURSE_A as i64], ..Default::default() };
+        let stu = student(10001, 123456);
+        assert!(matches!(
+            visible_grades(&teacher, &stu, vec![grade(1, COURSE_B, "Mid", 80)]),
+            Err(AppError::Permission { .. })
+        ));
+
+        // 两门课都有成绩时只返回课程 A
+        let visible =
+            visible_grades(&teacher, &stu, vec![grade(1, COURSE_A, "Mid", 80), grade(1, COURSE_B, "Mid", 90)])
+                .unwrap();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].course_id, COURSE_A);
+
+        // 学生本来就没有成绩时仍返回空
+        assert!(visible_grades(&teacher, &stu, Vec::new()).unwrap().is_empty());
+
+        // 助教按学生所在的群授权，与课程无关
+        let ta = Grants { groups: vec![123456], ..Default::default() };
+        assert_eq!(visible_grades(&ta, &stu, vec![grade(1, COURSE_B, "Mid", 80)]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_exam_stats_outside_scope() {
+        let teacher = Grants { courses: vec![COURSE_A as i64], ..Default::default() };
+        let rows = vec![
+            (grade(1, COURSE_B, "Mid", 80), Some(student(10001, 123456))),
+            (grade(2, COURSE_B, "Mid", 60), None),
+        ];
+        assert!(matches!(
+            visible_exam_grades(&teacher, "Mid", rows.clone()),
+            Err(AppError::NotFound { .. })
+        ));
+
+        let mut rows = rows;
+        rows.push((grade(3, COURSE_A, "Mid", 70), None));
+        let grades = visible_exam_grades(&teacher, "Mid", rows).unwrap();
+        assert_eq!(grades.len(), 1);
+        assert_eq!(grades[0].course_id, COURSE_A);
+    }
+}
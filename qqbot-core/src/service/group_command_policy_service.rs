@@ -0,0 +1,190 @@
+use std::{collections::HashMap, sync::Arc};
+
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    GroupId, UserId,
+    config::{APPCONFIG, get_db},
+    error::{AppError, AppResult},
+    models::group_command_policy::{COMMAND_LLM, Model},
+    permission::Role,
+    repo::group_command_policy::{GroupCommandPolicyRepo, GroupCommandPolicyRepository},
+};
+
+/// 群内某个命令的生效策略，没有记录的命令默认启用且不限制角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandPolicy {
+    pub enabled: bool,
+    pub min_role: Option<Role>,
+}
+
+impl Default for CommandPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_role: None,
+        }
+    }
+}
+
+impl CommandPolicy {
+    fn from_model(model: &Model) -> Self {
+        Self {
+            enabled: model.enabled,
+            min_role: model.min_role.as_deref().and_then(Role::parse),
+        }
+    }
+
+    /// 检查角色能否使用该命令，机器人管理员不受停用限制
+    pub fn check(&self, command: &str, role: Role) -> AppResult<()> {
+        if !self.enabled && role != Role::BotAdmin {
+            return Err(AppError::permission(format!("本群已停用 {} 命令", command)));
+        }
+        match self.min_role {
+            Some(min_role) if role < min_role => Err(AppError::permission(format!(
+                "本群中 {} 命令需要 {} 及以上角色",
+                command,
+                min_role.as_str()
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+// 每条命令都要检查，群内策略整体缓存，修改后失效
+static GROUP_POLICIES: Lazy<Cache<GroupId, Arc<HashMap<String, CommandPolicy>>>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(APPCONFIG.cache.cache_capacity / 10)
+        .time_to_live(APPCONFIG.cache.cache_lifetime)
+        .build()
+});
+
+/// 群命令策略服务：按群启用、停用命令或限制使用的角色
+pub struct GroupCommandPolicyService {
+    repo: GroupCommandPolicyRepo,
+}
+
+impl GroupCommandPolicyService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: GroupCommandPolicyRepo::new(db),
+        }
+    }
+
+    pub async fn list(&self, group_id: GroupId) -> AppResult<Vec<Model>> {
+        let policies = self.repo.list_by_group(group_id).await?;
+        Ok(policies)
+    }
+
+    /// 群内生效的策略
+    pub async fn policies(&self, group_id: GroupId) -> AppResult<Arc<HashMap<String, CommandPolicy>>> {
+        if let Some(policies) = GROUP_POLICIES.get(&group_id).await {
+            return Ok(policies);
+        }
+        let policies: HashMap<String, CommandPolicy> = self
+            .list(group_id)
+            .await?
+            .iter()
+            .map(|model| (model.command.clone(), CommandPolicy::from_model(model)))
+            .collect();
+        let policies = Arc::new(policies);
+        GROUP_POLICIES.insert(group_id, policies.clone()).await;
+        Ok(policies)
+    }
+
+    pub async fn policy(&self, group_id: GroupId, command: &str) -> AppResult<CommandPolicy> {
+        Ok(self
+            .policies(group_id)
+            .await?
+            .get(command)
+            .copied()
+            .unwrap_or_default())
+    }
+
+    /// 启用或停用命令，保留原有的角色限制
+    pub async fn set_enabled(
+        &self,
+        group_id: GroupId,
+        command: &str,
+        enabled: bool,
+        operator: UserId,
+    ) -> AppResult<()> {
+        let policy = self.policy(group_id, command).await?;
+        self.save(group_id, command, CommandPolicy { enabled, ..policy }, operator).await
+    }
+
+    /// 设置命令所需的最低角色，None 表示不限制
+    pub async fn set_min_role(
+        &self,
+        group_id: GroupId,
+        command: &str,
+        min_role: Option<Role>,
+        operator: UserId,
+    ) -> AppResult<()> {
+        let policy = self.policy(group_id, command).await?;
+        self.save(group_id, command, CommandPolicy { min_role, ..policy }, operator).await
+    }
+
+    /// 删除命令的策略，恢复默认，返回是否存在策略
+    pub async fn reset(&self, group_id: GroupId, command: &str) -> AppResult<bool> {
+        let deleted = self.repo.delete(group_id, command).await?;
+        GROUP_POLICIES.invalidate(&group_id).await;
+        Ok(deleted)
+    }
+
+    async fn save(
+        &self,
+        group_id: GroupId,
+        command: &str,
+        policy: CommandPolicy,
+        operator: UserId,
+    ) -> AppResult<()> {
+        let min_role = policy.min_role.map(|role| role.as_str().to_string());
+        self.repo
+            .save(group_id, command, policy.enabled, min_role, operator)
+            .await?;
+        GROUP_POLICIES.invalidate(&group_id).await;
+        Ok(())
+    }
+}
+
+/// 检查群内能否使用命令，读取策略失败时放行，不影响正常使用
+pub async fn check(group_id: GroupId, command: &str, role: Role) -> AppResult<()> {
+    match GroupCommandPolicyService::new(get_db().await).policy(group_id, command).await {
+        Ok(policy) => policy.check(command, role),
+        Err(err) => {
+            log::warn!("读取群 {} 的命令策略失败: {}", group_id, err);
+            Ok(())
+        }
+    }
+}
+
+/// 群内是否可以和大模型聊天
+pub async fn llm_allowed(group_id: GroupId, role: Role) -> bool {
+    check(group_id, COMMAND_LLM, role).await.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_check() {
+        let disabled = CommandPolicy {
+            enabled: false,
+            min_role: None,
+        };
+        assert!(disabled.check("kick", Role::Owner).is_err());
+        assert!(disabled.check("kick", Role::BotAdmin).is_ok());
+
+        let admin_only = CommandPolicy {
+            enabled: true,
+            min_role: Some(Role::Admin),
+        };
+        assert!(admin_only.check("query", Role::Member).is_err());
+        assert!(admin_only.check("query", Role::Admin).is_ok());
+        assert!(CommandPolicy::default().check("query", Role::Member).is_ok());
+    }
+}
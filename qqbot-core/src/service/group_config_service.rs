@@ -56,7 +56,7 @@ impl GroupConfigService {
         // 保存到数据库
         self.repo.upsert_group_config(group_id, group_data)
             .await
-            .map_err(|e| AppError::Database(e))?;
+            .map_err(AppError::Database)?;
 
         // 同步到缓存
         GROUP_CACHE.insert(group_id, group_data.clone()).await;
@@ -69,7 +69,7 @@ impl GroupConfigService {
         // 从数据库删除
         self.repo.delete_group_config(group_id)
             .await
-            .map_err(|e| AppError::Database(e))?;
+            .map_err(AppError::Database)?;
 
         // 从缓存删除
         GROUP_CACHE.remove(&group_id).await;
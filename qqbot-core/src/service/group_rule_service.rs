@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    GroupId,
+    config::{APPCONFIG, get_db},
+    error::{AppError, AppResult},
+    models::group_rule::Model,
+    permission,
+    repo::group_rule::{GroupRuleFields, GroupRuleRepo, GroupRuleRepository},
+    rules::{self, CompiledRule, RuleEvent, RuleOutcome},
+};
+
+/// 各群编译好的启用规则。管理后台与机器人是不同进程，修改后依赖 cache_ttl 过期重新加载
+static RULE_CACHE: Lazy<Cache<GroupId, Arc<Vec<CompiledRule>>>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(APPCONFIG.cache.cache_capacity)
+        .time_to_live(APPCONFIG.rules.cache_ttl)
+        .build()
+});
+
+/// 群规则服务：规则的增删改查与群消息匹配
+pub struct GroupRuleService {
+    repo: GroupRuleRepo,
+}
+
+impl GroupRuleService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: GroupRuleRepo::new(db),
+        }
+    }
+
+    pub async fn list(&self, group_id: Option<GroupId>) -> AppResult<Vec<Model>> {
+        let rules = self.repo.list(group_id).await?;
+        Ok(rules)
+    }
+
+    pub async fn create(&self, fields: GroupRuleFields) -> AppResult<Model> {
+        let fields = Self::validate(fields)?;
+        let rule = self.repo.create(fields).await?;
+        RULE_CACHE.invalidate(&rule.group_id).await;
+        Ok(rule)
+    }
+
+    pub async fn update(&self, id: i64, fields: GroupRuleFields) -> AppResult<Model> {
+        let fields = Self::validate(fields)?;
+        let rule = self.repo.update(id, fields).await.map_err(|e| match e {
+            sea_orm::DbErr::RecordNotFound(_) => AppError::not_found(format!("群规则 {}", id)),
+            e => AppError::Database(e),
+        })?;
+        // 规则可能被移到了其他群，全部失效
+        RULE_CACHE.invalidate_all();
+        Ok(rule)
+    }
+
+    pub async fn delete(&self, id: i64) -> AppResult<()> {
+        let group_id = self
+            .repo
+            .delete(id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("群规则 {}", id)))?;
+        RULE_CACHE.invalidate(&group_id).await;
+        Ok(())
+    }
+
+    /// 用群内启用的规则匹配一条消息
+    pub async fn evaluate(&self, event: &RuleEvent<'_>) -> AppResult<RuleOutcome> {
+        let rules = self.load_rules(event.group_id).await?;
+        Ok(rules::evaluate(&rules, event, &permission::super_admins().await))
+    }
+
+    fn validate(mut fields: GroupRuleFields) -> AppResult<GroupRuleFields> {
+        fields.name = fields.name.trim().to_string();
+        if fields.name.is_empty() {
+            return Err(AppError::validation("规则名称不能为空"));
+        }
+        if fields.group_id <= 0 {
+            return Err(AppError::validation("群号无效"));
+        }
+        rules::validate(
+            &fields.trigger_type,
+            &fields.trigger_value,
+            &fields.action_type,
+            &fields.action_value,
+        )?;
+        Ok(fields)
+    }
+
+    async fn load_rules(&self, group_id: GroupId) -> AppResult<Arc<Vec<CompiledRule>>> {
+        if let Some(rules) = RULE_CACHE.get(&group_id).await {
+            return Ok(rules);
+        }
+        let rules: Vec<CompiledRule> = self
+            .repo
+            .list_enabled(group_id)
+            .await?
+            .into_iter()
+            .filter_map(|rule| {
+                let id = rule.id;
+                CompiledRule::compile(rule)
+                    .map_err(|err| log::warn!("群规则 {} 无效: {}", id, err))
+                    .ok()
+            })
+            .collect();
+        let rules = Arc::new(rules);
+        RULE_CACHE.insert(group_id, rules.clone()).await;
+        Ok(rules)
+    }
+}
+
+/// 用群规则匹配一条群消息，未启用或出错时返回空结果，不影响正常处理
+pub async fn apply_rules(event: &RuleEvent<'_>) -> RuleOutcome {
+    if !APPCONFIG.rules.enabled {
+        return RuleOutcome::default();
+    }
+    match GroupRuleService::new(get_db().await).evaluate(event).await {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            log::warn!("群 {} 的规则匹配失败: {}", event.group_id, err);
+            RuleOutcome::default()
+        }
+    }
+}
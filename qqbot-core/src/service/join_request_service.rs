@@ -0,0 +1,203 @@
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    UserId,
+    config::APPCONFIG,
+    error::{AppError, AppResult},
+    models::join_request::{
+        Model, STATUS_APPROVED, STATUS_APPROVING, STATUS_FAILED, STATUS_PENDING, STATUS_REJECTED,
+        STATUS_REJECTING, SUB_TYPE_INVITE, TYPE_FRIEND,
+    },
+    permission::check_permission,
+    repo::{
+        join_request::{JoinRequestRepo, JoinRequestRepository, NewJoinRequest},
+        student::{StudentRepo, StudentRepository},
+    },
+};
+
+/// 验证信息中最多检查的学号个数
+const MAX_CANDIDATES: usize = 3;
+
+static STUDENT_ID: Lazy<Regex> = Lazy::new(|| Regex::new(r"\d{6,12}").unwrap());
+
+/// 协议端推送的好友或加群申请
+#[derive(Debug, Clone)]
+pub struct IncomingRequest {
+    pub request_type: String,
+    pub sub_type: String,
+    pub flag: String,
+    pub user_id: UserId,
+    pub group_id: Option<i64>,
+    pub comment: String,
+}
+
+/// 好友与加群申请服务：按策略自动审批或交给管理员，已审批的申请由 apply-request 插件执行
+pub struct JoinRequestService {
+    repo: JoinRequestRepo,
+    students: StudentRepo,
+}
+
+impl JoinRequestService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: JoinRequestRepo::new(db.clone()),
+            students: StudentRepo::new(db),
+        }
+    }
+
+    /// 记录申请并按策略给出初步结论：approving 表示自动同意，pending 表示等待管理员审批
+    pub async fn receive(&self, incoming: IncomingRequest) -> AppResult<Model> {
+        let (status, reason) = if incoming.request_type == TYPE_FRIEND || incoming.sub_type != SUB_TYPE_INVITE {
+            self.check_student(&incoming).await?
+        } else {
+            check_invite(&incoming).await
+        };
+        let request = self
+            .repo
+            .create(NewJoinRequest {
+                request_type: incoming.request_type,
+                sub_type: incoming.sub_type,
+                flag: incoming.flag,
+                user_id: incoming.user_id,
+                group_id: incoming.group_id,
+                comment: incoming.comment,
+                status,
+                reason,
+            })
+            .await?;
+        Ok(request)
+    }
+
+    /// 验证信息中包含已录入、且未绑定其他QQ的学号时自动同意
+    async fn check_student(&self, incoming: &IncomingRequest) -> AppResult<(&'static str, String)> {
+        if !APPCONFIG.join_request.require_student_id {
+            return Ok((STATUS_APPROVING, "未开启学号校验".to_string()));
+        }
+        for student_id in candidate_student_ids(&incoming.comment) {
+            if let Some(student) = self.students.find_by_id(student_id).await? {
+                return Ok(if student.qq_number == 0 || student.qq_number == incoming.user_id {
+                    (STATUS_APPROVING, format!("学号 {}（{}）", student_id, student.name))
+                } else {
+                    (STATUS_PENDING, format!("学号 {} 已绑定其他QQ", student_id))
+                });
+            }
+        }
+        Ok((STATUS_PENDING, "验证信息中没有已录入的学号".to_string()))
+    }
+
+    /// 管理员审批，只能审批等待中的申请
+    pub async fn decide(
+        &self,
+        id: i64,
+        approve: bool,
+        reason: Option<String>,
+        handled_by: Option<UserId>,
+    ) -> AppResult<Model> {
+        let status = if approve { STATUS_APPROVING } else { STATUS_REJECTING };
+        let reason = reason.unwrap_or_default().trim().chars().take(255).collect();
+        if !self.repo.decide(id, status, reason, handled_by).await? {
+            return match self.repo.find(id).await? {
+                Some(request) => Err(AppError::validation(format!("申请 #{} 已处理（{}）", id, request.status))),
+                None => Err(AppError::not_found(format!("申请 #{}", id))),
+            };
+        }
+        self.find(id).await
+    }
+
+    pub async fn find(&self, id: i64) -> AppResult<Model> {
+        self.repo
+            .find(id)
+            .await?
+            .ok_or_else(|| AppError::not_found(format!("申请 #{}", id)))
+    }
+
+    /// 取出已审批、等待执行的申请
+    pub async fn next_decided(&self) -> AppResult<Vec<Model>> {
+        let requests = self.repo.find_decided(20).await?;
+        Ok(requests)
+    }
+
+    /// 记录执行结果
+    pub async fn report(&self, request: &Model, result: Result<(), String>) -> AppResult<()> {
+        match result {
+            Ok(()) if request.status == STATUS_APPROVING => self.repo.finish(request.id, STATUS_APPROVED, None).await?,
+            Ok(()) => self.repo.finish(request.id, STATUS_REJECTED, None).await?,
+            Err(err) => self.repo.finish(request.id, STATUS_FAILED, Some(err)).await?,
+        }
+        Ok(())
+    }
+
+    pub async fn list(
+        &self,
+        status: Option<String>,
+        page: u64,
+        limit: u64,
+    ) -> AppResult<(Vec<Model>, u64)> {
+        let result = self.repo.list(status, page, limit).await?;
+        Ok(result)
+    }
+}
+
+/// 白名单中的群或机器人管理员发出的入群邀请自动同意
+async fn check_invite(incoming: &IncomingRequest) -> (&'static str, String) {
+    let allowlisted = incoming
+        .group_id
+        .is_some_and(|group_id| APPCONFIG.join_request.group_allowlist.contains(&group_id));
+    if allowlisted {
+        (STATUS_APPROVING, "群在白名单中".to_string())
+    } else if check_permission(incoming.user_id).await {
+        (STATUS_APPROVING, "机器人管理员邀请".to_string())
+    } else {
+        (STATUS_PENDING, "群不在白名单中".to_string())
+    }
+}
+
+/// 验证信息中可能是学号的数字，QQ 的问答验证一般形如 "问题：学号\n回答：2023123456"
+pub(crate) fn candidate_student_ids(comment: &str) -> Vec<i64> {
+    let mut ids: Vec<i64> = Vec::new();
+    for id in STUDENT_ID.find_iter(comment).filter_map(|m| m.as_str().parse().ok()) {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids.truncate(MAX_CANDIDATES);
+    ids
+}
+
+/// 发给管理员的审批提醒
+pub fn admin_notice(request: &Model) -> String {
+    let kind = match (request.request_type.as_str(), request.group_id) {
+        (TYPE_FRIEND, _) => "好友申请".to_string(),
+        (_, Some(group_id)) if request.sub_type == SUB_TYPE_INVITE => format!("入群邀请（群 {}）", group_id),
+        (_, Some(group_id)) => format!("加群申请（群 {}）", group_id),
+        _ => "申请".to_string(),
+    };
+    format!(
+        "收到{} #{}\nQQ：{}\n验证信息：{}\n待审批原因：{}\n发送 /request approve {} 同意，/request reject {} [理由] 拒绝",
+        kind,
+        request.id,
+        request.user_id,
+        if request.comment.is_empty() { "（无）" } else { &request.comment },
+        request.reason,
+        request.id,
+        request.id
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidate_student_ids() {
+        assert_eq!(
+            candidate_student_ids("问题：请输入学号\n回答：2023123456 2023123456 QQ 12345"),
+            vec![2023123456]
+        );
+        assert!(candidate_student_ids("我是三班的").is_empty());
+    }
+}
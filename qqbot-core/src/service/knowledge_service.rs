@@ -0,0 +1,188 @@
+use std::{collections::HashMap, sync::Arc};
+
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+
+use crate::{
+    config::{APPCONFIG, get_db},
+    error::{AppError, AppResult},
+    knowledge::{
+        chunk::{self, DocumentFormat},
+        embed::{self, EMBEDDER},
+    },
+    models::knowledge_document::Model as DocumentModel,
+    repo::knowledge::{KnowledgeRepo, KnowledgeRepository},
+};
+
+/// 检索命中的片段
+#[derive(Debug, Clone, Serialize)]
+pub struct KnowledgeHit {
+    pub document_id: i64,
+    pub title: String,
+    pub content: String,
+    pub score: f32,
+}
+
+struct IndexedChunk {
+    document_id: i64,
+    title: Arc<str>,
+    content: String,
+    vector: Vec<f32>,
+}
+
+/// 全部片段及其向量的内存索引。管理后台与机器人是不同进程，
+/// 因此除了本进程内上传、删除时主动失效外，还依赖 cache_ttl 定期重新加载
+static INDEX: Lazy<Cache<(), Arc<Vec<IndexedChunk>>>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(1)
+        .time_to_live(APPCONFIG.knowledge.cache_ttl)
+        .build()
+});
+
+/// 课程知识库服务：文档入库（解析、切分、向量化）与相似度检索
+pub struct KnowledgeService {
+    repo: KnowledgeRepo,
+}
+
+impl KnowledgeService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: KnowledgeRepo::new(db),
+        }
+    }
+
+    /// 解析并保存上传的文档，未指定标题时使用去掉扩展名的文件名
+    pub async fn ingest(&self, title: Option<String>, filename: String, bytes: Vec<u8>) -> AppResult<DocumentModel> {
+        let config = &APPCONFIG.knowledge;
+        if bytes.len() > config.max_document_bytes {
+            return Err(AppError::validation(format!("文档过大: {} 字节", bytes.len())));
+        }
+        let format = DocumentFormat::from_filename(&filename)
+            .ok_or_else(|| AppError::validation("仅支持 Markdown、PDF 和纯文本文档"))?;
+
+        let text = tokio::task::spawn_blocking(move || chunk::extract_text(format, &bytes))
+            .await
+            .map_err(|e| AppError::internal(e.to_string()))??;
+        let chunks = chunk::split_chunks(format, &text, config.chunk_chars, config.chunk_overlap);
+        let vectors = EMBEDDER.embed(&chunks).await?;
+
+        let title = title
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or_else(|| filename.rsplit_once('.').map_or(filename.as_str(), |(name, _)| name).to_string());
+        let chunks = chunks
+            .into_iter()
+            .zip(vectors.iter().map(|vector| embed::encode(vector)))
+            .collect();
+        let document = self
+            .repo
+            .create_document(title, filename, format.as_str(), chunks)
+            .await?;
+        INDEX.invalidate_all();
+        Ok(document)
+    }
+
+    pub async fn list(&self) -> AppResult<Vec<DocumentModel>> {
+        let documents = self.repo.list_documents().await?;
+        Ok(documents)
+    }
+
+    pub async fn delete(&self, id: i64) -> AppResult<()> {
+        if !self.repo.delete_document(id).await? {
+            return Err(AppError::not_found(format!("文档 {}", id)));
+        }
+        INDEX.invalidate_all();
+        Ok(())
+    }
+
+    /// 检索与问题最相关的片段
+    pub async fn search(&self, query: &str, top_k: usize) -> AppResult<Vec<KnowledgeHit>> {
+        let index = self.load_index().await?;
+        if index.is_empty() {
+            return Ok(Vec::new());
+        }
+        let query = EMBEDDER.embed(&[query.to_string()]).await?;
+        let Some(query) = query.first() else {
+            return Ok(Vec::new());
+        };
+
+        let mut hits: Vec<KnowledgeHit> = index
+            .iter()
+            .map(|chunk| KnowledgeHit {
+                document_id: chunk.document_id,
+                title: chunk.title.to_string(),
+                content: chunk.content.clone(),
+                score: embed::cosine(query, &chunk.vector),
+            })
+            .filter(|hit| hit.score >= APPCONFIG.knowledge.min_score)
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(top_k);
+        Ok(hits)
+    }
+
+    async fn load_index(&self) -> AppResult<Arc<Vec<IndexedChunk>>> {
+        if let Some(index) = INDEX.get(&()).await {
+            return Ok(index);
+        }
+        let titles: HashMap<i64, Arc<str>> = self
+            .repo
+            .list_documents()
+            .await?
+            .into_iter()
+            .map(|document| (document.id, Arc::from(document.title)))
+            .collect();
+        let index: Vec<IndexedChunk> = self
+            .repo
+            .all_chunks()
+            .await?
+            .into_iter()
+            .filter_map(|chunk| {
+                Some(IndexedChunk {
+                    document_id: chunk.document_id,
+                    title: titles.get(&chunk.document_id)?.clone(),
+                    vector: embed::decode(&chunk.embedding),
+                    content: chunk.content,
+                })
+            })
+            .collect();
+        let index = Arc::new(index);
+        INDEX.insert((), index.clone()).await;
+        Ok(index)
+    }
+}
+
+/// 检索当前问题相关的课程资料，未启用或出错时返回空列表，不影响正常回复
+pub async fn retrieve(query: &str) -> Vec<KnowledgeHit> {
+    if !APPCONFIG.knowledge.enabled || query.trim().is_empty() {
+        return Vec::new();
+    }
+    match KnowledgeService::new(get_db().await)
+        .search(query, APPCONFIG.knowledge.top_k)
+        .await
+    {
+        Ok(hits) => hits,
+        Err(err) => {
+            log::warn!("知识库检索失败: {}", err);
+            Vec::new()
+        }
+    }
+}
+
+/// 把检索到的片段拼接到系统提示词中，要求模型按编号注明出处
+pub fn system_prompt_with_knowledge(system_prompt: &str, hits: &[KnowledgeHit]) -> String {
+    if hits.is_empty() {
+        return system_prompt.to_string();
+    }
+    let references = hits
+        .iter()
+        .enumerate()
+        .map(|(i, hit)| format!("[{}]《{}》\n{}", i + 1, hit.title, hit.content))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    format!(
+        "{}\n\n以下是课程资料中与问题相关的片段。回答时优先依据这些资料，在用到的内容后用 [编号] 注明出处，并在末尾列出引用的资料名称；资料中没有的内容请说明不确定，不要编造：\n{}",
+        system_prompt, references
+    )
+}
@@ -0,0 +1,222 @@
+use std::{collections::HashSet, sync::Arc};
+
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    UserId,
+    config::{APPCONFIG, get_db},
+    error::{AppError, AppResult},
+    models::user_memory::{Model, SOURCE_AUTO},
+    repo::user_memory::{UserMemoryRepo, UserMemoryRepository},
+    reply_strategy::context,
+};
+
+/// 单条记忆的最大字数
+pub const MAX_MEMORY_CHARS: usize = 200;
+
+/// 用户长期记忆服务：保存用户主动告知或从对话中提取的信息，回复时按相关度注入提示词
+pub struct MemoryService {
+    repo: UserMemoryRepo,
+}
+
+impl MemoryService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: UserMemoryRepo::new(db),
+        }
+    }
+
+    /// 添加一条记忆，重复内容直接返回已有记录；达到上限时淘汰最早的自动记忆
+    pub async fn add(&self, user_id: UserId, content: &str, source: &str) -> AppResult<Model> {
+        let content = content.trim();
+        if content.is_empty() {
+            return Err(AppError::validation("记忆内容不能为空"));
+        }
+        if content.chars().count() > MAX_MEMORY_CHARS {
+            return Err(AppError::validation(format!("记忆内容不能超过{}字", MAX_MEMORY_CHARS)));
+        }
+
+        let memories = self.repo.list_by_user(user_id).await?;
+        if let Some(existing) = memories.iter().find(|m| m.content == content) {
+            return Ok(existing.clone());
+        }
+        if memories.len() >= APPCONFIG.memory.max_per_user {
+            let oldest_auto = memories.iter().find(|m| m.source == SOURCE_AUTO);
+            match oldest_auto {
+                Some(oldest) => {
+                    self.repo.delete(user_id, oldest.id).await?;
+                }
+                None => {
+                    return Err(AppError::validation(format!(
+                        "记忆已达上限（{}条），请先使用 /memory forget 删除",
+                        APPCONFIG.memory.max_per_user
+                    )));
+                }
+            }
+        }
+
+        let memory = self.repo.add(user_id, content.to_string(), source).await?;
+        Ok(memory)
+    }
+
+    pub async fn list(&self, user_id: UserId) -> AppResult<Vec<Model>> {
+        let memories = self.repo.list_by_user(user_id).await?;
+        Ok(memories)
+    }
+
+    /// 删除一条记忆，只能删除自己的
+    pub async fn forget(&self, user_id: UserId, id: i64) -> AppResult<()> {
+        if !self.repo.delete(user_id, id).await? {
+            return Err(AppError::not_found(format!("记忆 #{}", id)));
+        }
+        Ok(())
+    }
+
+    /// 清空用户的全部记忆，返回删除的条数
+    pub async fn forget_all(&self, user_id: UserId) -> AppResult<u64> {
+        let count = self.repo.delete_all(user_id).await?;
+        Ok(count)
+    }
+
+    /// 取出与当前消息最相关的记忆
+    pub async fn relevant(&self, user_id: UserId, query: &str) -> AppResult<Vec<Model>> {
+        let memories = self.repo.list_by_user(user_id).await?;
+        Ok(rank_memories(memories, query, APPCONFIG.memory.inject_limit))
+    }
+
+    /// 让模型从用户消息中提取值得长期记住的信息并保存
+    pub async fn extract(&self, user_id: UserId, message: &str) -> AppResult<Vec<Model>> {
+        let known = self.repo.list_by_user(user_id).await?;
+        let known = known
+            .iter()
+            .map(|m| format!("- {}", m.content))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let prompt = format!(
+            "从用户的这条消息中提取值得长期记住的个人信息（如身份、班级、偏好、长期目标），\
+             忽略一次性的问题和闲聊，不要重复已知信息。\
+             只输出 JSON 字符串数组，每条不超过50字，没有则输出 []。\n\n已知信息：\n{}\n\n用户消息：\n{}",
+            if known.is_empty() { "（无）" } else { &known },
+            message
+        );
+        let response = context::complete(prompt, 0.2).await.map_err(AppError::reply)?;
+
+        let mut saved = Vec::new();
+        for fact in parse_facts(&response) {
+            saved.push(self.add(user_id, &fact, SOURCE_AUTO).await?);
+        }
+        Ok(saved)
+    }
+}
+
+/// 把记忆拼接到系统提示词中
+pub fn system_prompt_with_memories(system_prompt: &str, memories: &[Model]) -> String {
+    if memories.is_empty() {
+        return system_prompt.to_string();
+    }
+    let facts = memories
+        .iter()
+        .map(|m| format!("- {}", m.content))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("{}\n\n关于该用户的已知信息：\n{}", system_prompt, facts)
+}
+
+/// 取出当前消息相关的记忆，失败时返回空列表，不影响正常回复
+pub async fn relevant_memories(user_id: UserId, query: &str) -> Vec<Model> {
+    if !APPCONFIG.memory.enabled {
+        return Vec::new();
+    }
+    match MemoryService::new(get_db().await).relevant(user_id, query).await {
+        Ok(memories) => memories,
+        Err(err) => {
+            log::warn!("读取用户 {} 的记忆失败: {}", user_id, err);
+            Vec::new()
+        }
+    }
+}
+
+/// 在后台从用户消息中提取记忆
+pub fn schedule_extract(user_id: UserId, message: String) {
+    if !APPCONFIG.memory.enabled || !APPCONFIG.memory.auto_extract || message.trim().is_empty() {
+        return;
+    }
+    tokio::spawn(async move {
+        if let Err(err) = MemoryService::new(get_db().await).extract(user_id, &message).await {
+            log::warn!("提取用户 {} 的记忆失败: {}", user_id, err);
+        }
+    });
+}
+
+/// 按字符二元组的重合度排序，相同得分时较新的记忆优先。
+/// 单个用户的记忆不多，得分为 0 的记忆也会用来补足数量
+pub fn rank_memories(memories: Vec<Model>, query: &str, limit: usize) -> Vec<Model> {
+    let query = bigrams(query);
+    let mut scored: Vec<(usize, Model)> = memories
+        .into_iter()
+        .map(|m| (bigrams(&m.content).intersection(&query).count(), m))
+        .collect();
+    scored.sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then(b.id.cmp(&a.id)));
+    scored.into_iter().take(limit).map(|(_, m)| m).collect()
+}
+
+fn bigrams(text: &str) -> HashSet<(char, char)> {
+    let chars: Vec<char> = text
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect();
+    chars.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+/// 解析模型输出的 JSON 数组，容忍代码块包裹
+fn parse_facts(response: &str) -> Vec<String> {
+    let start = response.find('[');
+    let end = response.rfind(']');
+    let (Some(start), Some(end)) = (start, end) else {
+        return Vec::new();
+    };
+    if start >= end {
+        return Vec::new();
+    }
+    serde_json::from_str::<Vec<String>>(&response[start..=end])
+        .unwrap_or_default()
+        .into_iter()
+        .map(|fact| fact.trim().to_string())
+        .filter(|fact| !fact.is_empty() && fact.chars().count() <= MAX_MEMORY_CHARS)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::user_memory::SOURCE_MANUAL;
+    use chrono::Utc;
+
+    fn memory(id: i64, content: &str) -> Model {
+        Model {
+            id,
+            user_id: 1,
+            content: content.to_string(),
+            source: SOURCE_MANUAL.to_string(),
+            created_at: Utc::now().into(),
+        }
+    }
+
+    #[test]
+    fn test_rank_and_parse() {
+        let memories = vec![
+            memory(1, "在三班"),
+            memory(2, "喜欢用英文交流"),
+            memory(3, "目标是数学考到满分"),
+        ];
+        let ranked = rank_memories(memories, "我这次数学考了多少分", 2);
+        assert_eq!(ranked.iter().map(|m| m.id).collect::<Vec<_>>(), vec![3, 2]);
+
+        assert_eq!(
+            parse_facts("```json\n[\"在三班\", \"\"]\n```"),
+            vec!["在三班".to_string()]
+        );
+        assert!(parse_facts("没有").is_empty());
+    }
+}
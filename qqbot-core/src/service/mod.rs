@@ -1,7 +1,19 @@
 pub mod student_service;
 pub use student_service::*;
+pub mod content_filter_service;
+pub mod faq_service;
 pub mod grade_service;
+pub mod group_command_policy_service;
 pub mod group_config_service;
+pub mod group_rule_service;
+pub mod join_request_service;
+pub mod knowledge_service;
+pub mod memory_service;
+pub mod moderation_service;
+pub mod notify_service;
+pub mod onboarding_service;
+pub mod permission_service;
+pub mod template_service;
 pub mod user_config_service;
 
 // 重新导出新的错误类型
@@ -0,0 +1,177 @@
+use std::{sync::Arc, time::Duration};
+
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    GroupId, UserId,
+    action::{self, ActionExecutor, BotAction},
+    error::{AppError, AppResult},
+    models::moderation_log::{
+        ACTION_KICK, ACTION_MUTE, ACTION_RECALL, ACTION_UNMUTE, ACTION_WHOLE_MUTE, Model,
+    },
+    permission::{MemberRole, Role},
+    repo::moderation_log::{ModerationLogRepo, ModerationLogRepository, NewModerationLog},
+};
+
+/// 群管理操作
+#[derive(Debug, Clone)]
+pub enum Moderation {
+    Mute { user_id: UserId, duration: Duration },
+    Unmute { user_id: UserId },
+    Kick { user_id: UserId, reject: bool },
+    Recall { message_id: i64 },
+    WholeMute { enable: bool },
+}
+
+impl Moderation {
+    fn kind(&self) -> &'static str {
+        match self {
+            Moderation::Mute { .. } => ACTION_MUTE,
+            Moderation::Unmute { .. } => ACTION_UNMUTE,
+            Moderation::Kick { .. } => ACTION_KICK,
+            Moderation::Recall { .. } => ACTION_RECALL,
+            Moderation::WholeMute { .. } => ACTION_WHOLE_MUTE,
+        }
+    }
+
+    fn target(&self) -> Option<UserId> {
+        match self {
+            Moderation::Mute { user_id, .. }
+            | Moderation::Unmute { user_id }
+            | Moderation::Kick { user_id, .. } => Some(*user_id),
+            _ => None,
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            Moderation::Mute { duration, .. } => humantime::format_duration(*duration).to_string(),
+            Moderation::Kick { reject: true, .. } => "拒绝再次加群".to_string(),
+            Moderation::Recall { message_id } => message_id.to_string(),
+            Moderation::WholeMute { enable } => if *enable { "on" } else { "off" }.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    fn into_action(self, group_id: GroupId) -> BotAction {
+        match self {
+            Moderation::Mute { user_id, duration } => BotAction::Mute { group_id, user_id, duration },
+            Moderation::Unmute { user_id } => BotAction::Mute { group_id, user_id, duration: Duration::ZERO },
+            Moderation::Kick { user_id, reject } => BotAction::Kick { group_id, user_id, reject },
+            Moderation::Recall { message_id } => BotAction::Recall { message_id },
+            Moderation::WholeMute { enable } => BotAction::WholeMute { group_id, enable },
+        }
+    }
+}
+
+/// 发起操作的管理员
+#[derive(Debug, Clone, Copy)]
+pub struct Operator {
+    pub group_id: GroupId,
+    pub user_id: UserId,
+    pub self_id: UserId,
+    // 发起人在本群的角色，机器人管理员为 Role::BotAdmin
+    pub role: Role,
+}
+
+/// 群管理服务：检查权限后通过插件注册的执行器禁言、踢人、撤回，并记录审计日志
+pub struct ModerationService {
+    repo: ModerationLogRepo,
+}
+
+impl ModerationService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: ModerationLogRepo::new(db),
+        }
+    }
+
+    /// 执行群管理操作。通过权限检查后的每次尝试都会记录，无论成功与否
+    pub async fn moderate(&self, operator: Operator, moderation: Moderation) -> AppResult<Model> {
+        if !operator.role.is_manager() {
+            return Err(AppError::permission("只有群管理员或机器人管理员可以使用此命令"));
+        }
+
+        let mut log = NewModerationLog {
+            group_id: operator.group_id,
+            operator_id: operator.user_id,
+            action: moderation.kind(),
+            target_id: moderation.target(),
+            detail: moderation.detail(),
+            error: None,
+        };
+        let result = self.execute(&operator, moderation).await;
+        log.error = result.as_ref().err().map(|err| err.to_string());
+        let record = self.repo.create(log).await?;
+        result.map(|_| record)
+    }
+
+    async fn execute(&self, operator: &Operator, moderation: Moderation) -> AppResult<()> {
+        let executor = action::executor()?;
+        let bot_role = executor.member_role(operator.group_id, operator.self_id).await?;
+        if !bot_role.is_manager() {
+            return Err(AppError::permission("机器人不是本群管理员，无法执行该操作"));
+        }
+        if let Some(target) = moderation.target() {
+            check_target(executor.as_ref(), operator, bot_role, target).await?;
+        }
+        executor.execute(moderation.into_action(operator.group_id)).await
+    }
+
+    pub async fn list(
+        &self,
+        group_id: Option<GroupId>,
+        page: u64,
+        limit: u64,
+    ) -> AppResult<(Vec<Model>, u64)> {
+        let result = self.repo.list(group_id, page, limit).await?;
+        Ok(result)
+    }
+}
+
+/// 机器人只能处理角色比自己低的成员，也不处理自己和发起人；
+/// 群管理员也只能处理角色比自己低的成员
+async fn check_target(
+    executor: &dyn ActionExecutor,
+    operator: &Operator,
+    bot_role: MemberRole,
+    target: UserId,
+) -> AppResult<()> {
+    if target == operator.self_id {
+        return Err(AppError::validation("不能对机器人自己执行该操作"));
+    }
+    if target == operator.user_id {
+        return Err(AppError::validation("不能对自己执行该操作"));
+    }
+    let target_role = executor.member_role(operator.group_id, target).await?;
+    check_rank(operator.role, bot_role, target_role)
+}
+
+/// 目标的角色需低于机器人，并且低于发起人（群主和机器人管理员不受后者限制）
+fn check_rank(operator_role: Role, bot_role: MemberRole, target_role: MemberRole) -> AppResult<()> {
+    if target_role >= bot_role {
+        return Err(AppError::permission("机器人无法处理群主或其他管理员"));
+    }
+    let outranks = matches!(operator_role, Role::Owner | Role::BotAdmin) || Role::from(target_role) < operator_role;
+    if !outranks {
+        return Err(AppError::permission("不能处理与自己同级或更高级别的成员"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_rank() {
+        // 机器人是群主时，群管理员也不能处理其他管理员
+        assert!(check_rank(Role::Admin, MemberRole::Owner, MemberRole::Admin).is_err());
+        assert!(check_rank(Role::Admin, MemberRole::Owner, MemberRole::Member).is_ok());
+        assert!(check_rank(Role::Owner, MemberRole::Owner, MemberRole::Admin).is_ok());
+        assert!(check_rank(Role::BotAdmin, MemberRole::Owner, MemberRole::Admin).is_ok());
+        // 机器人只是管理员时谁都不能处理其他管理员
+        assert!(check_rank(Role::BotAdmin, MemberRole::Admin, MemberRole::Admin).is_err());
+        assert!(check_rank(Role::Member, MemberRole::Owner, MemberRole::Member).is_err());
+    }
+}
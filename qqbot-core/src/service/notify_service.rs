@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+
+use super::template_service::TemplateService;
+use crate::{
+    config::APPCONFIG,
+    error::AppResult,
+    models::{grade::Model as GradeModel, grade_notification::Model, student::Model as StudentModel},
+    repo::{
+        grade_notification::{GradeNotificationRepo, GradeNotificationRepository},
+        student::{StudentRepo, StudentRepository},
+    },
+    template::NOTIFY_GRADE_PUBLISHED,
+};
+
+/// 成绩发布通知服务：负责排队、取批次以及记录投递结果。
+/// 实际的消息发送由 push 插件完成，这里只维护 grade_notification 表。
+pub struct GradeNotifyService {
+    repo: GradeNotificationRepo,
+    students: StudentRepo,
+    templates: TemplateService,
+}
+
+impl GradeNotifyService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: GradeNotificationRepo::new(db.clone()),
+            students: StudentRepo::new(db.clone()),
+            templates: TemplateService::new(db),
+        }
+    }
+
+    /// 为新发布的成绩登记一条私聊通知，学生不存在或尚未绑定QQ时跳过
+    pub async fn enqueue(&self, grade: &GradeModel) -> AppResult<Option<Model>> {
+        let student = self.students.find_by_id(grade.student_id).await?;
+        let Some(qq) = recipient(student.as_ref()) else {
+            return Ok(None);
+        };
+
+        let notification = self.repo.enqueue(grade, qq).await?;
+        Ok(Some(notification))
+    }
+
+    /// 取出下一批待发送的通知
+    pub async fn next_batch(&self) -> AppResult<Vec<Model>> {
+        let batch = self.repo.find_pending(APPCONFIG.notify.batch_size).await?;
+        Ok(batch)
+    }
+
+    /// 记录一次投递结果，失败次数达到上限后不再重试
+    pub async fn report(&self, id: i64, result: Result<(), String>) -> AppResult<()> {
+        match result {
+            Ok(()) => self.repo.mark_sent(id).await?,
+            Err(err) => {
+                self.repo
+                    .mark_failed(id, err, APPCONFIG.notify.max_attempts)
+                    .await?
+            }
+        }
+        Ok(())
+    }
+
+    /// 将通知重新放回待发送队列
+    pub async fn retry(&self, id: i64) -> AppResult<Model> {
+        let notification = self.repo.reset(id).await?;
+        Ok(notification)
+    }
+
+    /// 分页查询通知记录，供管理后台展示投递结果
+    pub async fn list(
+        &self,
+        status: Option<String>,
+        page: u64,
+        limit: u64,
+    ) -> AppResult<(Vec<Model>, u64)> {
+        let result = self.repo.list(status, page, limit).await?;
+        Ok(result)
+    }
+
+    /// 按 notify.grade_published 模板生成发给学生的通知文本
+    pub async fn render(&self, notification: &Model) -> AppResult<String> {
+        let ctx = json!({
+            "student": {
+                "name": notification.student_name,
+                "student_id": notification.student_id,
+                "qq_number": notification.qq_number,
+            },
+            "grade": {
+                "id": notification.grade_id,
+                "exam_name": notification.exam_name,
+                "score": notification.score,
+            },
+        });
+        self.templates.render(NOTIFY_GRADE_PUBLISHED, ctx).await
+    }
+}
+
+/// 通知的接收人：学生存在且已绑定QQ时为其QQ号
+fn recipient(student: Option<&StudentModel>) -> Option<i64> {
+    student.map(|s| s.qq_number).filter(|&qq| qq != 0)
+}
+
+/// 成绩发布钩子：开启通知时为该成绩排队，失败只记录日志，不影响成绩写入
+pub async fn notify_grade_published(db: Arc<DatabaseConnection>, grade: &GradeModel) {
+    if !APPCONFIG.notify.grade_enabled {
+        return;
+    }
+
+    if let Err(err) = GradeNotifyService::new(db).enqueue(grade).await {
+        log::warn!("failed to enqueue grade notification for {}: {}", grade.id, err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn test_recipient_skips_unbound_students() {
+        assert_eq!(recipient(Some(&fixtures::student(10001, 0))), Some(10001));
+        assert_eq!(recipient(Some(&fixtures::student(0, 0))), None);
+        assert_eq!(recipient(None), None);
+    }
+}
@@ -0,0 +1,152 @@
+use std::sync::Arc;
+
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    BOT_CACHE, StrategeType, UserId,
+    config::{APPCONFIG, get_db},
+    error::AppResult,
+    models::onboarding::{STEP_DONE, STEP_STUDENT_ID},
+    repo::{
+        onboarding::{OnboardingRepo, OnboardingRepository},
+        student::{StudentRepo, StudentRepository},
+    },
+    service::join_request_service::candidate_student_ids,
+};
+
+/// 发送这些内容时跳过绑定
+const SKIP_WORDS: [&str; 3] = ["跳过", "skip", "算了"];
+
+/// 新好友引导：询问学号并绑定，再介绍常用命令和当前回复模式。
+/// 进度保存在数据库中，机器人重启后用户的下一条私聊消息会继续引导
+pub struct OnboardingService {
+    repo: OnboardingRepo,
+    students: StudentRepo,
+}
+
+impl OnboardingService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: OnboardingRepo::new(db.clone()),
+            students: StudentRepo::new(db),
+        }
+    }
+
+    /// 开始引导，返回第一条欢迎消息。已绑定学号的用户直接介绍命令
+    pub async fn start(&self, user_id: UserId) -> AppResult<String> {
+        if let Some(student) = self.students.find_by_qq(user_id).await? {
+            self.repo.save(user_id, STEP_DONE, 0).await?;
+            return Ok(format!(
+                "欢迎使用 wbot！你已绑定学号 {}（{}）。\n{}",
+                student.student_id,
+                student.name,
+                guide(user_id).await
+            ));
+        }
+        self.repo.save(user_id, STEP_STUDENT_ID, 0).await?;
+        Ok("欢迎使用 wbot！请直接发送你的学号完成绑定，绑定后可以查询成绩、接收成绩通知。\n暂时不想绑定可以发送“跳过”。".to_string())
+    }
+
+    /// 处理引导中用户的私聊消息，用户不在引导中或发送的是命令时返回 None，按普通消息处理
+    pub async fn handle(&self, user_id: UserId, text: &str) -> AppResult<Option<String>> {
+        let Some(progress) = self.repo.find(user_id).await? else {
+            return Ok(None);
+        };
+        let text = text.trim();
+        if progress.step == STEP_DONE || text.is_empty() || text.starts_with(&APPCONFIG.cmd_suffix) {
+            return Ok(None);
+        }
+
+        // 引导期间用户可能已用 /bind 自行绑定
+        if let Some(student) = self.students.find_by_qq(user_id).await? {
+            return self.finish(user_id, format!("你已绑定学号 {}（{}）。", student.student_id, student.name)).await;
+        }
+        if SKIP_WORDS.iter().any(|word| text.eq_ignore_ascii_case(word)) {
+            return self
+                .finish(user_id, format!("已跳过绑定，之后可以发送 {}bind 学号 再绑定。", APPCONFIG.cmd_suffix))
+                .await;
+        }
+
+        let problem = match candidate_student_ids(text).first() {
+            None => "没有识别到学号".to_string(),
+            Some(&student_id) => match self.students.find_by_id(student_id).await? {
+                None => format!("学号 {} 不在名单中", student_id),
+                Some(student) if student.qq_number != 0 && student.qq_number != user_id => {
+                    format!("学号 {} 已被其他QQ绑定，如有疑问请联系老师", student_id)
+                }
+                Some(student) => {
+                    self.students.update_qq(student_id, user_id).await?;
+                    return self
+                        .finish(user_id, format!("绑定成功：{}（{}）。", student.name, student_id))
+                        .await;
+                }
+            },
+        };
+
+        let attempts = progress.attempts + 1;
+        if attempts >= APPCONFIG.onboarding.max_attempts {
+            return self
+                .finish(
+                    user_id,
+                    format!("{}，暂不绑定。之后可以发送 {}bind 学号 再绑定。", problem, APPCONFIG.cmd_suffix),
+                )
+                .await;
+        }
+        self.repo.save(user_id, STEP_STUDENT_ID, attempts).await?;
+        Ok(Some(format!("{}，请重新发送学号，或发送“跳过”。", problem)))
+    }
+
+    async fn finish(&self, user_id: UserId, message: String) -> AppResult<Option<String>> {
+        self.repo.save(user_id, STEP_DONE, 0).await?;
+        Ok(Some(format!("{}\n{}", message, guide(user_id).await)))
+    }
+}
+
+/// 引导中的用户发来私聊消息时返回引导回复，出错时记录日志并按普通消息处理
+pub async fn intercept(user_id: UserId, text: &str) -> Option<String> {
+    if !APPCONFIG.onboarding.enabled {
+        return None;
+    }
+    match OnboardingService::new(get_db().await).handle(user_id, text).await {
+        Ok(reply) => reply,
+        Err(err) => {
+            log::warn!("处理用户 {} 的引导消息失败: {}", user_id, err);
+            None
+        }
+    }
+}
+
+/// 常用命令和用户当前的回复模式
+async fn guide(user_id: UserId) -> String {
+    let strategy = BOT_CACHE.get(&user_id).await.unwrap_or_default().stratege;
+    guide_text(&APPCONFIG.cmd_suffix, &strategy)
+}
+
+fn guide_text(prefix: &str, strategy: &StrategeType) -> String {
+    let mode = match strategy {
+        StrategeType::LlmStrategy => format!(
+            "当前为聊天模式：直接发消息就能和我聊天，以 {p} 开头的消息按命令处理。发送 {p}strategy cmd 切换到命令模式",
+            p = prefix
+        ),
+        StrategeType::CmdStrategy => format!(
+            "当前为命令模式：只回复以 {p} 开头的命令。发送 {p}strategy llm 切换到聊天模式",
+            p = prefix
+        ),
+    };
+    format!(
+        "常用命令：\n{p}query grade 查询成绩\n{p}bind 学号 绑定学号，{p}bind --clear 解除绑定\n{p}memory list 查看我记住的关于你的信息\n{p}strategy query 查看当前回复设置\n{mode}",
+        p = prefix,
+        mode = mode
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guide_text_mentions_switch() {
+        assert!(guide_text("/", &StrategeType::LlmStrategy).contains("/strategy cmd"));
+        assert!(guide_text("#", &StrategeType::CmdStrategy).contains("#strategy llm"));
+    }
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use sea_orm::DatabaseConnection;
+
+use crate::{
+    UserId,
+    error::{AppError, AppResult},
+    models::permission_grant::{Model, ROLE_SUPER_ADMIN, ROLE_TA, ROLE_TEACHER},
+    permission,
+    repo::permission_grant::{PermissionGrantRepo, PermissionGrantRepository},
+};
+
+/// 权限服务：超级管理员授予或撤销超级管理员、教师（按课程）和助教（按群）
+pub struct PermissionService {
+    repo: PermissionGrantRepo,
+}
+
+impl PermissionService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: PermissionGrantRepo::new(db),
+        }
+    }
+
+    pub async fn list(&self, user_id: Option<UserId>) -> AppResult<Vec<Model>> {
+        let grants = match user_id {
+            Some(user_id) => self.repo.list_by_user(user_id).await?,
+            None => self.repo.list().await?,
+        };
+        Ok(grants)
+    }
+
+    pub async fn grant(
+        &self,
+        user_id: UserId,
+        role: &str,
+        scope_id: Option<i64>,
+        operator: UserId,
+    ) -> AppResult<Model> {
+        let scope_id = validate_scope(role, scope_id)?;
+        let grant = self
+            .repo
+            .grant(user_id, role, scope_id, operator)
+            .await?
+            .ok_or_else(|| AppError::validation(format!("{} 已有该权限", user_id)))?;
+        permission::invalidate(user_id).await;
+        Ok(grant)
+    }
+
+    pub async fn revoke(&self, user_id: UserId, role: &str, scope_id: Option<i64>) -> AppResult<()> {
+        let scope_id = validate_scope(role, scope_id)?;
+        if !self.repo.revoke(user_id, role, scope_id).await? {
+            return Err(AppError::not_found(format!("{} 的 {} 权限", user_id, role)));
+        }
+        permission::invalidate(user_id).await;
+        Ok(())
+    }
+}
+
+/// 教师和助教必须指定课程号或群号，超级管理员不区分范围
+fn validate_scope(role: &str, scope_id: Option<i64>) -> AppResult<i64> {
+    match (role, scope_id) {
+        (ROLE_SUPER_ADMIN, _) => Ok(0),
+        (ROLE_TEACHER, Some(course_id)) if course_id > 0 => Ok(course_id),
+        (ROLE_TEACHER, _) => Err(AppError::validation("教师需要指定课程号")),
+        (ROLE_TA, Some(group_id)) if group_id > 0 => Ok(group_id),
+        (ROLE_TA, _) => Err(AppError::validation("助教需要指定群号")),
+        _ => Err(AppError::validation(format!(
+            "未知角色 {}，可选 {}, {}, {}",
+            role, ROLE_SUPER_ADMIN, ROLE_TEACHER, ROLE_TA
+        ))),
+    }
+}
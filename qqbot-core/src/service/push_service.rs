@@ -137,9 +137,6 @@ use std::{future::Future, pin::Pin};
 #[command(name = "push")]
 #[command(about = "推送消息到群成员（私聊中使用，需要群管理员权限）")]
 pub struct PushCommand {
-    #[command(flatten)]
-    pub common: crate::cmd::CommonArgs,
-
     #[arg(short = 'g', long, help = "目标群号")]
     pub group_id: i64,
 
@@ -152,38 +149,36 @@ pub struct PushCommand {
 
 impl HandlerBuilder for PushCommand {
     fn build() -> CmdHandler {
-        Box::new(|args: Vec<String>| {
+        Box::new(|ctx: CmdContext, args: Vec<String>| {
             Box::pin(async move {
                 let push = PushCommand::try_parse_from(std::iter::once("push".to_string()).chain(args))
                     .map_err(|e| AppError::command(e.to_string()))?;
 
                 // 只能在私聊中使用
-                if push.common.env != "private" {
+                if ctx.env != "private" {
                     return Err(AppError::command("❌ 此命令只能在私聊中使用".to_string()));
                 }
 
                 let request = PushRequest {
-                    sender_id: push.common.sender,
+                    sender_id: ctx.sender,
                     group_id: push.group_id,
                     message: push.message,
                     target_members: push.members,
                 };
 
                 match PushService::push_messages(request).await {
-                    Ok(result) => Ok(CmdResult {
-                        output: format!(
-                            "📤 推送结果：\n{}\n\n📊 详细统计：\n• 成功：{}条\n• 失败：{}条\n• 总计：{}条{}",
-                            result.message,
-                            result.success_count,
-                            result.failed_count,
-                            result.total_count,
-                            if !result.failed_members.is_empty() {
-                                format!("\n\n❌ 失败详情：\n{}", result.failed_members.join("\n"))
-                            } else {
-                                String::new()
-                            }
-                        ),
-                    }),
+                    Ok(result) => Ok(CmdResult::text(format!(
+                        "📤 推送结果：\n{}\n\n📊 详细统计：\n• 成功：{}条\n• 失败：{}条\n• 总计：{}条{}",
+                        result.message,
+                        result.success_count,
+                        result.failed_count,
+                        result.total_count,
+                        if !result.failed_members.is_empty() {
+                            format!("\n\n❌ 失败详情：\n{}", result.failed_members.join("\n"))
+                        } else {
+                            String::new()
+                        }
+                    ))),
                     Err(e) => Err(e),
                 }
             }) as Pin<Box<dyn Future<Output = Result<CmdResult, AppError>> + Send>>
@@ -58,7 +58,7 @@ pub async fn create_student(
     group_id: i64,
 ) -> AppResult<Model> {
     use crate::config::get_db;
-    use crate::models::student::{ActiveModel, Entity as Student};
+    use crate::models::student::ActiveModel;
     use sea_orm::{ActiveModelTrait, Set};
 
     let db = get_db().await;
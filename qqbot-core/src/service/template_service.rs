@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use moka::future::Cache;
+use once_cell::sync::Lazy;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+
+use crate::{
+    config::APPCONFIG,
+    error::{AppError, AppResult},
+    models::student::Model as StudentModel,
+    repo::message_template::{MessageTemplateRepo, MessageTemplateRepository},
+    template::{self, DEFAULT_TEMPLATES, PUSH_MESSAGE},
+};
+
+// 模板内容缓存，避免每次渲染都查询数据库
+pub static TEMPLATE_CACHE: Lazy<Cache<String, String>> = Lazy::new(|| {
+    Cache::builder()
+        .max_capacity(DEFAULT_TEMPLATES.len() as u64 * 4)
+        .time_to_live(APPCONFIG.cache.cache_lifetime)
+        .build()
+});
+
+/// 管理后台展示用的模板信息
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateEntry {
+    pub key: String,
+    pub description: String,
+    pub content: String,
+    pub default_content: String,
+    pub customized: bool,
+}
+
+pub struct TemplateService {
+    repo: MessageTemplateRepo,
+}
+
+impl TemplateService {
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self {
+            repo: MessageTemplateRepo::new(db),
+        }
+    }
+
+    /// 获取模板内容：数据库中的自定义模板优先，否则使用内置模板
+    pub async fn get(&self, key: &str) -> AppResult<String> {
+        if let Some(content) = TEMPLATE_CACHE.get(key).await {
+            return Ok(content);
+        }
+
+        let def = template::default_template(key)
+            .ok_or_else(|| AppError::not_found(format!("模板: {}", key)))?;
+        let content = match self.repo.find_by_key(key).await? {
+            Some(model) => model.content,
+            None => def.content.to_string(),
+        };
+
+        TEMPLATE_CACHE.insert(key.to_string(), content.clone()).await;
+        Ok(content)
+    }
+
+    /// 按模板标识渲染消息
+    pub async fn render<S: Serialize>(&self, key: &str, ctx: S) -> AppResult<String> {
+        let source = self.get(key).await?;
+        template::render_str(&source, ctx)
+    }
+
+    /// 渲染推送给单个成员的消息。管理员输入的内容只作为 `message` 变量传入
+    /// push.message 模板，不会被当作模板编译；个性化内容（如 `{{ student.name }}`）
+    /// 写在后台的 push.message 模板中
+    pub async fn render_push(
+        &self,
+        message: &str,
+        member_qq: i64,
+        student: Option<StudentModel>,
+    ) -> AppResult<String> {
+        self.render(
+            PUSH_MESSAGE,
+            minijinja::context! {
+                message => message,
+                student => student,
+                member => minijinja::context! { qq => member_qq },
+            },
+        )
+        .await
+    }
+
+    /// 列出所有模板及其当前内容
+    pub async fn list(&self) -> AppResult<Vec<TemplateEntry>> {
+        let customized = self.repo.list().await?;
+
+        let entries = DEFAULT_TEMPLATES
+            .iter()
+            .map(|def| {
+                let custom = customized.iter().find(|m| m.template_key == def.key);
+                TemplateEntry {
+                    key: def.key.to_string(),
+                    description: def.description.to_string(),
+                    content: custom
+                        .map(|m| m.content.clone())
+                        .unwrap_or_else(|| def.content.to_string()),
+                    default_content: def.content.to_string(),
+                    customized: custom.is_some(),
+                }
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    /// 保存自定义模板，保存前检查语法
+    pub async fn save(&self, key: &str, content: &str) -> AppResult<()> {
+        if template::default_template(key).is_none() {
+            return Err(AppError::not_found(format!("模板: {}", key)));
+        }
+        template::validate(content)?;
+
+        self.repo.upsert(key, content).await?;
+        TEMPLATE_CACHE.insert(key.to_string(), content.to_string()).await;
+        Ok(())
+    }
+
+    /// 删除自定义内容，恢复为内置模板
+    pub async fn reset(&self, key: &str) -> AppResult<()> {
+        self.repo.delete(key).await?;
+        TEMPLATE_CACHE.remove(key).await;
+        Ok(())
+    }
+}
@@ -47,7 +47,7 @@ impl UserConfigService {
         // 保存到数据库
         self.repo.upsert_user_config(user_id, user_data)
             .await
-            .map_err(|e| AppError::Database(e))?;
+            .map_err(AppError::Database)?;
 
         // 同步到缓存
         BOT_CACHE.insert(user_id, user_data.clone()).await;
@@ -60,7 +60,7 @@ impl UserConfigService {
         // 从数据库删除
         self.repo.delete_user_config(user_id)
             .await
-            .map_err(|e| AppError::Database(e))?;
+            .map_err(AppError::Database)?;
 
         // 从缓存删除
         BOT_CACHE.remove(&user_id).await;
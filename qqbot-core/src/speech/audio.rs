@@ -0,0 +1,234 @@
+use std::time::Duration;
+
+use base64::{Engine as _, engine::general_purpose};
+use tokio::process::Command;
+
+use crate::error::{AppError, AppResult};
+
+/// 语音消息常见的音频格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Silk,
+    Amr,
+    Mp3,
+    Wav,
+    Ogg,
+    Flac,
+    Unknown,
+}
+
+impl AudioFormat {
+    /// 根据文件头识别格式，QQ 语音的 silk 文件头前可能带有一个 0x02 字节
+    pub fn sniff(bytes: &[u8]) -> Self {
+        let body = bytes.strip_prefix(&[0x02]).unwrap_or(bytes);
+        if body.starts_with(b"#!SILK") {
+            AudioFormat::Silk
+        } else if bytes.starts_with(b"#!AMR") {
+            AudioFormat::Amr
+        } else if bytes.starts_with(b"ID3") || (bytes.len() > 1 && bytes[0] == 0xFF && bytes[1] & 0xE0 == 0xE0) {
+            AudioFormat::Mp3
+        } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WAVE" {
+            AudioFormat::Wav
+        } else if bytes.starts_with(b"OggS") {
+            AudioFormat::Ogg
+        } else if bytes.starts_with(b"fLaC") {
+            AudioFormat::Flac
+        } else {
+            AudioFormat::Unknown
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Silk => "silk",
+            AudioFormat::Amr => "amr",
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Wav => "wav",
+            AudioFormat::Ogg => "ogg",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Unknown => "bin",
+        }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            AudioFormat::Silk => "audio/silk",
+            AudioFormat::Amr => "audio/amr",
+            AudioFormat::Mp3 => "audio/mpeg",
+            AudioFormat::Wav => "audio/wav",
+            AudioFormat::Ogg => "audio/ogg",
+            AudioFormat::Flac => "audio/flac",
+            AudioFormat::Unknown => "application/octet-stream",
+        }
+    }
+
+    /// 语音识别接口普遍支持的格式，其余格式需要先解码
+    pub fn is_supported(&self) -> bool {
+        matches!(self, AudioFormat::Mp3 | AudioFormat::Wav | AudioFormat::Ogg | AudioFormat::Flac)
+    }
+}
+
+/// 语音数据的来源，对应 OneBot `get_record` 返回的 base64、本地路径或消息段中的 url
+#[derive(Debug, Clone)]
+pub enum AudioSource {
+    Base64(String),
+    Path(String),
+    Url(String),
+}
+
+impl AudioSource {
+    pub async fn load(&self, max_bytes: usize) -> AppResult<Vec<u8>> {
+        let bytes = match self {
+            AudioSource::Base64(data) => general_purpose::STANDARD
+                .decode(data.trim_start_matches("base64://"))
+                .map_err(|e| AppError::speech(format!("语音数据解码失败: {}", e)))?,
+            AudioSource::Path(path) => tokio::fs::read(path.trim_start_matches("file://"))
+                .await
+                .map_err(|e| AppError::speech(format!("读取语音文件失败: {}", e)))?,
+            AudioSource::Url(url) => {
+                let response = reqwest::get(url.as_str()).await?.error_for_status()?;
+                response.bytes().await?.to_vec()
+            }
+        };
+        if bytes.len() > max_bytes {
+            return Err(AppError::speech(format!("语音文件过大: {} 字节", bytes.len())));
+        }
+        Ok(bytes)
+    }
+}
+
+/// QQ 语音 silk 解码后的 PCM 采样率，需与 silk_decoder_command 的 -Fs_API 一致
+pub const SILK_SAMPLE_RATE: u32 = 24000;
+
+/// 将接口不支持的格式（silk/amr 等）通过外部命令解码为 wav，
+/// 配置了 silk_decoder_command 时 silk 语音改用它解码
+pub async fn normalize(
+    bytes: Vec<u8>,
+    decoder_command: &str,
+    silk_decoder_command: Option<&str>,
+    timeout: Duration,
+) -> AppResult<(Vec<u8>, AudioFormat)> {
+    let format = AudioFormat::sniff(&bytes);
+    if format.is_supported() {
+        return Ok((bytes, format));
+    }
+    if let (AudioFormat::Silk, Some(command)) = (format, silk_decoder_command) {
+        return decode_silk(&bytes, command, timeout).await;
+    }
+
+    let dir = tempfile::tempdir().map_err(|e| AppError::speech(e.to_string()))?;
+    let input = dir.path().join(format!("voice.{}", format.extension()));
+    let output = dir.path().join("voice.wav");
+    tokio::fs::write(&input, &bytes)
+        .await
+        .map_err(|e| AppError::speech(e.to_string()))?;
+
+    run_command(
+        decoder_command,
+        &[("input", &input.to_string_lossy()), ("output", &output.to_string_lossy())],
+        timeout,
+    )
+    .await?;
+    let wav = tokio::fs::read(&output)
+        .await
+        .map_err(|e| AppError::speech(format!("解码 {} 语音失败: {}", format.extension(), e)))?;
+    Ok((wav, AudioFormat::Wav))
+}
+
+/// 用 SILK SDK 的 decoder 将 silk 解码为 16 位单声道 PCM，再封装为 wav。
+/// ffmpeg 不支持 QQ 的 silk 格式，decoder 也不识别开头的 0x02 字节，需先去掉
+async fn decode_silk(bytes: &[u8], command: &str, timeout: Duration) -> AppResult<(Vec<u8>, AudioFormat)> {
+    let dir = tempfile::tempdir().map_err(|e| AppError::speech(e.to_string()))?;
+    let input = dir.path().join("voice.silk");
+    let output = dir.path().join("voice.pcm");
+    tokio::fs::write(&input, bytes.strip_prefix(&[0x02]).unwrap_or(bytes))
+        .await
+        .map_err(|e| AppError::speech(e.to_string()))?;
+
+    run_command(
+        command,
+        &[("input", &input.to_string_lossy()), ("output", &output.to_string_lossy())],
+        timeout,
+    )
+    .await?;
+    let pcm = tokio::fs::read(&output)
+        .await
+        .map_err(|e| AppError::speech(format!("解码 silk 语音失败: {}", e)))?;
+    Ok((pcm_to_wav(&pcm, SILK_SAMPLE_RATE), AudioFormat::Wav))
+}
+
+/// 为 16 位单声道 PCM 加上 wav 文件头
+fn pcm_to_wav(pcm: &[u8], sample_rate: u32) -> Vec<u8> {
+    let data_len = pcm.len() as u32;
+    let mut wav = Vec::with_capacity(44 + pcm.len());
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.extend_from_slice(pcm);
+    wav
+}
+
+/// 执行命令模板，模板中的 {name} 按 vars 替换（如 {input}、{output}），返回标准输出
+pub async fn run_command(template: &str, vars: &[(&str, &str)], timeout: Duration) -> AppResult<String> {
+    // 先按空白拆分再替换，替换后的值即使包含空格也作为单个参数传递
+    let args: Vec<String> = template
+        .split_whitespace()
+        .map(|arg| {
+            vars.iter().fold(arg.to_string(), |arg, (name, value)| {
+                arg.replace(&format!("{{{}}}", name), value)
+            })
+        })
+        .collect();
+    let (program, args) = args
+        .split_first()
+        .ok_or_else(|| AppError::config("语音处理命令为空"))?;
+
+    let result = tokio::time::timeout(timeout, Command::new(program).args(args).kill_on_drop(true).output())
+        .await
+        .map_err(|_| AppError::speech(format!("{} 执行超时", program)))?
+        .map_err(|e| AppError::speech(format!("无法执行 {}: {}", program, e)))?;
+
+    if !result.status.success() {
+        return Err(AppError::speech(format!(
+            "{} 执行失败: {}",
+            program,
+            String::from_utf8_lossy(&result.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&result.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff_audio_format() {
+        assert_eq!(AudioFormat::sniff(b"\x02#!SILK_V3\x0c\x00"), AudioFormat::Silk);
+        assert_eq!(AudioFormat::sniff(b"#!SILK_V3"), AudioFormat::Silk);
+        assert_eq!(AudioFormat::sniff(b"#!AMR\n"), AudioFormat::Amr);
+        assert_eq!(AudioFormat::sniff(b"ID3\x04\x00"), AudioFormat::Mp3);
+        assert_eq!(AudioFormat::sniff(b"RIFF\x24\x00\x00\x00WAVEfmt "), AudioFormat::Wav);
+        assert_eq!(AudioFormat::sniff(b"hello"), AudioFormat::Unknown);
+        assert!(!AudioFormat::Silk.is_supported());
+    }
+
+    #[test]
+    fn test_pcm_to_wav() {
+        let wav = pcm_to_wav(&[0u8; 100], 24000);
+        assert_eq!(wav.len(), 144);
+        assert_eq!(AudioFormat::sniff(&wav), AudioFormat::Wav);
+        assert_eq!(&wav[4..8], &136u32.to_le_bytes());
+        assert_eq!(&wav[24..28], &24000u32.to_le_bytes());
+        assert_eq!(&wav[40..44], &100u32.to_le_bytes());
+    }
+}
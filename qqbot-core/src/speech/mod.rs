@@ -0,0 +1,5 @@
+//! 语音消息处理：音频格式识别与解码、语音转文字、文字转语音
+
+pub mod audio;
+pub mod stt;
+pub mod tts;
@@ -0,0 +1,141 @@
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use reqwest::{
+    Client,
+    multipart::{Form, Part},
+};
+use serde::Deserialize;
+
+use super::audio::{self, AudioFormat, AudioSource};
+use crate::{
+    config::{APPCONFIG, app_config::SttBackend},
+    error::{AppError, AppResult},
+};
+
+/// 语音转文字后端
+#[async_trait]
+pub trait SpeechToText: Send + Sync {
+    async fn transcribe(&self, audio: Vec<u8>, format: AudioFormat) -> AppResult<String>;
+}
+
+/// OpenAI 兼容的 `/audio/transcriptions` 接口
+pub struct OpenAiTranscriber {
+    client: Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+}
+
+#[derive(Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+}
+
+impl OpenAiTranscriber {
+    pub fn new() -> Self {
+        let stt = &APPCONFIG.stt;
+        Self {
+            client: Client::builder()
+                .timeout(stt.timeout)
+                .build()
+                .expect("Failed to create HTTP client"),
+            base_url: stt.base_url.clone().unwrap_or_else(|| APPCONFIG.llm.base_url.clone()),
+            api_key: stt.api_key.clone().unwrap_or_else(|| APPCONFIG.llm.api_key.clone()),
+            model: stt.model.clone(),
+        }
+    }
+}
+
+impl Default for OpenAiTranscriber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SpeechToText for OpenAiTranscriber {
+    async fn transcribe(&self, audio: Vec<u8>, format: AudioFormat) -> AppResult<String> {
+        let file = Part::bytes(audio)
+            .file_name(format!("voice.{}", format.extension()))
+            .mime_str(format.mime())?;
+        let form = Form::new().text("model", self.model.clone()).part("file", file);
+
+        let response = self
+            .client
+            .post(format!("{}/audio/transcriptions", self.base_url))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .multipart(form)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::speech(format!("语音识别接口返回错误 {}: {}", status, error_text)));
+        }
+        Ok(response.json::<TranscriptionResponse>().await?.text)
+    }
+}
+
+/// 本地命令后端，例如 whisper.cpp，命令的标准输出即识别结果
+pub struct CommandTranscriber {
+    command: String,
+}
+
+impl CommandTranscriber {
+    pub fn new(command: String) -> Self {
+        Self { command }
+    }
+}
+
+#[async_trait]
+impl SpeechToText for CommandTranscriber {
+    async fn transcribe(&self, audio: Vec<u8>, format: AudioFormat) -> AppResult<String> {
+        let dir = tempfile::tempdir().map_err(|e| AppError::speech(e.to_string()))?;
+        let input = dir.path().join(format!("voice.{}", format.extension()));
+        tokio::fs::write(&input, &audio)
+            .await
+            .map_err(|e| AppError::speech(e.to_string()))?;
+        audio::run_command(&self.command, &[("input", &input.to_string_lossy())], APPCONFIG.stt.timeout).await
+    }
+}
+
+/// 根据配置创建的语音识别后端，未启用时为 None
+pub static STT_BACKEND: Lazy<Option<Box<dyn SpeechToText>>> = Lazy::new(|| {
+    let stt = &APPCONFIG.stt;
+    if !stt.enabled {
+        return None;
+    }
+    match stt.backend {
+        SttBackend::OpenAi => Some(Box::new(OpenAiTranscriber::new())),
+        SttBackend::Command => match &stt.command {
+            Some(command) => Some(Box::new(CommandTranscriber::new(command.clone()))),
+            None => {
+                log::warn!("stt.backend 为 command 但未配置 stt.command，语音转写已禁用");
+                None
+            }
+        },
+    }
+});
+
+/// 语音转写是否可用
+pub fn is_enabled() -> bool {
+    STT_BACKEND.is_some()
+}
+
+/// 下载、解码并转写一条语音，返回识别出的文字
+pub async fn transcribe_voice(source: &AudioSource) -> AppResult<String> {
+    let backend = STT_BACKEND
+        .as_ref()
+        .ok_or_else(|| AppError::config("语音转写未启用"))?;
+    let stt = &APPCONFIG.stt;
+
+    let bytes = source.load(stt.max_audio_bytes).await?;
+    let (audio, format) = audio::normalize(bytes, &stt.decoder_command, stt.silk_decoder_command.as_deref(), stt.timeout).await?;
+    let text = backend.transcribe(audio, format).await?;
+
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err(AppError::speech("未识别到语音内容"));
+    }
+    Ok(text)
+}
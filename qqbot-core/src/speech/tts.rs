@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde_json::json;
+
+use super::audio;
+use crate::{
+    config::{APPCONFIG, app_config::TtsBackend},
+    error::{AppError, AppResult},
+    reply_strategy::{MediaInfo, MessageContent, MessageSegment},
+};
+
+/// 文字转语音后端，返回可直接作为 record 消息段发送的音频数据
+#[async_trait]
+pub trait TextToSpeech: Send + Sync {
+    async fn synthesize(&self, text: &str) -> AppResult<Vec<u8>>;
+}
+
+/// OpenAI 兼容的 `/audio/speech` 接口
+pub struct OpenAiSynthesizer {
+    client: Client,
+    base_url: String,
+    api_key: String,
+    model: String,
+    voice: String,
+}
+
+impl OpenAiSynthesizer {
+    pub fn new() -> Self {
+        let tts = &APPCONFIG.tts;
+        Self {
+            client: Client::builder()
+                .timeout(tts.timeout)
+                .build()
+                .expect("Failed to create HTTP client"),
+            base_url: tts.base_url.clone().unwrap_or_else(|| APPCONFIG.llm.base_url.clone()),
+            api_key: tts.api_key.clone().unwrap_or_else(|| APPCONFIG.llm.api_key.clone()),
+            model: tts.model.clone(),
+            voice: tts.voice.clone(),
+        }
+    }
+}
+
+impl Default for OpenAiSynthesizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TextToSpeech for OpenAiSynthesizer {
+    async fn synthesize(&self, text: &str) -> AppResult<Vec<u8>> {
+        let response = self
+            .client
+            .post(format!("{}/audio/speech", self.base_url))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .json(&json!({
+                "model": self.model,
+                "input": text,
+                "voice": self.voice,
+                "response_format": "mp3",
+            }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::speech(format!("语音合成接口返回错误 {}: {}", status, error_text)));
+        }
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+/// 本地命令后端，例如 edge-tts，命令将音频写入 {output}
+pub struct CommandSynthesizer {
+    command: String,
+}
+
+impl CommandSynthesizer {
+    pub fn new(command: String) -> Self {
+        Self { command }
+    }
+}
+
+#[async_trait]
+impl TextToSpeech for CommandSynthesizer {
+    async fn synthesize(&self, text: &str) -> AppResult<Vec<u8>> {
+        let dir = tempfile::tempdir().map_err(|e| AppError::speech(e.to_string()))?;
+        let output = dir.path().join("reply.mp3");
+        audio::run_command(
+            &self.command,
+            &[("text", text), ("output", &output.to_string_lossy())],
+            APPCONFIG.tts.timeout,
+        )
+        .await?;
+        tokio::fs::read(&output)
+            .await
+            .map_err(|e| AppError::speech(format!("读取合成的语音失败: {}", e)))
+    }
+}
+
+/// 根据配置创建的语音合成后端，未启用时为 None
+pub static TTS_BACKEND: Lazy<Option<Box<dyn TextToSpeech>>> = Lazy::new(|| {
+    let tts = &APPCONFIG.tts;
+    if !tts.enabled {
+        return None;
+    }
+    match tts.backend {
+        TtsBackend::OpenAi => Some(Box::new(OpenAiSynthesizer::new())),
+        TtsBackend::Command => match &tts.command {
+            Some(command) => Some(Box::new(CommandSynthesizer::new(command.clone()))),
+            None => {
+                log::warn!("tts.backend 为 command 但未配置 tts.command，语音回复已禁用");
+                None
+            }
+        },
+    }
+});
+
+/// 语音回复是否可用
+pub fn is_enabled() -> bool {
+    TTS_BACKEND.is_some()
+}
+
+/// 将文字回复合成为 record 消息段，超过字数限制或未启用时返回 None
+pub async fn synthesize_reply(text: &str) -> AppResult<Option<MessageContent>> {
+    let Some(backend) = TTS_BACKEND.as_ref() else {
+        return Ok(None);
+    };
+    let text = text.trim();
+    if text.is_empty() || text.chars().count() > APPCONFIG.tts.max_chars {
+        return Ok(None);
+    }
+
+    let audio = backend.synthesize(text).await?;
+    let media = MediaInfo {
+        file: format!("base64://{}", general_purpose::STANDARD.encode(&audio)),
+        ..Default::default()
+    };
+    Ok(Some(MessageContent::Mixed(vec![MessageSegment::Record { media }])))
+}
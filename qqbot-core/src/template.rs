@@ -0,0 +1,118 @@
+use minijinja::Environment;
+use serde::Serialize;
+
+use crate::error::{AppError, AppResult};
+
+/// `/query grade` 的成绩汇总
+pub const QUERY_GRADE_SUMMARY: &str = "query.grade_summary";
+/// 成绩发布后发给学生的私聊通知
+pub const NOTIFY_GRADE_PUBLISHED: &str = "notify.grade_published";
+/// `/push` 推送给每个成员的消息外壳，`message` 为管理员输入的内容
+pub const PUSH_MESSAGE: &str = "push.message";
+
+/// 内置模板定义，数据库中没有自定义内容时使用
+pub struct TemplateDef {
+    pub key: &'static str,
+    pub description: &'static str,
+    pub content: &'static str,
+}
+
+pub const DEFAULT_TEMPLATES: &[TemplateDef] = &[
+    TemplateDef {
+        key: QUERY_GRADE_SUMMARY,
+        description: "成绩查询结果，可用变量: student, grades",
+        content: "{% for grade in grades %}
+{{ grade.student_name }} 在 {{ grade.exam_name }} 中获得 {{ grade.score }} 分;
+{% if grade.score < 60 %}
+请多花时间，微积分对未来的课程非常重要，务必掌握
+{% endif %}
+{% endfor %}",
+    },
+    TemplateDef {
+        key: NOTIFY_GRADE_PUBLISHED,
+        description: "成绩发布通知，可用变量: student, grade",
+        content: "📢 {{ student.name }} 同学你好，{{ grade.exam_name }} 的成绩已发布：{{ grade.score }} 分
+💡 发送 /query grade 可查看全部成绩",
+    },
+    TemplateDef {
+        key: PUSH_MESSAGE,
+        description: "推送消息，可用变量: message, student, member",
+        content: "{{ message }}",
+    },
+];
+
+/// 查找内置模板
+pub fn default_template(key: &str) -> Option<&'static TemplateDef> {
+    DEFAULT_TEMPLATES.iter().find(|t| t.key == key)
+}
+
+fn environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    // 块标签独占一行时不输出多余的换行和缩进
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env
+}
+
+/// 渲染模板源码，ctx 为任意可序列化的上下文
+pub fn render_str<S: Serialize>(source: &str, ctx: S) -> AppResult<String> {
+    environment()
+        .render_str(source, ctx)
+        .map_err(|err| AppError::template(err.to_string()))
+}
+
+/// 检查模板语法，保存前调用
+pub fn validate(source: &str) -> AppResult<()> {
+    environment()
+        .template_from_str(source)
+        .map(|_| ())
+        .map_err(|err| AppError::template(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_default_templates() {
+        let summary = default_template(QUERY_GRADE_SUMMARY).unwrap();
+        let output = render_str(
+            summary.content,
+            json!({
+                "student": { "name": "张三" },
+                "grades": [
+                    { "student_name": "张三", "exam_name": "Quiz-1", "score": 90 },
+                    { "student_name": "张三", "exam_name": "Mid", "score": 50 },
+                ],
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            output,
+            "张三 在 Quiz-1 中获得 90 分;\n张三 在 Mid 中获得 50 分;\n请多花时间，微积分对未来的课程非常重要，务必掌握\n"
+        );
+
+        let notify = default_template(NOTIFY_GRADE_PUBLISHED).unwrap();
+        let output = render_str(
+            notify.content,
+            json!({
+                "student": { "name": "李四" },
+                "grade": { "exam_name": "Mid", "score": 88 },
+            }),
+        )
+        .unwrap();
+        assert!(output.starts_with("📢 李四 同学你好，Mid 的成绩已发布：88 分"));
+
+        // 推送内容只是变量，其中的模板语法原样输出
+        let push = default_template(PUSH_MESSAGE).unwrap();
+        let output = render_str(
+            push.content,
+            json!({ "message": "{{ student.name }} {% for x in range(9999999) %}x{% endfor %}" }),
+        )
+        .unwrap();
+        assert_eq!(output, "{{ student.name }} {% for x in range(9999999) %}x{% endfor %}");
+
+        assert!(validate("{% for x in %}").is_err());
+    }
+}